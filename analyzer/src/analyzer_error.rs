@@ -37,4 +37,27 @@ pub enum AnalyzerDiagnosticError {
   ImportedFunctionIsNotExported(Token),
   BreakOutsideLoop(Token),
   ContinueOutsideLoop(Token),
+  ThisOutsideMethod(Token),
+  UndefinedClass(Token),
+  UndefinedProperty(String, Token),
+  PrivateMemberAccess(String, Token),
+  ReadonlyPropertyAssignment(String, Token),
+  InvalidMemberAccess(DataType, Token),
+  EnumAlreadyDefined(String, Token),
+  UndefinedEnumVariant(String, Token),
+  InvalidEnumValue(String, Token),
+  NonExhaustiveMatch(String, Vec<String>, Token),
+  UnreachableMatchArm(Token),
+  DuplicateSwitchCase(String, Token),
+  InvalidTemplateExpression(DataType, Token),
+  NotIndexable(DataType, Token),
+  InvalidIndexType(DataType, Token),
+  TypeAlreadyDefined(String, Token),
+  UndefinedInterface(Token),
+  MissingInterfaceMember(String, String, String, Token),
+  InterfaceMemberMismatch(String, String, DataType, DataType, Token),
+  CannotInferType(Token),
+  ReturnTypeMismatch(DataType, DataType, Token),
+  MissingReturn(Token),
+  UnassignedVariable(Token),
 }
@@ -47,7 +47,8 @@ impl AnalyzerValue {
       AnalyzerValue::Int(_) => DataType::Int,
       AnalyzerValue::Float(_) => DataType::Float,
       AnalyzerValue::Boolean(_) => DataType::Boolean,
-      AnalyzerValue::None | AnalyzerValue::Null => DataType::None,
+      AnalyzerValue::Null => DataType::Null,
+      AnalyzerValue::None => DataType::None,
       AnalyzerValue::Return(r) => r.to_data_type(),
       AnalyzerValue::Function(f) => {
         let value = f.return_type.as_ref();
@@ -0,0 +1,9 @@
+use lexer::token::Token;
+
+/**
+  Problems that do not stop the compilation, the code still runs.
+*/
+#[derive(Debug, Clone)]
+pub enum AnalyzerDiagnosticWarning {
+  UnreachableCode(Token),
+}
@@ -0,0 +1,504 @@
+use std::collections::HashSet;
+
+use lexer::token::Token;
+
+use crate::{analyzer_value::AnalyzerValue, ir::instruction::IRInstruction};
+
+const ENTRY: usize = 0;
+const EXIT: usize = 1;
+const END: usize = 2;
+
+/**
+  A node of a `ControlFlowGraph`.
+  - Instruction: a statement, or the condition of an `if` or a loop.
+  - Exit: reached by the `return` statements.
+  - End: reached when the execution gets to the end of the body.
+*/
+enum ControlFlowNode<'a> {
+  Entry,
+  Instruction(&'a IRInstruction),
+  Exit,
+  End,
+}
+
+/**
+  The statements a `break` or a `continue` jumps out of. `start` is the node
+  a `continue` goes back to, `None` for a `switch`.
+*/
+struct Breakable {
+  start: Option<usize>,
+  breaks: Vec<usize>,
+}
+
+/**
+  The statements of a body and the order they can run in. An edge goes from
+  a node to every node that can run after it.
+*/
+pub struct ControlFlowGraph<'a> {
+  nodes: Vec<ControlFlowNode<'a>>,
+  successors: Vec<Vec<usize>>,
+  breakables: Vec<Breakable>,
+  unreachable: Vec<&'a IRInstruction>,
+}
+
+impl<'a> ControlFlowGraph<'a> {
+  pub fn new(body: &'a [IRInstruction]) -> Self {
+    let mut graph = Self {
+      nodes: vec![
+        ControlFlowNode::Entry,
+        ControlFlowNode::Exit,
+        ControlFlowNode::End,
+      ],
+      successors: vec![Vec::new(), Vec::new(), Vec::new()],
+      breakables: Vec::new(),
+      unreachable: Vec::new(),
+    };
+
+    let ends = graph.add_statements(body, vec![ENTRY]);
+    graph.link(&ends, END);
+
+    graph
+  }
+
+  /**
+    The execution can get to the end of the body without a `return`.
+  */
+  pub fn falls_through(&self) -> bool {
+    self.reachable()[END]
+  }
+
+  /**
+    The first statement of every piece of code that can not run, like the
+    statements after a `return`.
+  */
+  pub fn unreachable_code(&self) -> Vec<Token> {
+    self
+      .unreachable
+      .iter()
+      .filter_map(|instruction| Self::token(instruction))
+      .collect()
+  }
+
+  /**
+    The reads of the variables declared without a value, `let x: int;`, that
+    can run before a value is assigned to them.
+  */
+  pub fn unassigned_reads(&self) -> Vec<Token> {
+    let declared: HashSet<&str> = self
+      .nodes
+      .iter()
+      .filter_map(|node| match node {
+        ControlFlowNode::Instruction(IRInstruction::Variable(variable))
+          if variable.metadata.is_declaration && variable.value.is_none() =>
+        {
+          Some(variable.name.as_str())
+        }
+        _ => None,
+      })
+      .collect();
+
+    if declared.is_empty() {
+      return Vec::new();
+    }
+
+    let reachable = self.reachable();
+    let mut predecessors: Vec<Vec<usize>> = vec![Vec::new(); self.nodes.len()];
+
+    for (node, successors) in self.successors.iter().enumerate() {
+      for successor in successors {
+        predecessors[*successor].push(node);
+      }
+    }
+
+    // The variables that are assigned after each node on every path to it.
+    let mut assigned: Vec<HashSet<&str>> = vec![declared.clone(); self.nodes.len()];
+    let mut changed = true;
+
+    while changed {
+      changed = false;
+
+      for node in (0..self.nodes.len()).filter(|node| reachable[*node]) {
+        let mut state = self.assigned_before(node, &predecessors, &reachable, &assigned);
+
+        if let ControlFlowNode::Instruction(instruction) = self.nodes[node] {
+          Self::assign(instruction, &declared, &mut state, &mut Vec::new());
+        }
+
+        if state != assigned[node] {
+          assigned[node] = state;
+          changed = true;
+        }
+      }
+    }
+
+    let mut reads: Vec<Token> = Vec::new();
+
+    for node in (0..self.nodes.len()).filter(|node| reachable[*node]) {
+      let mut state = self.assigned_before(node, &predecessors, &reachable, &assigned);
+
+      if let ControlFlowNode::Instruction(instruction) = self.nodes[node] {
+        Self::assign(instruction, &declared, &mut state, &mut reads);
+      }
+    }
+
+    reads
+  }
+
+  fn assigned_before(
+    &self,
+    node: usize,
+    predecessors: &[Vec<usize>],
+    reachable: &[bool],
+    assigned: &[HashSet<&'a str>],
+  ) -> HashSet<&'a str> {
+    if node == ENTRY {
+      return HashSet::new();
+    }
+
+    let mut states = predecessors[node]
+      .iter()
+      .filter(|predecessor| reachable[**predecessor])
+      .map(|predecessor| &assigned[*predecessor]);
+
+    let first = states.next().cloned().unwrap_or_default();
+
+    states.fold(first, |state, other| {
+      state.intersection(other).copied().collect()
+    })
+  }
+
+  /**
+    Runs `instruction` on the variables of `declared` that are assigned,
+    the reads of the ones that are not are added to `reads`.
+  */
+  fn assign(
+    instruction: &'a IRInstruction,
+    declared: &HashSet<&'a str>,
+    assigned: &mut HashSet<&'a str>,
+    reads: &mut Vec<Token>,
+  ) {
+    match instruction {
+      IRInstruction::Variable(variable) if variable.metadata.is_declaration => {
+        match &variable.value {
+          Some(value) => {
+            Self::assign(value, declared, assigned, reads);
+            assigned.insert(variable.name.as_str());
+          }
+          None => {
+            assigned.remove(variable.name.as_str());
+          }
+        }
+      }
+      IRInstruction::Variable(variable) => {
+        let name = variable.name.as_str();
+
+        if declared.contains(name) && !assigned.contains(name) {
+          reads.extend(variable.token.clone());
+        }
+      }
+      IRInstruction::Assign(assign) => {
+        Self::assign(&assign.value, declared, assigned, reads);
+        assigned.insert(assign.name.as_str());
+      }
+      // The right side does not always run.
+      IRInstruction::Logical(logical) => {
+        Self::assign(&logical.left, declared, assigned, reads);
+
+        let before = assigned.clone();
+        Self::assign(&logical.right, declared, assigned, reads);
+        *assigned = before;
+      }
+      IRInstruction::Ternary(ternary) => {
+        Self::assign(&ternary.condition, declared, assigned, reads);
+
+        let mut then_branch = assigned.clone();
+        Self::assign(&ternary.then_branch, declared, &mut then_branch, reads);
+        Self::assign(&ternary.else_branch, declared, assigned, reads);
+
+        assigned.retain(|name| then_branch.contains(name));
+      }
+      // Functions, lambdas and classes run their bodies when they are called.
+      IRInstruction::Function(_) | IRInstruction::Lambda(_) | IRInstruction::Class(_) => (),
+      instruction => {
+        for child in Self::children(instruction) {
+          Self::assign(child, declared, assigned, reads);
+        }
+      }
+    }
+  }
+
+  /**
+    The instructions that `instruction` runs, in the order they run.
+  */
+  fn children(instruction: &'a IRInstruction) -> Vec<&'a IRInstruction> {
+    match instruction {
+      IRInstruction::Binary(binary) => vec![&binary.left, &binary.right],
+      IRInstruction::Unary(unary) => vec![&unary.right],
+      IRInstruction::Call(call) => call.arguments.iter().collect(),
+      IRInstruction::CallValue(call) => std::iter::once(&*call.callee)
+        .chain(&call.arguments)
+        .collect(),
+      IRInstruction::MethodCall(call) => std::iter::once(&*call.object)
+        .chain(&call.arguments)
+        .collect(),
+      IRInstruction::New(new) => new.arguments.iter().collect(),
+      IRInstruction::EnumValue(enum_value) => enum_value.arguments.iter().collect(),
+      IRInstruction::Get(get) => vec![&get.object],
+      IRInstruction::Set(set) => vec![&set.object, &set.value],
+      IRInstruction::Index(index) => vec![&index.object, &index.index],
+      IRInstruction::SetIndex(set) => vec![&set.object, &set.index, &set.value],
+      IRInstruction::Array(array) => array.elements.iter().collect(),
+      IRInstruction::TemplateLiteral(template) => template.parts.iter().collect(),
+      IRInstruction::Return(ir_return) => vec![&ir_return.value],
+      IRInstruction::Block(block) => block.instructions.iter().collect(),
+      IRInstruction::Match(ir_match) => {
+        let mut children = vec![&*ir_match.value];
+
+        for arm in &ir_match.arms {
+          children.extend(arm.guard.as_deref());
+          children.push(&arm.body);
+        }
+
+        children
+      }
+      _ => Vec::new(),
+    }
+  }
+
+  fn reachable(&self) -> Vec<bool> {
+    let mut reachable = vec![false; self.nodes.len()];
+    let mut pending = vec![ENTRY];
+
+    while let Some(node) = pending.pop() {
+      if reachable[node] {
+        continue;
+      }
+
+      reachable[node] = true;
+      pending.extend(&self.successors[node]);
+    }
+
+    reachable
+  }
+
+  fn add_node(&mut self, node: ControlFlowNode<'a>, predecessors: &[usize]) -> usize {
+    let index = self.nodes.len();
+
+    self.nodes.push(node);
+    self.successors.push(Vec::new());
+    self.link(predecessors, index);
+
+    index
+  }
+
+  fn link(&mut self, from: &[usize], to: usize) {
+    for node in from {
+      self.successors[*node].push(to);
+    }
+  }
+
+  /**
+    Adds the statements of a block after the nodes `predecessors`, returns
+    the nodes the execution continues from after the block.
+  */
+  fn add_statements(
+    &mut self,
+    statements: &'a [IRInstruction],
+    predecessors: Vec<usize>,
+  ) -> Vec<usize> {
+    // A block that can not run was reported with the code before it.
+    let mut is_reported = predecessors.is_empty();
+    let mut current = predecessors;
+
+    for statement in statements {
+      if current.is_empty() && !is_reported {
+        self.unreachable.push(statement);
+        is_reported = true;
+      }
+
+      current = self.add_statement(statement, current);
+    }
+
+    current
+  }
+
+  fn add_statement(
+    &mut self,
+    statement: &'a IRInstruction,
+    predecessors: Vec<usize>,
+  ) -> Vec<usize> {
+    match statement {
+      IRInstruction::Block(block) => self.add_statements(&block.instructions, predecessors),
+      IRInstruction::If(ir_if) => {
+        let condition = self.add_node(
+          ControlFlowNode::Instruction(&ir_if.condition),
+          &predecessors,
+        );
+        let mut ends = self.add_statement(&ir_if.then_branch, vec![condition]);
+
+        match &ir_if.else_branch {
+          Some(else_branch) => ends.extend(self.add_statement(else_branch, vec![condition])),
+          None => ends.push(condition),
+        }
+
+        ends
+      }
+      IRInstruction::While(ir_while) => {
+        let condition = self.add_node(
+          ControlFlowNode::Instruction(&ir_while.condition),
+          &predecessors,
+        );
+
+        let mut exits = self.add_loop(&ir_while.body, condition);
+
+        // `while (true)` only ends with a `break`.
+        if !matches!(
+          &*ir_while.condition,
+          IRInstruction::Literal(literal) if matches!(literal.value, AnalyzerValue::Boolean(true))
+        ) {
+          exits.push(condition);
+        }
+
+        exits
+      }
+      IRInstruction::ForIn(for_in) => {
+        let iterable = self.add_node(
+          ControlFlowNode::Instruction(&for_in.iterable),
+          &predecessors,
+        );
+
+        let mut exits = self.add_loop(&for_in.body, iterable);
+        exits.push(iterable);
+
+        exits
+      }
+      IRInstruction::Switch(switch) => {
+        let value = self.add_node(ControlFlowNode::Instruction(&switch.value), &predecessors);
+
+        self.breakables.push(Breakable {
+          start: None,
+          breaks: Vec::new(),
+        });
+
+        // The execution goes on with the next case at the end of a case.
+        let mut ends: Vec<usize> = Vec::new();
+
+        for case in &switch.cases {
+          ends.push(value);
+          ends = self.add_statements(&case.body, ends);
+        }
+
+        ends.extend(self.breakables.pop().unwrap().breaks);
+
+        if switch.default_case().is_none() {
+          ends.push(value);
+        }
+
+        ends
+      }
+      IRInstruction::Match(ir_match) => {
+        let value = self.add_node(ControlFlowNode::Instruction(&ir_match.value), &predecessors);
+        let mut ends: Vec<usize> = Vec::new();
+
+        for arm in &ir_match.arms {
+          let start = match &arm.guard {
+            Some(guard) => self.add_node(ControlFlowNode::Instruction(guard), &[value]),
+            None => value,
+          };
+
+          ends.extend(self.add_statement(&arm.body, vec![start]));
+        }
+
+        if !ir_match.is_exhaustive {
+          ends.push(value);
+        }
+
+        ends
+      }
+      IRInstruction::Return(_) => {
+        let node = self.add_node(ControlFlowNode::Instruction(statement), &predecessors);
+        self.link(&[node], EXIT);
+
+        Vec::new()
+      }
+      IRInstruction::Break(_) => {
+        let node = self.add_node(ControlFlowNode::Instruction(statement), &predecessors);
+
+        if let Some(breakable) = self.breakables.last_mut() {
+          breakable.breaks.push(node);
+        }
+
+        Vec::new()
+      }
+      IRInstruction::Continue(_) => {
+        let node = self.add_node(ControlFlowNode::Instruction(statement), &predecessors);
+
+        if let Some(start) = self
+          .breakables
+          .iter()
+          .rev()
+          .find_map(|breakable| breakable.start)
+        {
+          self.link(&[node], start);
+        }
+
+        Vec::new()
+      }
+      _ => vec![self.add_node(ControlFlowNode::Instruction(statement), &predecessors)],
+    }
+  }
+
+  /**
+    Adds the body of a loop that starts at the node `start`, returns the
+    `break` statements that leave it.
+  */
+  fn add_loop(&mut self, body: &'a IRInstruction, start: usize) -> Vec<usize> {
+    self.breakables.push(Breakable {
+      start: Some(start),
+      breaks: Vec::new(),
+    });
+
+    let ends = self.add_statement(body, vec![start]);
+    self.link(&ends, start);
+
+    self.breakables.pop().unwrap().breaks
+  }
+
+  /**
+    A token of the source of `instruction`, to point at it in a diagnostic.
+  */
+  fn token(instruction: &IRInstruction) -> Option<Token> {
+    match instruction {
+      IRInstruction::Variable(variable) => variable.token.clone(),
+      IRInstruction::Binary(binary) => Some(binary.token.clone()),
+      IRInstruction::Unary(unary) => Some(unary.token.clone()),
+      IRInstruction::Logical(logical) => Some(logical.token.clone()),
+      IRInstruction::Ternary(ternary) => Some(ternary.token.clone()),
+      IRInstruction::Literal(literal) => Some(literal.token.clone()),
+      IRInstruction::Assign(assign) => Some(assign.token.clone()),
+      IRInstruction::Call(call) => Some(call.token.clone()),
+      IRInstruction::CallValue(call) => Some(call.token.clone()),
+      IRInstruction::MethodCall(call) => Some(call.token.clone()),
+      IRInstruction::New(new) => Some(new.token.clone()),
+      IRInstruction::EnumValue(enum_value) => Some(enum_value.token.clone()),
+      IRInstruction::Get(get) => Some(get.token.clone()),
+      IRInstruction::Set(set) => Some(set.token.clone()),
+      IRInstruction::Index(index) => Some(index.token.clone()),
+      IRInstruction::SetIndex(set) => Some(set.token.clone()),
+      IRInstruction::Array(array) => Some(array.token.clone()),
+      IRInstruction::TemplateLiteral(template) => Some(template.token.clone()),
+      IRInstruction::This(this) => Some(this.token.clone()),
+      IRInstruction::Lambda(lambda) => Some(lambda.token.clone()),
+      IRInstruction::Match(ir_match) => Some(ir_match.token.clone()),
+      IRInstruction::Return(ir_return) => Some(ir_return.token.clone()),
+      IRInstruction::Break(ir_break) => Some(ir_break.token.clone()),
+      IRInstruction::Continue(ir_continue) => Some(ir_continue.token.clone()),
+      IRInstruction::ForIn(for_in) => Some(for_in.token.clone()),
+      IRInstruction::Switch(switch) => Some(switch.token.clone()),
+      IRInstruction::If(ir_if) => Self::token(&ir_if.condition),
+      IRInstruction::While(ir_while) => Self::token(&ir_while.condition),
+      IRInstruction::Block(block) => block.instructions.iter().find_map(Self::token),
+      _ => None,
+    }
+  }
+}
@@ -119,7 +119,207 @@ pub fn display_ir(instruction: &IRInstruction, indent_level: usize) {
       println!("{}Value:", indent_subtext);
       display_ir(&a.value, indent_level);
     }
-    IRInstruction::Class(_) => todo!(),
+    IRInstruction::Class(class) => {
+      println!("{}Class:", indent);
+      println!("{}Name: {}", indent_subtext, class.name);
+
+      println!("{}Fields:", indent_subtext);
+      for field in &class.fields {
+        println!(
+          "{}  {}: {:?} {:?}",
+          indent_subtext, field.name, field.data_type, field.metadata
+        );
+
+        if let Some(value) = &field.value {
+          display_ir(value, indent_level + 1);
+        }
+      }
+
+      if let Some(constructor) = &class.constructor {
+        println!("{}Constructor:", indent_subtext);
+        display_ir(
+          &IRInstruction::Function(constructor.function.clone()),
+          indent_level,
+        );
+      }
+
+      println!("{}Methods:", indent_subtext);
+      for method in &class.methods {
+        println!("{}  {:?}", indent_subtext, method.metadata);
+        display_ir(
+          &IRInstruction::Function(method.function.clone()),
+          indent_level,
+        );
+      }
+    }
+    IRInstruction::This(_) => {
+      println!("{}This", indent);
+    }
+    IRInstruction::Get(get) => {
+      println!("{}Get:", indent);
+      println!("{}Name: {}", indent_subtext, get.name);
+      println!("{}Object:", indent_subtext);
+      display_ir(&get.object, indent_level);
+    }
+    IRInstruction::Set(set) => {
+      println!("{}Set:", indent);
+      println!("{}Name: {}", indent_subtext, set.name);
+      println!("{}Object:", indent_subtext);
+      display_ir(&set.object, indent_level);
+      println!("{}Value:", indent_subtext);
+      display_ir(&set.value, indent_level);
+    }
+    IRInstruction::New(new) => {
+      println!("{}New:", indent);
+      println!("{}Class: {}", indent_subtext, new.class_name);
+      println!("{}Arguments:", indent_subtext);
+
+      for argument in &new.arguments {
+        display_ir(argument, indent_level);
+      }
+    }
+    IRInstruction::MethodCall(call) => {
+      println!("{}MethodCall:", indent);
+      println!("{}Name: {}", indent_subtext, call.name);
+      println!("{}Object:", indent_subtext);
+      display_ir(&call.object, indent_level);
+      println!("{}Arguments:", indent_subtext);
+
+      for argument in &call.arguments {
+        display_ir(argument, indent_level);
+      }
+    }
+    IRInstruction::Enum(ir_enum) => {
+      println!("{}Enum:", indent);
+      println!("{}Name: {}", indent_subtext, ir_enum.name);
+      println!("{}Generics: {:?}", indent_subtext, ir_enum.generics);
+      println!("{}Variants:", indent_subtext);
+
+      for variant in &ir_enum.variants {
+        println!(
+          "{}  {}: {:?} {:?}",
+          indent_subtext, variant.name, variant.fields, variant.value
+        );
+      }
+    }
+    IRInstruction::EnumValue(enum_value) => {
+      println!("{}EnumValue:", indent);
+      println!(
+        "{}Variant: {}.{}",
+        indent_subtext, enum_value.enum_name, enum_value.variant
+      );
+      println!("{}Type: {:?}", indent_subtext, enum_value.data_type);
+      println!("{}Arguments:", indent_subtext);
+
+      for argument in &enum_value.arguments {
+        display_ir(argument, indent_level);
+      }
+    }
+    IRInstruction::Match(ir_match) => {
+      println!("{}Match:", indent);
+      println!("{}Type: {:?}", indent_subtext, ir_match.data_type);
+      println!("{}Value:", indent_subtext);
+      display_ir(&ir_match.value, indent_level);
+
+      for arm in &ir_match.arms {
+        println!("{}Arm: {:?}", indent_subtext, arm.pattern);
+
+        if let Some(guard) = &arm.guard {
+          println!("{}Guard:", indent_subtext);
+          display_ir(guard, indent_level + 1);
+        }
+
+        println!("{}Body:", indent_subtext);
+        display_ir(&arm.body, indent_level + 1);
+      }
+    }
+    IRInstruction::Switch(switch) => {
+      println!("{}Switch:", indent);
+      println!("{}Value:", indent_subtext);
+      display_ir(&switch.value, indent_level);
+
+      for case in &switch.cases {
+        match &case.value {
+          Some(value) => {
+            println!("{}Case:", indent_subtext);
+            display_ir(value, indent_level + 1);
+          }
+          None => println!("{}Default:", indent_subtext),
+        }
+
+        for instruction in &case.body {
+          display_ir(instruction, indent_level + 1);
+        }
+      }
+    }
+    IRInstruction::Lambda(lambda) => {
+      println!("{}Lambda:", indent);
+      println!("{}Captures: {:?}", indent_subtext, lambda.captures);
+      display_ir(
+        &IRInstruction::Function(lambda.function.clone()),
+        indent_level,
+      );
+    }
+    IRInstruction::TemplateLiteral(template) => {
+      println!("{}TemplateLiteral:", indent);
+
+      for part in &template.parts {
+        display_ir(part, indent_level);
+      }
+    }
+    IRInstruction::Index(index) => {
+      println!("{}Index:", indent);
+      println!("{}Object:", indent_subtext);
+      display_ir(&index.object, indent_level);
+      println!("{}Index:", indent_subtext);
+      display_ir(&index.index, indent_level);
+    }
+    IRInstruction::SetIndex(set) => {
+      println!("{}SetIndex:", indent);
+      println!("{}Object:", indent_subtext);
+      display_ir(&set.object, indent_level);
+      println!("{}Index:", indent_subtext);
+      display_ir(&set.index, indent_level);
+      println!("{}Value:", indent_subtext);
+      display_ir(&set.value, indent_level);
+    }
+    IRInstruction::Interface(interface) => {
+      println!("{}Interface:", indent);
+      println!("{}Name: {}", indent_subtext, interface.name);
+
+      println!("{}Fields:", indent_subtext);
+      for field in &interface.fields {
+        println!("{}  {}: {:?}", indent_subtext, field.name, field.data_type);
+      }
+
+      println!("{}Methods:", indent_subtext);
+      for method in &interface.methods {
+        display_ir(
+          &IRInstruction::Function(method.function.clone()),
+          indent_level,
+        );
+      }
+    }
+    IRInstruction::TypeAlias(alias) => {
+      println!("{}TypeAlias:", indent);
+      println!("{}Name: {}", indent_subtext, alias.name);
+      println!("{}Type: {:?}", indent_subtext, alias.data_type);
+    }
+    IRInstruction::Error(_) => {
+      println!("{}Error", indent);
+    }
+    IRInstruction::CallValue(call) => {
+      println!("{}CallValue:", indent);
+      println!("{}Callee:", indent_subtext);
+      display_ir(&call.callee, indent_level);
+      println!("{}Arguments:", indent_subtext);
+
+      for argument in &call.arguments {
+        display_ir(argument, indent_level);
+      }
+
+      println!("{}Return type: {:?}", indent_subtext, call.return_type);
+    }
     IRInstruction::Ternary(t) => {
       println!("{}Ternary:", indent);
       println!("{}Condition:", indent_subtext);
@@ -1,3 +1,5 @@
+use lexer::token::Token;
+
 use super::IRInstruction;
 
 #[repr(C)]
@@ -5,10 +7,11 @@ use super::IRInstruction;
 pub struct IRAssign {
   pub name: String,
   pub value: Box<IRInstruction>,
+  pub token: Token,
 }
 
 impl IRAssign {
-  pub fn new(name: String, value: Box<IRInstruction>) -> Self {
-    Self { name, value }
+  pub fn new(name: String, value: Box<IRInstruction>, token: Token) -> Self {
+    Self { name, value, token }
   }
 }
@@ -1,4 +1,5 @@
 use enums::data_type::DataType;
+use lexer::token::Token;
 
 use crate::ir::instruction_type::IRInstructionType;
 
@@ -10,6 +11,7 @@ pub struct IRBinary {
   pub left: Box<IRInstruction>,
   pub right: Box<IRInstruction>,
   pub data_type: DataType,
+  pub token: Token,
 }
 
 impl IRBinary {
@@ -18,12 +20,14 @@ impl IRBinary {
     left: Box<IRInstruction>,
     right: Box<IRInstruction>,
     data_type: DataType,
+    token: Token,
   ) -> Self {
     Self {
       instruction_type,
       left,
       right,
       data_type,
+      token,
     }
   }
 }
@@ -1,4 +1,5 @@
 use enums::data_type::DataType;
+use lexer::token::Token;
 
 use super::IRInstruction;
 
@@ -7,14 +8,21 @@ pub struct IRCall {
   pub name: String,
   pub arguments: Vec<IRInstruction>,
   pub return_type: DataType,
+  pub token: Token,
 }
 
 impl IRCall {
-  pub fn new(name: String, arguments: Vec<IRInstruction>, return_type: DataType) -> Self {
+  pub fn new(
+    name: String,
+    arguments: Vec<IRInstruction>,
+    return_type: DataType,
+    token: Token,
+  ) -> Self {
     Self {
       name,
       arguments,
       return_type,
+      token,
     }
   }
 }
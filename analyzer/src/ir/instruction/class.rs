@@ -1,13 +1,107 @@
-use super::function::IRFunction;
+use enums::data_type::DataType;
 
+use super::{function::IRFunction, IRInstruction};
+
+#[derive(Debug, Clone)]
+pub struct IRClassMemberMetadata {
+  pub is_public: bool,
+  pub is_static: bool,
+  pub is_readonly: bool,
+}
+
+impl IRClassMemberMetadata {
+  pub fn new(is_public: bool, is_static: bool, is_readonly: bool) -> Self {
+    Self {
+      is_public,
+      is_static,
+      is_readonly,
+    }
+  }
+}
+
+/**
+  A field of a class.
+  Only static fields keep their initializer here, the initializers of the
+  instance fields run at the start of the constructor.
+*/
+#[derive(Debug, Clone)]
+pub struct IRClassField {
+  pub name: String,
+  pub data_type: DataType,
+  pub value: Option<Box<IRInstruction>>,
+  pub metadata: IRClassMemberMetadata,
+}
+
+impl IRClassField {
+  pub fn new(
+    name: String,
+    data_type: DataType,
+    value: Option<Box<IRInstruction>>,
+    metadata: IRClassMemberMetadata,
+  ) -> Self {
+    Self {
+      name,
+      data_type,
+      value,
+      metadata,
+    }
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct IRMethod {
+  pub function: IRFunction,
+  pub metadata: IRClassMemberMetadata,
+}
+
+impl IRMethod {
+  pub fn new(function: IRFunction, metadata: IRClassMemberMetadata) -> Self {
+    Self { function, metadata }
+  }
+}
+
+/**
+  A class declaration.
+
+  - generics: the type parameters of the class, the type arguments of an
+    instance are inferred from the arguments of its constructor.
+  - constructor: named after the class, it is generated by the analyzer when
+    the class declares instance fields but no constructor.
+*/
 #[derive(Debug, Clone)]
 pub struct IRClass {
   pub name: String,
-  pub methods: Vec<IRFunction>,
+  pub generics: Vec<String>,
+  pub fields: Vec<IRClassField>,
+  pub constructor: Option<IRMethod>,
+  pub methods: Vec<IRMethod>,
 }
 
 impl IRClass {
-  pub fn new(name: String, methods: Vec<IRFunction>) -> Self {
-    Self { name, methods }
+  pub fn new(
+    name: String,
+    generics: Vec<String>,
+    fields: Vec<IRClassField>,
+    constructor: Option<IRMethod>,
+    methods: Vec<IRMethod>,
+  ) -> Self {
+    Self {
+      name,
+      generics,
+      fields,
+      constructor,
+      methods,
+    }
+  }
+
+  pub fn field(&self, name: &str) -> Option<&IRClassField> {
+    self.fields.iter().find(|field| field.name == name)
+  }
+
+  pub fn method(&self, name: &str) -> Option<&IRMethod> {
+    self
+      .methods
+      .iter()
+      .find(|method| method.function.name == name)
   }
 }
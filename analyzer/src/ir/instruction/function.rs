@@ -1,4 +1,5 @@
 use enums::data_type::DataType;
+use lexer::token::Token;
 
 use super::{variable::IRVariable, block::IRBlock};
 
@@ -8,42 +9,60 @@ pub struct IRFunctionMetadata {
   pub is_exported: bool,
   pub is_imported: bool,
   pub is_extern: bool,
+  pub extern_module: Option<String>,
 }
 
 impl IRFunctionMetadata {
-  pub fn new(is_recursive: bool, is_exported: bool, is_imported: bool, is_extern: bool) -> Self {
+  pub fn new(
+    is_recursive: bool,
+    is_exported: bool,
+    is_imported: bool,
+    extern_module: Option<String>,
+  ) -> Self {
     Self {
       is_recursive,
       is_exported,
       is_imported,
-      is_extern,
+      is_extern: extern_module.is_some(),
+      extern_module,
     }
   }
 }
 
+/**
+  A function, `generics` are its type parameters, they are inferred from
+  the arguments of every call. `token` is the name of the function where it
+  is declared or imported, the module path for the standard library.
+*/
 #[derive(Debug, Clone)]
 pub struct IRFunction {
   pub name: String,
+  pub generics: Vec<String>,
   pub parameters: Vec<IRVariable>,
   pub return_type: DataType,
   pub body: Option<Box<IRBlock>>,
   pub metadata: IRFunctionMetadata,
+  pub token: Token,
 }
 
 impl IRFunction {
   pub fn new(
     name: String,
+    generics: Vec<String>,
     parameters: Vec<IRVariable>,
     return_type: DataType,
     body: Option<Box<IRBlock>>,
     metadata: IRFunctionMetadata,
+    token: Token,
   ) -> Self {
     Self {
       name,
+      generics,
       parameters,
       return_type,
       body,
       metadata,
+      token,
     }
   }
 }
@@ -7,13 +7,15 @@ use super::IRInstruction;
 pub struct IRArray {
   pub elements: Vec<IRInstruction>,
   pub data_type: DataType,
+  pub token: Token,
 }
 
 impl IRArray {
-  pub fn new(elements: Vec<IRInstruction>, data_type: DataType) -> Self {
+  pub fn new(elements: Vec<IRInstruction>, data_type: DataType, token: Token) -> Self {
     Self {
       elements,
       data_type,
+      token,
     }
   }
 }
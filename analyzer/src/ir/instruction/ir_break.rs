@@ -2,7 +2,7 @@ use lexer::token::Token;
 
 #[derive(Debug, Clone)]
 pub struct IRBreak {
-  token: Token,
+  pub token: Token,
 }
 
 impl IRBreak {
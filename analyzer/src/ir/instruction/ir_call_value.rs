@@ -0,0 +1,32 @@
+use enums::data_type::DataType;
+use lexer::token::Token;
+
+use super::IRInstruction;
+
+/**
+  Calls a function that is the result of an expression, `makeAdder(1)(2)`.
+  Functions called by name are an `IRCall`.
+*/
+#[derive(Debug, Clone)]
+pub struct IRCallValue {
+  pub callee: Box<IRInstruction>,
+  pub arguments: Vec<IRInstruction>,
+  pub return_type: DataType,
+  pub token: Token,
+}
+
+impl IRCallValue {
+  pub fn new(
+    callee: Box<IRInstruction>,
+    arguments: Vec<IRInstruction>,
+    return_type: DataType,
+    token: Token,
+  ) -> Self {
+    Self {
+      callee,
+      arguments,
+      return_type,
+      token,
+    }
+  }
+}
@@ -0,0 +1,48 @@
+use enums::data_type::DataType;
+
+use crate::analyzer_value::AnalyzerValue;
+
+/**
+  A variant of an enum.
+
+  - fields: types of the payload, they may use the type parameters of the
+    enum.
+  - value: constant of the variants declared as `RED = "red"`.
+*/
+#[derive(Debug, Clone)]
+pub struct IREnumVariant {
+  pub name: String,
+  pub fields: Vec<DataType>,
+  pub value: Option<AnalyzerValue>,
+}
+
+impl IREnumVariant {
+  pub fn new(name: String, fields: Vec<DataType>, value: Option<AnalyzerValue>) -> Self {
+    Self {
+      name,
+      fields,
+      value,
+    }
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct IREnum {
+  pub name: String,
+  pub generics: Vec<String>,
+  pub variants: Vec<IREnumVariant>,
+}
+
+impl IREnum {
+  pub fn new(name: String, generics: Vec<String>, variants: Vec<IREnumVariant>) -> Self {
+    Self {
+      name,
+      generics,
+      variants,
+    }
+  }
+
+  pub fn variant(&self, name: &str) -> Option<&IREnumVariant> {
+    self.variants.iter().find(|variant| variant.name == name)
+  }
+}
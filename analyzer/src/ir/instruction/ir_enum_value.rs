@@ -0,0 +1,42 @@
+use enums::data_type::DataType;
+use lexer::token::Token;
+
+use crate::analyzer_value::AnalyzerValue;
+
+use super::IRInstruction;
+
+/**
+  Creates the variant `variant` of `enum_name`, `Option.SOME(1)`.
+  The type arguments of a generic enum are inferred from the payload, the
+  ones that can not be inferred are `DataType::None`.
+  Variants declared with a constant keep it in `value`, `Colors.RED`.
+*/
+#[derive(Debug, Clone)]
+pub struct IREnumValue {
+  pub enum_name: String,
+  pub variant: String,
+  pub arguments: Vec<IRInstruction>,
+  pub value: Option<AnalyzerValue>,
+  pub data_type: DataType,
+  pub token: Token,
+}
+
+impl IREnumValue {
+  pub fn new(
+    enum_name: String,
+    variant: String,
+    arguments: Vec<IRInstruction>,
+    value: Option<AnalyzerValue>,
+    data_type: DataType,
+    token: Token,
+  ) -> Self {
+    Self {
+      enum_name,
+      variant,
+      arguments,
+      value,
+      data_type,
+      token,
+    }
+  }
+}
@@ -0,0 +1,12 @@
+/**
+  Takes the place of an expression or a statement that could not be analyzed.
+  Its error is already reported, the code that uses it is not checked again.
+*/
+#[derive(Debug, Clone, Default)]
+pub struct IRError {}
+
+impl IRError {
+  pub fn new() -> Self {
+    Self {}
+  }
+}
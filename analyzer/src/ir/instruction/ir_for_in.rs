@@ -1,11 +1,17 @@
+use enums::data_type::DataType;
 use lexer::token::Token;
 
 use super::{variable::IRVariable, IRInstruction};
 
+/**
+  A `for in` loop, `iterable_type` is the type of `iterable`, an array or a
+  string.
+*/
 #[derive(Debug, Clone)]
 pub struct IRForIn {
   pub variable: IRVariable,
   pub iterable: Box<IRInstruction>,
+  pub iterable_type: DataType,
   pub body: Box<IRInstruction>,
   pub token: Token,
 }
@@ -14,12 +20,14 @@ impl IRForIn {
   pub fn new(
     variable: IRVariable,
     iterable: Box<IRInstruction>,
+    iterable_type: DataType,
     body: Box<IRInstruction>,
     token: Token,
   ) -> Self {
     Self {
       variable,
       iterable,
+      iterable_type,
       body,
       token,
     }
@@ -0,0 +1,38 @@
+use enums::data_type::DataType;
+use lexer::token::Token;
+
+use super::{class::IRClassMemberMetadata, IRInstruction};
+
+/**
+  Reads a field of an instance, or a static member when `object` is the
+  class itself. `is_method` is true when the member read is a method.
+*/
+#[derive(Debug, Clone)]
+pub struct IRGet {
+  pub object: Box<IRInstruction>,
+  pub name: String,
+  pub data_type: DataType,
+  pub metadata: IRClassMemberMetadata,
+  pub is_method: bool,
+  pub token: Token,
+}
+
+impl IRGet {
+  pub fn new(
+    object: Box<IRInstruction>,
+    name: String,
+    data_type: DataType,
+    metadata: IRClassMemberMetadata,
+    is_method: bool,
+    token: Token,
+  ) -> Self {
+    Self {
+      object,
+      name,
+      data_type,
+      metadata,
+      is_method,
+      token,
+    }
+  }
+}
@@ -1,3 +1,5 @@
+use lexer::token::Token;
+
 use super::IRInstruction;
 
 #[derive(Debug, Clone)]
@@ -5,6 +7,7 @@ pub struct IRIf {
   pub condition: Box<IRInstruction>,
   pub then_branch: Box<IRInstruction>,
   pub else_branch: Option<Box<IRInstruction>>,
+  pub token: Token,
 }
 
 impl IRIf {
@@ -12,11 +15,13 @@ impl IRIf {
     condition: Box<IRInstruction>,
     then_branch: Box<IRInstruction>,
     else_branch: Option<Box<IRInstruction>>,
+    token: Token,
   ) -> Self {
     Self {
       condition,
       then_branch,
       else_branch,
+      token,
     }
   }
 }
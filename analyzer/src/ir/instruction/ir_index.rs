@@ -0,0 +1,31 @@
+use enums::data_type::DataType;
+use lexer::token::Token;
+
+use super::IRInstruction;
+
+#[derive(Debug, Clone)]
+pub struct IRIndex {
+  pub object: Box<IRInstruction>,
+  pub index: Box<IRInstruction>,
+  pub data_type: DataType,
+  pub object_type: DataType,
+  pub token: Token,
+}
+
+impl IRIndex {
+  pub fn new(
+    object: Box<IRInstruction>,
+    index: Box<IRInstruction>,
+    data_type: DataType,
+    object_type: DataType,
+    token: Token,
+  ) -> Self {
+    Self {
+      object,
+      index,
+      data_type,
+      object_type,
+      token,
+    }
+  }
+}
@@ -0,0 +1,36 @@
+use super::class::{IRClass, IRClassField, IRMethod};
+
+/**
+  An interface declaration, it includes the members of the interfaces it
+  extends. Members of an interface are always public instance members.
+*/
+#[derive(Debug, Clone)]
+pub struct IRInterface {
+  pub name: String,
+  pub fields: Vec<IRClassField>,
+  pub methods: Vec<IRMethod>,
+}
+
+impl IRInterface {
+  pub fn new(name: String, fields: Vec<IRClassField>, methods: Vec<IRMethod>) -> Self {
+    Self {
+      name,
+      fields,
+      methods,
+    }
+  }
+
+  /**
+    The members of the interface as a class without constructor, used to
+    type the members accessed through a value of the interface.
+  */
+  pub fn as_class(&self) -> IRClass {
+    IRClass::new(
+      self.name.clone(),
+      Vec::new(),
+      self.fields.clone(),
+      None,
+      self.methods.clone(),
+    )
+  }
+}
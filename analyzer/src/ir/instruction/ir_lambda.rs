@@ -0,0 +1,40 @@
+use enums::data_type::DataType;
+use lexer::token::Token;
+
+use super::function::IRFunction;
+
+/**
+  An arrow function used as a value.
+
+  - captures: variables of the enclosing functions used by the lambda or by
+    the lambdas declared inside it, in the order they are first used. `this`
+    is captured by the lambdas declared in a method.
+*/
+#[derive(Debug, Clone)]
+pub struct IRLambda {
+  pub function: IRFunction,
+  pub captures: Vec<String>,
+  pub token: Token,
+}
+
+impl IRLambda {
+  pub fn new(function: IRFunction, captures: Vec<String>, token: Token) -> Self {
+    Self {
+      function,
+      captures,
+      token,
+    }
+  }
+
+  pub fn data_type(&self) -> DataType {
+    DataType::Callable(
+      self
+        .function
+        .parameters
+        .iter()
+        .map(|parameter| parameter.data_type.clone())
+        .collect(),
+      Box::new(self.function.return_type.clone()),
+    )
+  }
+}
@@ -0,0 +1,78 @@
+use enums::data_type::DataType;
+use lexer::token::Token;
+
+use crate::analyzer_value::AnalyzerValue;
+
+use super::IRInstruction;
+
+/**
+  Pattern of an arm of a `match`.
+
+  - Variant: `value` is the constant of the variants declared with one,
+    they are compared with it. `bindings` are the locals that receive the
+    payload, `None` for the values ignored with `_`.
+*/
+#[derive(Debug, Clone)]
+pub enum IRMatchPattern {
+  Literal(AnalyzerValue),
+  Wildcard,
+  Variant {
+    enum_name: String,
+    variant: String,
+    value: Option<AnalyzerValue>,
+    bindings: Vec<Option<String>>,
+  },
+}
+
+#[derive(Debug, Clone)]
+pub struct IRMatchArm {
+  pub pattern: IRMatchPattern,
+  pub guard: Option<Box<IRInstruction>>,
+  pub body: Box<IRInstruction>,
+}
+
+impl IRMatchArm {
+  pub fn new(
+    pattern: IRMatchPattern,
+    guard: Option<Box<IRInstruction>>,
+    body: Box<IRInstruction>,
+  ) -> Self {
+    Self {
+      pattern,
+      guard,
+      body,
+    }
+  }
+}
+
+/**
+  The value of a `match` is the body of the first arm that matches, or
+  `null` when none does. Arms with a block body have no value.
+  `is_exhaustive` is true when an arm matches every possible value.
+*/
+#[derive(Debug, Clone)]
+pub struct IRMatch {
+  pub value: Box<IRInstruction>,
+  pub arms: Vec<IRMatchArm>,
+  pub data_type: DataType,
+  pub is_exhaustive: bool,
+  pub token: Token,
+}
+
+impl IRMatch {
+  pub fn new(
+    value: Box<IRInstruction>,
+    arms: Vec<IRMatchArm>,
+    data_type: DataType,
+    is_exhaustive: bool,
+    token: Token,
+  ) -> Self {
+    Self {
+      value,
+      arms,
+      data_type,
+      is_exhaustive,
+      token,
+    }
+  }
+}
@@ -0,0 +1,38 @@
+use enums::data_type::DataType;
+use lexer::token::Token;
+
+use super::{class::IRClassMemberMetadata, IRInstruction};
+
+/**
+  Calls the method `name` of `object`, `object` is the class when the
+  method is static.
+*/
+#[derive(Debug, Clone)]
+pub struct IRMethodCall {
+  pub object: Box<IRInstruction>,
+  pub name: String,
+  pub arguments: Vec<IRInstruction>,
+  pub return_type: DataType,
+  pub metadata: IRClassMemberMetadata,
+  pub token: Token,
+}
+
+impl IRMethodCall {
+  pub fn new(
+    object: Box<IRInstruction>,
+    name: String,
+    arguments: Vec<IRInstruction>,
+    return_type: DataType,
+    metadata: IRClassMemberMetadata,
+    token: Token,
+  ) -> Self {
+    Self {
+      object,
+      name,
+      arguments,
+      return_type,
+      metadata,
+      token,
+    }
+  }
+}
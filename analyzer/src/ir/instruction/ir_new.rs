@@ -0,0 +1,28 @@
+use enums::data_type::DataType;
+use lexer::token::Token;
+
+use super::IRInstruction;
+
+#[derive(Debug, Clone)]
+pub struct IRNew {
+  pub class_name: String,
+  pub arguments: Vec<IRInstruction>,
+  pub data_type: DataType,
+  pub token: Token,
+}
+
+impl IRNew {
+  pub fn new(
+    class_name: String,
+    arguments: Vec<IRInstruction>,
+    data_type: DataType,
+    token: Token,
+  ) -> Self {
+    Self {
+      class_name,
+      arguments,
+      data_type,
+      token,
+    }
+  }
+}
@@ -1,4 +1,5 @@
 use enums::data_type::DataType;
+use lexer::token::Token;
 
 use super::IRInstruction;
 
@@ -6,10 +7,15 @@ use super::IRInstruction;
 pub struct IRReturn {
   pub value: Box<IRInstruction>,
   pub data_type: DataType,
+  pub token: Token,
 }
 
 impl IRReturn {
-  pub fn new(value: Box<IRInstruction>, data_type: DataType) -> Self {
-    Self { value, data_type }
+  pub fn new(value: Box<IRInstruction>, data_type: DataType, token: Token) -> Self {
+    Self {
+      value,
+      data_type,
+      token,
+    }
   }
 }
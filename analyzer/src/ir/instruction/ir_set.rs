@@ -0,0 +1,34 @@
+use enums::data_type::DataType;
+use lexer::token::Token;
+
+use super::{class::IRClassMemberMetadata, IRInstruction};
+
+#[derive(Debug, Clone)]
+pub struct IRSet {
+  pub object: Box<IRInstruction>,
+  pub name: String,
+  pub value: Box<IRInstruction>,
+  pub data_type: DataType,
+  pub metadata: IRClassMemberMetadata,
+  pub token: Token,
+}
+
+impl IRSet {
+  pub fn new(
+    object: Box<IRInstruction>,
+    name: String,
+    value: Box<IRInstruction>,
+    data_type: DataType,
+    metadata: IRClassMemberMetadata,
+    token: Token,
+  ) -> Self {
+    Self {
+      object,
+      name,
+      value,
+      data_type,
+      metadata,
+      token,
+    }
+  }
+}
@@ -0,0 +1,31 @@
+use enums::data_type::DataType;
+use lexer::token::Token;
+
+use super::IRInstruction;
+
+#[derive(Debug, Clone)]
+pub struct IRSetIndex {
+  pub object: Box<IRInstruction>,
+  pub index: Box<IRInstruction>,
+  pub value: Box<IRInstruction>,
+  pub data_type: DataType,
+  pub token: Token,
+}
+
+impl IRSetIndex {
+  pub fn new(
+    object: Box<IRInstruction>,
+    index: Box<IRInstruction>,
+    value: Box<IRInstruction>,
+    data_type: DataType,
+    token: Token,
+  ) -> Self {
+    Self {
+      object,
+      index,
+      value,
+      data_type,
+      token,
+    }
+  }
+}
@@ -0,0 +1,51 @@
+use lexer::token::Token;
+
+use super::IRInstruction;
+
+/**
+  A case of a `switch`, `value` is `None` for `default`.
+*/
+#[derive(Debug, Clone)]
+pub struct IRSwitchCase {
+  pub value: Option<Box<IRInstruction>>,
+  pub body: Vec<IRInstruction>,
+  pub token: Token,
+}
+
+impl IRSwitchCase {
+  pub fn new(value: Option<Box<IRInstruction>>, body: Vec<IRInstruction>, token: Token) -> Self {
+    Self { value, body, token }
+  }
+
+  /**
+    The execution continues with the next case unless the body ends with a
+    `break`, `continue` or `return`.
+  */
+  pub fn falls_through(&self) -> bool {
+    !matches!(
+      self.body.last(),
+      Some(IRInstruction::Break(_) | IRInstruction::Continue(_) | IRInstruction::Return(_))
+    )
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct IRSwitch {
+  pub value: Box<IRInstruction>,
+  pub cases: Vec<IRSwitchCase>,
+  pub token: Token,
+}
+
+impl IRSwitch {
+  pub fn new(value: Box<IRInstruction>, cases: Vec<IRSwitchCase>, token: Token) -> Self {
+    Self {
+      value,
+      cases,
+      token,
+    }
+  }
+
+  pub fn default_case(&self) -> Option<usize> {
+    self.cases.iter().position(|case| case.value.is_none())
+  }
+}
@@ -0,0 +1,19 @@
+use lexer::token::Token;
+
+use super::IRInstruction;
+
+/**
+  A template string. The text between the expressions is a string literal,
+  every part is converted to a string and joined in order.
+*/
+#[derive(Debug, Clone)]
+pub struct IRTemplateLiteral {
+  pub parts: Vec<IRInstruction>,
+  pub token: Token,
+}
+
+impl IRTemplateLiteral {
+  pub fn new(parts: Vec<IRInstruction>, token: Token) -> Self {
+    Self { parts, token }
+  }
+}
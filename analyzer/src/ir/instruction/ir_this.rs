@@ -0,0 +1,13 @@
+use lexer::token::Token;
+
+#[derive(Debug, Clone)]
+pub struct IRThis {
+  pub class_name: String,
+  pub token: Token,
+}
+
+impl IRThis {
+  pub fn new(class_name: String, token: Token) -> Self {
+    Self { class_name, token }
+  }
+}
@@ -0,0 +1,13 @@
+use enums::data_type::DataType;
+
+#[derive(Debug, Clone)]
+pub struct IRTypeAlias {
+  pub name: String,
+  pub data_type: DataType,
+}
+
+impl IRTypeAlias {
+  pub fn new(name: String, data_type: DataType) -> Self {
+    Self { name, data_type }
+  }
+}
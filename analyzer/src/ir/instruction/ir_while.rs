@@ -1,13 +1,20 @@
+use lexer::token::Token;
+
 use super::IRInstruction;
 
 #[derive(Debug, Clone)]
 pub struct IRWhile {
   pub condition: Box<IRInstruction>,
   pub body: Box<IRInstruction>,
+  pub token: Token,
 }
 
 impl IRWhile {
-  pub fn new(condition: Box<IRInstruction>, body: Box<IRInstruction>) -> Self {
-    Self { condition, body }
+  pub fn new(condition: Box<IRInstruction>, body: Box<IRInstruction>, token: Token) -> Self {
+    Self {
+      condition,
+      body,
+      token,
+    }
   }
 }
@@ -1,12 +1,15 @@
+use lexer::token::Token;
+
 use crate::analyzer_value::AnalyzerValue;
 
 #[derive(Debug, Clone)]
 pub struct IRLiteral {
   pub value: AnalyzerValue,
+  pub token: Token,
 }
 
 impl IRLiteral {
-  pub fn new(value: AnalyzerValue) -> Self {
-    Self { value }
+  pub fn new(value: AnalyzerValue, token: Token) -> Self {
+    Self { value, token }
   }
 }
@@ -1,3 +1,5 @@
+use lexer::token::Token;
+
 use crate::ir::instruction_type::IRInstructionType;
 
 use super::IRInstruction;
@@ -7,6 +9,7 @@ pub struct IRLogical {
   pub instruction_type: IRInstructionType,
   pub left: Box<IRInstruction>,
   pub right: Box<IRInstruction>,
+  pub token: Token,
 }
 
 impl IRLogical {
@@ -14,11 +17,13 @@ impl IRLogical {
     instruction_type: IRInstructionType,
     left: Box<IRInstruction>,
     right: Box<IRInstruction>,
+    token: Token,
   ) -> Self {
     Self {
       instruction_type,
       left,
       right,
+      token,
     }
   }
 }
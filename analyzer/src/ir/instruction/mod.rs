@@ -6,10 +6,26 @@ pub mod class;
 pub mod function;
 pub mod import;
 pub mod ir_array;
+pub mod ir_call_value;
+pub mod ir_enum;
+pub mod ir_enum_value;
 pub mod ir_for_in;
+pub mod ir_get;
 pub mod ir_if;
+pub mod ir_index;
+pub mod ir_interface;
+pub mod ir_lambda;
+pub mod ir_match;
+pub mod ir_method_call;
+pub mod ir_new;
 pub mod ir_println;
 pub mod ir_return;
+pub mod ir_set;
+pub mod ir_set_index;
+pub mod ir_switch;
+pub mod ir_template_literal;
+pub mod ir_this;
+pub mod ir_type_alias;
 pub mod ir_while;
 pub mod literal;
 pub mod logical;
@@ -18,12 +34,18 @@ pub mod unary;
 pub mod variable;
 pub mod ir_break;
 pub mod ir_continue;
+pub mod ir_error;
 
 use self::{
   binary::IRBinary, block::IRBlock, literal::IRLiteral, unary::IRUnary, variable::IRVariable,
   logical::IRLogical, ir_if::IRIf, ir_while::IRWhile, function::IRFunction, call::IRCall,
   class::IRClass, assign::IRAssign, ir_return::IRReturn, ternary::IRTernary, ir_for_in::IRForIn,
-  ir_array::IRArray, import::IRImport, ir_break::IRBreak, ir_continue::IRContinue,
+  ir_array::IRArray, import::IRImport, ir_break::IRBreak, ir_continue::IRContinue, ir_this::IRThis,
+  ir_get::IRGet, ir_set::IRSet, ir_new::IRNew, ir_method_call::IRMethodCall, ir_enum::IREnum,
+  ir_enum_value::IREnumValue, ir_match::IRMatch, ir_switch::IRSwitch,
+  ir_lambda::IRLambda, ir_call_value::IRCallValue, ir_template_literal::IRTemplateLiteral,
+  ir_index::IRIndex, ir_set_index::IRSetIndex, ir_interface::IRInterface,
+  ir_type_alias::IRTypeAlias, ir_error::IRError,
 };
 
 #[derive(Debug, Clone)]
@@ -47,4 +69,21 @@ pub enum IRInstruction {
   Import(IRImport),
   Break(IRBreak),
   Continue(IRContinue),
+  This(IRThis),
+  Get(IRGet),
+  Set(IRSet),
+  New(IRNew),
+  MethodCall(IRMethodCall),
+  Enum(IREnum),
+  EnumValue(IREnumValue),
+  Match(IRMatch),
+  Switch(IRSwitch),
+  Lambda(IRLambda),
+  CallValue(IRCallValue),
+  TemplateLiteral(IRTemplateLiteral),
+  Index(IRIndex),
+  SetIndex(IRSetIndex),
+  Interface(IRInterface),
+  TypeAlias(IRTypeAlias),
+  Error(IRError),
 }
@@ -1,3 +1,6 @@
+use enums::data_type::DataType;
+use lexer::token::Token;
+
 use super::IRInstruction;
 
 #[derive(Debug, Clone)]
@@ -5,6 +8,8 @@ pub struct IRTernary {
   pub condition: Box<IRInstruction>,
   pub then_branch: Box<IRInstruction>,
   pub else_branch: Box<IRInstruction>,
+  pub data_type: DataType,
+  pub token: Token,
 }
 
 impl IRTernary {
@@ -12,11 +17,15 @@ impl IRTernary {
     condition: Box<IRInstruction>,
     then_branch: Box<IRInstruction>,
     else_branch: Box<IRInstruction>,
+    data_type: DataType,
+    token: Token,
   ) -> Self {
     Self {
       condition,
       then_branch,
       else_branch,
+      data_type,
+      token,
     }
   }
 }
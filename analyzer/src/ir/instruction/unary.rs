@@ -1,4 +1,5 @@
 use enums::data_type::DataType;
+use lexer::token::Token;
 
 use crate::ir::instruction_type::IRInstructionType;
 
@@ -9,6 +10,7 @@ pub struct IRUnary {
   pub instruction_type: IRInstructionType,
   pub right: Box<IRInstruction>,
   pub data_type: DataType,
+  pub token: Token,
 }
 
 impl IRUnary {
@@ -16,11 +18,13 @@ impl IRUnary {
     instruction_type: IRInstructionType,
     right: Box<IRInstruction>,
     data_type: DataType,
+    token: Token,
   ) -> Self {
     Self {
       instruction_type,
       right,
       data_type,
+      token,
     }
   }
 }
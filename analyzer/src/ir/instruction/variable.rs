@@ -1,4 +1,5 @@
 use enums::data_type::DataType;
+use lexer::token::Token;
 
 use super::IRInstruction;
 
@@ -33,12 +34,18 @@ impl IRVariableMetadata {
   }
 }
 
+/**
+  A declaration of a variable or a parameter, or a read of one. `token` is
+  its name in the source, `None` for the variables declared by the
+  application that embeds the VM and by the standard library.
+*/
 #[derive(Debug, Clone)]
 pub struct IRVariable {
   pub name: String,
   pub data_type: DataType,
   pub value: Option<Box<IRInstruction>>,
   pub metadata: IRVariableMetadata,
+  pub token: Option<Token>,
 }
 
 impl IRVariable {
@@ -47,12 +54,14 @@ impl IRVariable {
     data_type: DataType,
     value: Option<Box<IRInstruction>>,
     metadata: IRVariableMetadata,
+    token: Option<Token>,
   ) -> Self {
     Self {
       name,
       data_type,
       value,
       metadata,
+      token,
     }
   }
 }
@@ -1,18 +1,31 @@
 pub mod analyzer_error;
 pub mod analyzer_value;
+pub mod analyzer_warning;
+pub mod control_flow;
 pub mod debug;
 pub mod ir;
 
-use std::{collections::HashMap, vec, fs};
+use std::{
+  collections::{HashMap, HashSet},
+  vec, fs,
+};
 
 use analyzer_error::AnalyzerDiagnosticError;
+use analyzer_warning::AnalyzerDiagnosticWarning;
 use analyzer_value::AnalyzerValue;
+use control_flow::ControlFlowGraph;
 use ast::{
   visitor::Visitor,
   expression::{
     binary::Binary, Expression, literal::Literal, unary::Unary, grouping::Grouping,
     logical::Logical, assign::Assign, variable::VariableExpression, ternary::Ternary, call::Call,
-    array::Array,
+    array::Array, this::This, get::Get, set::Set, new::NewExpression,
+    match_expression::{Match, MatchArm, MatchPattern},
+    lambda::Lambda,
+    template_literal::{TemplateLiteral, TemplatePart},
+    index::Index,
+    set_index::SetIndex,
+    error_expression::ErrorExpression,
   },
   statement::{
     Statement,
@@ -21,13 +34,18 @@ use ast::{
     variable::Variable,
     if_statement::IfStatement,
     while_statement::WhileStatement,
-    function::{FunctionStatement, FunctionDecorator},
+    function::{FunctionStatement, FunctionDecorator, FunctionParameter},
     return_statement::Return,
-    class::Class,
+    class::{Class, ClassMemberModifiers},
     for_in::ForIn,
     import::Import,
     break_statement::BreakStatement,
     continue_statement::Continue,
+    enum_statement::Enum,
+    switch::{Switch, SwitchCase},
+    interface::Interface,
+    type_alias::TypeAlias,
+    error_statement::ErrorStatement,
   },
 };
 use enums::{data_type::DataType, token_type::TokenType};
@@ -51,6 +69,24 @@ use ir::{
     ir_array::IRArray,
     import::IRImport,
     ir_break::IRBreak, ir_continue::IRContinue,
+    class::{IRClass, IRClassField, IRClassMemberMetadata, IRMethod},
+    ir_this::IRThis,
+    ir_get::IRGet,
+    ir_set::IRSet,
+    ir_new::IRNew,
+    ir_method_call::IRMethodCall,
+    ir_enum::{IREnum, IREnumVariant},
+    ir_enum_value::IREnumValue,
+    ir_match::{IRMatch, IRMatchArm, IRMatchPattern},
+    ir_switch::{IRSwitch, IRSwitchCase},
+    ir_lambda::IRLambda,
+    ir_template_literal::IRTemplateLiteral,
+    ir_call_value::IRCallValue,
+    ir_index::IRIndex,
+    ir_set_index::IRSetIndex,
+    ir_interface::IRInterface,
+    ir_type_alias::IRTypeAlias,
+    ir_error::IRError,
   },
   instruction_type::IRInstructionType,
 };
@@ -63,26 +99,80 @@ type CheckCompatibility<T> = (bool, T);
 enum AnalyzerContext {
   Function,
   Method,
+  Initializer,
   Class,
   Loop,
   Switch,
   Match,
+  Lambda,
+}
+
+/**
+  Names declared by a function, method or lambda that is being analyzed.
+  `captures` are the names a lambda uses from the functions that enclose it.
+  `return_type` is the type its `return` statements must have, it is unknown
+  for constructors and for lambdas without annotation.
+
+  The first scope is the top level code, its locals are the variables
+  declared inside its blocks, the ones outside of them are globals.
+*/
+struct FunctionScope {
+  locals: HashSet<String>,
+  captures: Vec<String>,
+  is_lambda: bool,
+  return_type: Option<DataType>,
+}
+
+impl FunctionScope {
+  fn new(is_lambda: bool, return_type: Option<DataType>) -> Self {
+    Self {
+      locals: HashSet::new(),
+      captures: Vec::new(),
+      is_lambda,
+      return_type,
+    }
+  }
+}
+
+/**
+  The size of the stacks of the analyzer before a statement or an expression
+  is analyzed. When its analysis fails they are cut back to it, so the scopes
+  and contexts it opened do not leak into the code after it.
+*/
+struct Checkpoint {
+  block_stack: usize,
+  scopes_variables: usize,
+  context: usize,
+  function_scopes: usize,
 }
 
 pub struct Analyzer {
   pub irs: HashMap<String, Vec<IRInstruction>>,
   pub block_stack: Vec<HashMap<String, bool>>,
   pub diagnostics: Vec<AnalyzerDiagnosticError>,
+  pub warnings: Vec<AnalyzerDiagnosticWarning>,
   pub scopes_variables: Vec<IRVariable>,
   pub current_function: Option<IRFunction>,
   pub current_file: String,
+  pub global_functions: Vec<IRFunction>,
+  pub classes: HashMap<String, IRClass>,
+  pub enums: HashMap<String, IREnum>,
+  pub interfaces: HashMap<String, IRInterface>,
+  pub type_aliases: HashMap<String, DataType>,
+  current_class: Option<String>,
   context: Vec<AnalyzerContext>,
+  function_scopes: Vec<FunctionScope>,
 }
 
 impl Visitor<AnalyzerResult> for Analyzer {
   fn visit_binary_expression(&mut self, expression: &Binary) -> AnalyzerResult {
-    let left = self.analyzer(&*expression.left)?;
-    let right = self.analyzer(&*expression.right)?;
+    let left = self.analyze_or_recover(&expression.left);
+    let right = self.analyze_or_recover(&expression.right);
+
+    if Self::has_error(&[&left, &right]) {
+      return Ok(IRInstruction::Error(IRError::new()));
+    }
+
     let operator = expression.operator.clone();
     let instruction_type = if operator.kind == TokenType::Plus {
       if self.extract_data_type(&left) == DataType::String
@@ -113,6 +203,7 @@ impl Visitor<AnalyzerResult> for Analyzer {
       Box::new(left),
       Box::new(right),
       data_type,
+      operator.clone(),
     ));
 
     Ok(instruction)
@@ -123,19 +214,27 @@ impl Visitor<AnalyzerResult> for Analyzer {
   }
 
   fn visit_literal_expression(&mut self, expression: &Literal) -> AnalyzerResult {
-    let instruction = IRInstruction::Literal(IRLiteral::new(AnalyzerValue::from_literation_value(
-      expression.value.clone(),
-    )));
+    let instruction = IRInstruction::Literal(IRLiteral::new(
+      AnalyzerValue::from_literation_value(expression.value.clone()),
+      expression.token.clone(),
+    ));
 
     Ok(instruction)
   }
 
   fn visit_unary_expression(&mut self, expression: &Unary) -> AnalyzerResult {
-    let right = self.analyzer(&*expression.right)?;
+    let right = self.analyze_or_recover(&expression.right);
+
+    if Self::has_error(&[&right]) {
+      return Ok(right);
+    }
+
     let instruction_type = IRInstructionType::from_token_kind(&expression.operator.kind);
+    let right_type = self.extract_data_type(&right);
 
-    if !self.are_types_unary_compatible(&right, &instruction_type) {
-      let right_type = self.extract_data_type(&right);
+    let (result, data_type) = self.check_unary_compatibility(&right_type, &instruction_type);
+
+    if !result {
       return Err(AnalyzerDiagnosticError::TypeMismatchUnary(
         right_type,
         expression.operator.clone(),
@@ -145,7 +244,8 @@ impl Visitor<AnalyzerResult> for Analyzer {
     let instruction = IRInstruction::Unary(IRUnary::new(
       instruction_type,
       Box::new(right),
-      DataType::Int,
+      data_type,
+      expression.operator.clone(),
     ));
 
     Ok(instruction)
@@ -172,6 +272,14 @@ impl Visitor<AnalyzerResult> for Analyzer {
       return Ok(instruction);
     }
 
+    if let Some(f) = self
+      .global_functions
+      .iter()
+      .find(|f| f.name == variable.name.span.literal)
+    {
+      return Ok(IRInstruction::Function(f.clone()));
+    }
+
     if let Some(f) = &mut self.current_function {
       if f.name == variable.name.span.literal {
         f.metadata.is_recursive = true;
@@ -199,14 +307,24 @@ impl Visitor<AnalyzerResult> for Analyzer {
         ));
       }
 
+      let token = variable.name.clone();
       let mut variable = self
         .scopes_variables
         .iter()
-        .find(|v| v.name == variable.name.span.literal)
+        .rfind(|v| v.name == variable.name.span.literal)
         .unwrap()
         .clone();
 
       variable.metadata.is_declaration = false;
+      variable.token = Some(token);
+      self.capture(&variable.name);
+
+      // The error of its value is already reported.
+      if variable.data_type == DataType::None
+        && matches!(variable.value.as_deref(), Some(IRInstruction::Error(_)))
+      {
+        return Ok(IRInstruction::Error(IRError::new()));
+      }
 
       let instruction = IRInstruction::Variable(variable.clone());
 
@@ -232,7 +350,6 @@ impl Visitor<AnalyzerResult> for Analyzer {
       ));
     }
 
-    let value = self.analyzer(&expression.value)?;
     let current_block = self.block_stack.last().unwrap();
 
     let env = current_block.into_iter().find(|(name, is_declared)| {
@@ -243,13 +360,19 @@ impl Visitor<AnalyzerResult> for Analyzer {
       let variable = self
         .scopes_variables
         .iter()
-        .find(|v| v.name == *name)
-        .unwrap();
+        .rfind(|v| v.name == *name)
+        .unwrap()
+        .clone();
 
       if variable.metadata.is_mutable {
+        let value =
+          self.analyze_assigned_value(&expression.value, &variable.data_type, &expression.name)?;
+        self.capture(&variable.name);
+
         let instruction = IRInstruction::Assign(IRAssign::new(
           expression.name.span.literal.clone(),
           Box::new(value),
+          expression.name.clone(),
         ));
 
         Ok(instruction)
@@ -266,17 +389,24 @@ impl Visitor<AnalyzerResult> for Analyzer {
   }
 
   fn visit_logical_expression(&mut self, expression: &Logical) -> AnalyzerResult {
-    let left = self.analyzer(&expression.left)?;
-    let right = self.analyzer(&expression.right)?;
+    let left = self.analyze_or_recover(&expression.left);
+    let right = self.analyze_or_recover(&expression.right);
+
+    if Self::has_error(&[&left, &right]) {
+      return Ok(IRInstruction::Error(IRError::new()));
+    }
 
     let instruction_type = IRInstructionType::from_token_kind(&expression.operator.kind);
 
     match instruction_type {
       IRInstructionType::And | IRInstructionType::Or => {
-        if !self.are_types_logical_compatibel(&left, &right, &instruction_type) {
+        let left_type = self.extract_data_type(&left);
+        let right_type = self.extract_data_type(&right);
+
+        if !self.check_logical_compatibility(&left_type, &right_type).0 {
           return Err(AnalyzerDiagnosticError::TypeMismatch(
-            self.extract_data_type(&left),
-            self.extract_data_type(&right),
+            left_type,
+            right_type,
             expression.operator.clone(),
           ));
         }
@@ -285,6 +415,7 @@ impl Visitor<AnalyzerResult> for Analyzer {
           instruction_type,
           Box::new(left),
           Box::new(right),
+          expression.operator.clone(),
         ));
 
         Ok(instruction)
@@ -296,102 +427,103 @@ impl Visitor<AnalyzerResult> for Analyzer {
   }
 
   fn visit_ternary_expression(&mut self, expression: &Ternary) -> AnalyzerResult {
-    let condition = self.analyzer(&*expression.condition)?;
-    let then_branch = self.analyzer(&*expression.then_branch)?;
-    let else_branch = self.analyzer(&*expression.else_branch)?;
+    let condition = self.analyze_or_recover(&expression.condition);
+    let then_branch = self.analyze_or_recover(&expression.then_branch);
+    let else_branch = self.analyze_or_recover(&expression.else_branch);
+
+    if Self::has_error(&[&condition, &then_branch, &else_branch]) {
+      return Ok(IRInstruction::Error(IRError::new()));
+    }
+
+    let then_type = self.extract_data_type(&then_branch);
+    let else_type = self.extract_data_type(&else_branch);
+
+    let data_type = match self.common_type(&then_type, &else_type) {
+      Some(data_type) => data_type,
+      None => {
+        return Err(AnalyzerDiagnosticError::TypeMismatch(
+          then_type,
+          else_type,
+          expression.token.clone(),
+        ))
+      }
+    };
 
     Ok(IRInstruction::Ternary(IRTernary::new(
       Box::new(condition),
       Box::new(then_branch),
       Box::new(else_branch),
+      data_type,
+      expression.token.clone(),
     )))
   }
 
   fn visit_call_expression(&mut self, expression: &Call) -> AnalyzerResult {
-    let calle = self.analyzer(&expression.callee)?;
-
-    let function = match calle {
-      IRInstruction::Function(f) => Some(f),
-      _ => {
-        return Err(AnalyzerDiagnosticError::NotCallable(
-          expression.paren.clone(),
-        ))
+    if let Expression::Get(get) = &*expression.callee {
+      if let Some(enum_name) = self.enum_name(&get.object) {
+        return self.enum_value(&enum_name, &get.name, Some(expression));
       }
-    };
-
-    let function = function.unwrap();
 
-    if function.parameters.len() != expression.arguments.len() {
-      return Err(AnalyzerDiagnosticError::InvalidNumberOfArguments(
-        function.parameters.len(),
-        expression.arguments.len(),
-        expression.paren.clone(),
-      ));
+      return self.analyze_method_call(get, expression);
     }
 
-    let mut arguments = Vec::<IRInstruction>::new();
-
-    for (i, arg) in expression.arguments.iter().enumerate() {
-      let arg_type = self.analyzer(&arg)?;
-
-      let kind = match &arg_type {
-        IRInstruction::Literal(l) => l.value.to_data_type(),
-        IRInstruction::Variable(v) => v.data_type.clone(),
-        IRInstruction::Function(f) => f.return_type.clone(),
-        IRInstruction::Call(c) => c.return_type.clone(),
-        IRInstruction::Return(r) => r.data_type.clone(),
-        IRInstruction::Binary(b) => b.data_type.clone(),
-        IRInstruction::Unary(u) => u.data_type.clone(),
-        IRInstruction::Logical(_) => DataType::Boolean,
-        _ => DataType::None,
-      };
-
-      if kind != function.parameters[i].data_type
-        && function.parameters[i].data_type != DataType::None
-      {
-        return Err(AnalyzerDiagnosticError::ArgumentTypeMismatch(
-          function.parameters[i].data_type.clone(),
-          kind,
-          expression.paren.clone(),
-        ));
-      }
+    let calle = self.analyze_or_recover(&expression.callee);
 
-      match &arg_type {
-        IRInstruction::Variable(v) => {
-          if !v.metadata.is_mutable && function.parameters[i].metadata.is_mutable {
-            return Err(
-              AnalyzerDiagnosticError::ImmutableVariableAsMutableParameter(
-                function.parameters[i].name.clone(),
-                v.name.clone(),
-                expression.paren.clone(),
-              ),
-            );
-          }
+    let function = match calle {
+      IRInstruction::Function(f) => f,
+      IRInstruction::Error(_) => {
+        // The errors of the arguments are reported as well.
+        for argument in &expression.arguments {
+          self.analyze_or_recover(argument);
         }
-        _ => (),
-      };
 
-      arguments.push(arg_type);
-    }
+        return Ok(calle);
+      }
+      calle => return self.analyze_value_call(calle, expression),
+    };
 
-    let instruction =
-      IRInstruction::Call(IRCall::new(function.name, arguments, function.return_type));
+    let (arguments, type_arguments) = self.infer_arguments(
+      &function.parameters,
+      &function.generics,
+      &expression.arguments,
+      &expression.paren,
+    )?;
+
+    let instruction = IRInstruction::Call(IRCall::new(
+      function.name,
+      arguments,
+      Self::substitute_type(&function.generics, &type_arguments, &function.return_type),
+      expression.paren.clone(),
+    ));
 
     Ok(instruction)
   }
 
   fn visit_expression_statement(&mut self, statement: &ExpressionStatement) -> AnalyzerResult {
-    self.analyzer(&statement.expression)
+    match &*statement.expression {
+      Expression::Match(expression) => self.analyze_match(expression, false),
+      expression => self.analyzer(expression),
+    }
   }
 
   fn visit_variable_statement(&mut self, variable: &Variable) -> AnalyzerResult {
     self.declare(&variable.name.span.literal);
 
-    let mut value = IRInstruction::Literal(IRLiteral::new(AnalyzerValue::Null));
-    let data_type = variable.type_annotation.clone();
+    let mut value = IRInstruction::Literal(IRLiteral::new(
+      AnalyzerValue::Null,
+      *variable.name.clone(),
+    ));
+    let annotation = variable
+      .type_annotation
+      .as_ref()
+      .map(|type_annotation| self.resolve_type(type_annotation));
 
     if let Some(initializer) = &variable.initializer {
-      let expression = self.analyzer(&initializer)?;
+      let expression = match &annotation {
+        Some(data_type) => self.analyze_assigned_value(initializer, data_type, &variable.name)?,
+        None => self.analyze_or_recover(initializer),
+      };
+
       match expression {
         IRInstruction::Literal(literal) => {
           value = IRInstruction::Literal(literal);
@@ -420,14 +552,64 @@ impl Visitor<AnalyzerResult> for Analyzer {
         IRInstruction::Array(array) => {
           value = IRInstruction::Array(array);
         }
+        IRInstruction::This(this) => {
+          value = IRInstruction::This(this);
+        }
+        IRInstruction::Get(get) => {
+          value = IRInstruction::Get(get);
+        }
+        IRInstruction::Set(set) => {
+          value = IRInstruction::Set(set);
+        }
+        IRInstruction::New(new) => {
+          value = IRInstruction::New(new);
+        }
+        IRInstruction::MethodCall(call) => {
+          value = IRInstruction::MethodCall(call);
+        }
+        IRInstruction::EnumValue(enum_value) => {
+          value = IRInstruction::EnumValue(enum_value);
+        }
+        IRInstruction::Match(ir_match) => {
+          value = IRInstruction::Match(ir_match);
+        }
+        IRInstruction::Function(function) => {
+          value = IRInstruction::Function(function);
+        }
+        IRInstruction::Lambda(lambda) => {
+          value = IRInstruction::Lambda(lambda);
+        }
+        IRInstruction::CallValue(call) => {
+          value = IRInstruction::CallValue(call);
+        }
+        IRInstruction::TemplateLiteral(template) => {
+          value = IRInstruction::TemplateLiteral(template);
+        }
+        IRInstruction::Index(index) => {
+          value = IRInstruction::Index(index);
+        }
+        IRInstruction::Error(error) => {
+          value = IRInstruction::Error(error);
+        }
         _ => (),
       }
     }
 
+    // A variable whose value has an error gets an unknown type.
+    let data_type = match annotation {
+      Some(data_type) => data_type,
+      None if Self::has_error(&[&value]) => DataType::None,
+      None => self.infer_variable_type(&value, &variable.name)?,
+    };
+
+    // A variable declared without a value has none until it is assigned.
     let variable = IRVariable::new(
       variable.name.span.literal.clone(),
       data_type.clone(),
-      Some(Box::new(value.clone())),
+      variable
+        .initializer
+        .as_ref()
+        .map(|_| Box::new(value.clone())),
       IRVariableMetadata::new(
         variable.metadata.is_mutable,
         variable.metadata.is_reference,
@@ -436,6 +618,7 @@ impl Visitor<AnalyzerResult> for Analyzer {
         false,
         true,
       ),
+      Some(*variable.name.clone()),
     );
 
     self.define(&variable.name);
@@ -453,7 +636,7 @@ impl Visitor<AnalyzerResult> for Analyzer {
     let mut ir_block = IRBlock::new(Vec::new(), Vec::new());
 
     for statement in &block.statements {
-      let result = self.analyze_statement(statement)?;
+      let result = self.analyze_statement_or_recover(statement);
       ir_block.instructions.push(result);
     }
 
@@ -465,13 +648,13 @@ impl Visitor<AnalyzerResult> for Analyzer {
   }
 
   fn visit_if_statement(&mut self, statement: &IfStatement) -> AnalyzerResult {
-    let condition = self.analyzer(&statement.condition)?;
-    let then_branch = self.analyze_statement(&statement.then_branch)?;
+    let condition = self.analyze_or_recover(&statement.condition);
+    let then_branch = self.analyze_statement_or_recover(&statement.then_branch);
 
     let else_branch: Option<Box<IRInstruction>> = if statement.else_branch.is_some() {
-      Some(Box::new(
-        self.analyze_statement(statement.else_branch.as_ref().unwrap())?,
-      ))
+      Some(Box::new(self.analyze_statement_or_recover(
+        statement.else_branch.as_ref().unwrap(),
+      )))
     } else {
       None
     };
@@ -480,6 +663,7 @@ impl Visitor<AnalyzerResult> for Analyzer {
       Box::new(condition),
       Box::new(then_branch),
       else_branch,
+      statement.token.clone(),
     ));
 
     Ok(instruction)
@@ -487,10 +671,14 @@ impl Visitor<AnalyzerResult> for Analyzer {
 
   fn visit_while_statement(&mut self, statement: &WhileStatement) -> AnalyzerResult {
     self.context.push(AnalyzerContext::Loop);
-    let condition = self.analyzer(&statement.condition)?;
-    let body = self.analyze_statement(&statement.body)?;
+    let condition = self.analyze_or_recover(&statement.condition);
+    let body = self.analyze_statement_or_recover(&statement.body);
 
-    let instruction = IRInstruction::While(IRWhile::new(Box::new(condition), Box::new(body)));
+    let instruction = IRInstruction::While(IRWhile::new(
+      Box::new(condition),
+      Box::new(body),
+      statement.token.clone(),
+    ));
 
     self.context.pop();
 
@@ -509,65 +697,44 @@ impl Visitor<AnalyzerResult> for Analyzer {
       ));
     }
 
+    let return_type = self.resolve_type(&statement.return_type.clone().unwrap_or(DataType::Void));
+
     self.declare(&statement.name.span.literal);
     self.define(&statement.name.span.literal);
+    self
+      .function_scopes
+      .push(FunctionScope::new(false, Some(return_type.clone())));
 
     for param in &statement.parameters {
       self.define_parameter(&param.name.span.literal);
-      let parameter = IRVariable::new(
-        param.name.span.literal.clone(),
-        param.data_type.clone(),
-        None,
-        IRVariableMetadata::new(
-          param.is_mutable,
-          param.is_reference,
-          true,
-          false,
-          false,
-          false,
-        ),
-      );
+      let parameter = self.parameter_signature(param);
 
       self.scopes_variables.push(parameter.clone());
 
       parameters.push(parameter);
     }
 
-    let mut ir: IRBlock = IRBlock::new(Vec::new(), Vec::new());
-
-    let is_extern = statement
-      .annotations
-      .clone()
-      .into_iter()
-      .find(|a| match a {
-        FunctionDecorator::Extern(_) => true,
-        _ => false,
-      })
-      .is_some();
+    let extern_module = statement.annotations.iter().find_map(|a| match a {
+      FunctionDecorator::Extern(path) => Some(path.span.literal.clone()),
+      _ => None,
+    });
 
     let mut current_function = IRFunction::new(
       statement.name.span.literal.clone(),
+      Self::generic_names(&statement.generics),
       parameters.clone(),
-      statement.return_type.clone().unwrap_or(DataType::Void),
+      return_type,
       None,
-      IRFunctionMetadata::new(false, statement.is_exported, false, is_extern),
+      IRFunctionMetadata::new(false, statement.is_exported, false, extern_module),
+      statement.name.clone(),
     );
 
     self.current_function = Some(current_function.clone());
 
-    for body in &statement.body {
-      let result = self.analyze_statement(body)?;
+    let ir = self.analyze_body(&statement.body, &statement.name);
 
-      match result {
-        IRInstruction::Variable(v) => {
-          self.scopes_variables.push(v.clone());
-          ir.scopes_variables.push(v);
-        }
-        _ => {
-          ir.instructions.push(result);
-        }
-      };
-    }
+    self.function_scopes.pop();
+    let ir = ir?;
 
     self.end_scope();
 
@@ -588,7 +755,10 @@ impl Visitor<AnalyzerResult> for Analyzer {
       .context
       .iter()
       .find(|context| match context {
-        AnalyzerContext::Function | AnalyzerContext::Method => true,
+        AnalyzerContext::Function
+        | AnalyzerContext::Method
+        | AnalyzerContext::Initializer
+        | AnalyzerContext::Lambda => true,
         _ => false,
       })
       .is_none()
@@ -598,26 +768,90 @@ impl Visitor<AnalyzerResult> for Analyzer {
       ));
     }
 
+    let expected = self
+      .function_scopes
+      .last()
+      .and_then(|scope| scope.return_type.clone());
+
     let value = &statement.value;
     if value.is_none() {
+      if let Some(expected) = expected.filter(|expected| *expected != DataType::Void) {
+        return Err(AnalyzerDiagnosticError::ReturnTypeMismatch(
+          expected,
+          DataType::Void,
+          *statement.keyword.clone(),
+        ));
+      }
+
       let instruction = IRInstruction::Return(IRReturn::new(
-        Box::new(IRInstruction::Literal(IRLiteral::new(AnalyzerValue::Null))),
+        Box::new(IRInstruction::Literal(IRLiteral::new(
+          AnalyzerValue::Null,
+          *statement.keyword.clone(),
+        ))),
         DataType::Void,
+        *statement.keyword.clone(),
       ));
 
       return Ok(instruction);
     }
 
-    let value = self.analyzer(&value.as_ref().unwrap())?;
+    let mut value = self.analyze_or_recover(value.as_ref().unwrap());
+
+    if let Some(expected) = &expected {
+      value = Self::promote(value, expected);
+    }
+
     let data_type = self.extract_data_type(&value);
 
-    let instruction = IRInstruction::Return(IRReturn::new(Box::new(value), data_type));
+    if let Some(expected) = expected {
+      // The error of a value that could not be analyzed is already reported.
+      let is_valid = Self::has_error(&[&value])
+        || (expected != DataType::Void && self.is_assignable(&expected, &data_type));
+
+      if !is_valid {
+        return Err(AnalyzerDiagnosticError::ReturnTypeMismatch(
+          expected,
+          data_type,
+          *statement.keyword.clone(),
+        ));
+      }
+    }
+
+    let instruction = IRInstruction::Return(IRReturn::new(
+      Box::new(value),
+      data_type,
+      *statement.keyword.clone(),
+    ));
 
     Ok(instruction)
   }
 
-  fn visit_class_statement(&mut self, _statement: &Class) -> AnalyzerResult {
-    todo!()
+  fn visit_class_statement(&mut self, statement: &Class) -> AnalyzerResult {
+    let name = statement.name.span.literal.clone();
+
+    if self.is_type_name(&name) || self.is_allready_declared(&name) {
+      return Err(AnalyzerDiagnosticError::ClassAlreadyDefined(name));
+    }
+
+    // The signatures are known before the bodies are analyzed, so the methods
+    // can use every member of the class.
+    let mut class = self.class_signature(statement);
+    self.declare_class(class.clone());
+    self.check_implements(statement, &class);
+
+    let enclosing_class = self.current_class.replace(name.clone());
+    self.context.push(AnalyzerContext::Class);
+
+    let result = self.analyze_class_body(statement, &mut class);
+
+    self.context.pop();
+    self.current_class = enclosing_class;
+
+    result?;
+
+    self.classes.insert(name, class.clone());
+
+    Ok(IRInstruction::Class(class))
   }
 
   fn visit_array_expression(&mut self, expression: &Array) -> AnalyzerResult {
@@ -625,13 +859,17 @@ impl Visitor<AnalyzerResult> for Analyzer {
     let mut element_types = Vec::new();
 
     for elem in &expression.elements {
-      let analyzed_elem = self.analyzer(elem)?;
+      let analyzed_elem = self.analyze_or_recover(elem);
       let elem_type = self.extract_data_type(&analyzed_elem);
 
       elements.push(analyzed_elem);
       element_types.push(elem_type);
     }
 
+    if Self::has_error(&elements.iter().collect::<Vec<_>>()) {
+      return Ok(IRInstruction::Error(IRError::new()));
+    }
+
     let first_type = element_types.first().unwrap_or(&DataType::None);
 
     if !element_types.iter().all(|t| t == first_type) {
@@ -643,6 +881,7 @@ impl Visitor<AnalyzerResult> for Analyzer {
     let instruction = IRInstruction::Array(IRArray::new(
       elements,
       DataType::Array(Box::new(first_type.clone())),
+      expression.token.clone(),
     ));
 
     Ok(instruction)
@@ -651,14 +890,11 @@ impl Visitor<AnalyzerResult> for Analyzer {
   fn visit_for_in_statement(&mut self, statement: &ForIn) -> AnalyzerResult {
     self.declare(&statement.variable.name.span.literal);
 
-    let iterable = self.analyzer(&*statement.iterable)?;
-    let data_type = self.extract_data_type(&iterable);
+    let iterable = self.analyze_or_recover(&statement.iterable);
+    let iterable_type = self.extract_data_type(&iterable);
 
-    if !self.is_iterable(&iterable) {
-      return Err(AnalyzerDiagnosticError::NotIterable(
-        statement.token.clone(),
-      ));
-    }
+    let element_type = Self::element_type(&iterable_type);
+    let data_type = element_type.clone().unwrap_or(DataType::None);
 
     self.begin_scope();
 
@@ -676,17 +912,28 @@ impl Visitor<AnalyzerResult> for Analyzer {
         false,
         false,
       ),
+      Some(*statement.variable.name.clone()),
     );
 
     self.scopes_variables.push(variable.clone());
 
-    let body = self.analyze_statement(&statement.body)?;
+    self.context.push(AnalyzerContext::Loop);
+    let body = self.analyze_statement_or_recover(&statement.body);
+    self.context.pop();
 
     self.end_scope();
 
+    // The body is analyzed first so its errors are reported as well.
+    if element_type.is_none() && !Self::has_error(&[&iterable]) {
+      return Err(AnalyzerDiagnosticError::NotIterable(
+        statement.token.clone(),
+      ));
+    }
+
     let instruction = IRInstruction::ForIn(IRForIn::new(
       variable,
       Box::new(iterable),
+      iterable_type,
       Box::new(body),
       statement.token.clone(),
     ));
@@ -700,7 +947,7 @@ impl Visitor<AnalyzerResult> for Analyzer {
     if !statement.is_std {
       self.resolve_module_import(statement, &mut block_stack)?;
     } else {
-      self.resolve_std_import(statement.module_path.span.literal.clone(), &mut block_stack);
+      self.resolve_std_import(&statement.module_path, &mut block_stack);
     }
 
     Ok(IRInstruction::Import(IRImport::new(
@@ -715,7 +962,7 @@ impl Visitor<AnalyzerResult> for Analyzer {
   }
 
   fn visit_break_statement(&mut self, statement: &BreakStatement) -> AnalyzerResult {
-    let is_loop = self.context.iter().find(|context| match context {
+    let is_loop = self.enclosing_contexts().find(|context| match context {
       AnalyzerContext::Loop | AnalyzerContext::Switch => true,
       _ => false,
     });
@@ -729,97 +976,736 @@ impl Visitor<AnalyzerResult> for Analyzer {
     Ok(IRInstruction::Break(IRBreak::new(statement.token.clone())))
   }
 
-  fn visit_continue_statement(&mut self, statement: &Continue) -> AnalyzerResult {
-    let is_loop = self.context.iter().find(|context| match context {
-      AnalyzerContext::Loop => true,
-      _ => false,
-    });
+  fn visit_this_expression(&mut self, expression: &This) -> AnalyzerResult {
+    match (self.function_context(), &self.current_class) {
+      (Some(AnalyzerContext::Method | AnalyzerContext::Initializer), Some(class_name)) => {
+        let instruction =
+          IRInstruction::This(IRThis::new(class_name.clone(), expression.keyword.clone()));
 
-    if is_loop.is_none() {
-      return Err(AnalyzerDiagnosticError::ContinueOutsideLoop(
-        statement.token.clone(),
-      ));
+        self.capture(&"this".to_string());
+
+        Ok(instruction)
+      }
+      _ => Err(AnalyzerDiagnosticError::ThisOutsideMethod(
+        expression.keyword.clone(),
+      )),
     }
+  }
 
-    Ok(IRInstruction::Continue(IRContinue::new(
-      statement.token.clone(),
+  fn visit_get_expression(&mut self, expression: &Get) -> AnalyzerResult {
+    if let Some(enum_name) = self.enum_name(&expression.object) {
+      return self.enum_value(&enum_name, &expression.name, None);
+    }
+
+    let (object, class, is_static) = self.member_object(&expression.object, &expression.name)?;
+    let name = &expression.name.span.literal;
+
+    let (metadata, data_type, is_method) = match (class.field(name), class.method(name)) {
+      (Some(field), _) if field.metadata.is_static == is_static => {
+        (field.metadata.clone(), field.data_type.clone(), false)
+      }
+      (_, Some(method)) if method.metadata.is_static == is_static => (
+        method.metadata.clone(),
+        Self::function_type(&method.function),
+        true,
+      ),
+      _ => {
+        return Err(AnalyzerDiagnosticError::UndefinedProperty(
+          class.name,
+          expression.name.clone(),
+        ))
+      }
+    };
+
+    self.check_member_access(&class, &metadata, &expression.name)?;
+
+    Ok(IRInstruction::Get(IRGet::new(
+      Box::new(object),
+      name.clone(),
+      data_type,
+      metadata,
+      is_method,
+      expression.name.clone(),
     )))
   }
-}
 
-impl Analyzer {
-  pub fn new(current_file: String) -> Self {
-    let mut irs = HashMap::new();
-    let block_stack: HashMap<String, bool> = HashMap::new();
+  fn visit_set_expression(&mut self, expression: &Set) -> AnalyzerResult {
+    let (object, class, is_static) = self.member_object(&expression.object, &expression.name)?;
 
-    irs.insert(current_file.clone(), Vec::new());
+    let field = match class.field(&expression.name.span.literal) {
+      Some(field) if field.metadata.is_static == is_static => field.clone(),
+      _ => {
+        return Err(AnalyzerDiagnosticError::UndefinedProperty(
+          class.name,
+          expression.name.clone(),
+        ))
+      }
+    };
 
-    Self {
-      irs,
-      diagnostics: Vec::new(),
-      block_stack: vec![block_stack],
-      scopes_variables: Vec::new(),
-      current_function: None,
-      current_file,
-      context: Vec::new(),
+    self.check_member_access(&class, &field.metadata, &expression.name)?;
+
+    let in_constructor = matches!(self.function_context(), Some(AnalyzerContext::Initializer))
+      && matches!(object, IRInstruction::This(_));
+
+    if field.metadata.is_readonly && !in_constructor {
+      return Err(AnalyzerDiagnosticError::ReadonlyPropertyAssignment(
+        class.name,
+        expression.name.clone(),
+      ));
     }
+
+    let value =
+      self.analyze_assigned_value(&expression.value, &field.data_type, &expression.name)?;
+
+    Ok(IRInstruction::Set(IRSet::new(
+      Box::new(object),
+      field.name,
+      Box::new(value),
+      field.data_type,
+      field.metadata,
+      expression.name.clone(),
+    )))
   }
 
-  pub fn analyze(&mut self, statements: &Vec<Statement>) {
-    for statement in statements {
-      match self.analyze_statement(statement) {
-        Ok(ir) => {
-          let mut current_ir = self.irs.get_mut(&self.current_file).unwrap();
-          current_ir.push(ir.clone());
-        }
-        Err(e) => self.diagnostics.push(e),
+  fn visit_new_expression(&mut self, expression: &NewExpression) -> AnalyzerResult {
+    let class = match self.classes.get(&expression.class_name.span.literal) {
+      Some(class) => class.clone(),
+      None => {
+        return Err(AnalyzerDiagnosticError::UndefinedClass(
+          expression.class_name.clone(),
+        ))
       }
-    }
-  }
+    };
 
-  fn analyzer(&mut self, expression: &Expression) -> AnalyzerResult {
-    expression.accept(self)
-  }
+    let parameters = match &class.constructor {
+      Some(constructor) => {
+        self.check_member_access(&class, &constructor.metadata, &expression.class_name)?;
 
-  fn analyze_statement(&mut self, statement: &Statement) -> AnalyzerResult {
-    statement.accept(self)
-  }
+        constructor.function.parameters.clone()
+      }
+      None => Vec::new(),
+    };
 
-  fn begin_scope(&mut self) {
-    self
-      .block_stack
-      .push(self.block_stack.clone().last().unwrap().clone());
-  }
+    let (arguments, type_arguments) = self.infer_arguments(
+      &parameters,
+      &class.generics,
+      &expression.arguments,
+      &expression.keyword,
+    )?;
 
-  fn end_scope(&mut self) {
-    self.block_stack.pop().unwrap();
+    let data_type = if class.generics.is_empty() {
+      DataType::ClassType(class.name.clone())
+    } else {
+      DataType::GenericType {
+        base: Box::new(DataType::ClassType(class.name.clone())),
+        parameters: type_arguments,
+      }
+    };
+
+    Ok(IRInstruction::New(IRNew::new(
+      class.name,
+      arguments,
+      data_type,
+      expression.keyword.clone(),
+    )))
   }
 
-  fn declare(&mut self, name: &String) {
-    if self.block_stack.is_empty() {
-      return;
+  fn visit_enum_statement(&mut self, statement: &Enum) -> AnalyzerResult {
+    let name = statement.name.span.literal.clone();
+
+    if self.is_type_name(&name) || self.is_allready_declared(&name) {
+      return Err(AnalyzerDiagnosticError::EnumAlreadyDefined(
+        name,
+        statement.name.clone(),
+      ));
     }
 
-    let current_block = self.block_stack.last_mut().unwrap();
+    let mut variants: Vec<IREnumVariant> = Vec::new();
+    let mut value_type: Option<DataType> = None;
 
-    current_block.insert(name.clone(), false);
-  }
+    for variant in &statement.variants {
+      let value = match &variant.value {
+        Some(expression) => match self.analyzer(expression)? {
+          IRInstruction::Literal(IRLiteral {
+            value: value @ (AnalyzerValue::String(_) | AnalyzerValue::Int(_)),
+            ..
+          }) if value_type.get_or_insert(value.to_data_type()) == &value.to_data_type() => {
+            Some(value)
+          }
+          _ => {
+            return Err(AnalyzerDiagnosticError::InvalidEnumValue(
+              name,
+              variant.name.clone(),
+            ))
+          }
+        },
+        None => None,
+      };
 
-  fn resolve_std_import(&mut self, lib: String, block_stack: &mut HashMap<String, bool>) {
+      variants.push(IREnumVariant::new(
+        variant.name.span.literal.clone(),
+        variant
+          .fields
+          .iter()
+          .map(|field| self.resolve_type(field))
+          .collect(),
+        value,
+      ));
+    }
+
+    let ir_enum = IREnum::new(name, Self::generic_names(&statement.generics), variants);
+    self.declare_enum(ir_enum.clone());
+
+    Ok(IRInstruction::Enum(ir_enum))
+  }
+
+  fn visit_match_expression(&mut self, expression: &Match) -> AnalyzerResult {
+    self.analyze_match(expression, true)
+  }
+
+  fn visit_lambda_expression(&mut self, expression: &Lambda) -> AnalyzerResult {
+    let scopes_variables = self.scopes_variables.clone();
+
+    let return_type = expression
+      .return_type
+      .as_ref()
+      .map(|return_type| self.resolve_type(return_type));
+
+    self.begin_scope();
+    self.context.push(AnalyzerContext::Lambda);
+    self
+      .function_scopes
+      .push(FunctionScope::new(true, return_type.clone()));
+
+    let mut parameters = Vec::<IRVariable>::new();
+
+    for param in &expression.parameters {
+      self.define_parameter(&param.name.span.literal);
+      let parameter = self.parameter_signature(param);
+
+      self.scopes_variables.push(parameter.clone());
+
+      parameters.push(parameter);
+    }
+
+    let body = self.analyze_body(&expression.body, &expression.token);
+
+    let scope = self.function_scopes.pop().unwrap();
+    self.context.pop();
+    self.end_scope();
+    self.scopes_variables = scopes_variables;
+
+    let body = body?;
+
+    // A lambda written with an expression returns the type of the expression.
+    let return_type = match (return_type, body.instructions.as_slice()) {
+      (Some(return_type), _) => return_type,
+      (None, [IRInstruction::Return(ir_return)]) => ir_return.data_type.clone(),
+      (None, _) => DataType::Void,
+    };
+
+    let function = IRFunction::new(
+      "@lambda".to_string(),
+      Vec::new(),
+      parameters,
+      return_type,
+      Some(Box::new(body)),
+      IRFunctionMetadata::new(false, false, false, None),
+      expression.token.clone(),
+    );
+
+    Ok(IRInstruction::Lambda(IRLambda::new(
+      function,
+      scope.captures,
+      expression.token.clone(),
+    )))
+  }
+
+  fn visit_template_literal_expression(&mut self, expression: &TemplateLiteral) -> AnalyzerResult {
+    let mut parts = Vec::new();
+
+    for part in &expression.parts {
+      let instruction = match part {
+        TemplatePart::Text(text) => IRInstruction::Literal(IRLiteral::new(
+          AnalyzerValue::String(text.span.literal.clone()),
+          text.clone(),
+        )),
+        TemplatePart::Expression(value) => self.analyze_or_recover(value),
+      };
+
+      // Only the values that both backends print the same way.
+      match self.extract_data_type(&instruction) {
+        DataType::String
+        | DataType::Int
+        | DataType::Float
+        | DataType::Boolean
+        | DataType::Char
+        | DataType::Null
+        | DataType::None => (),
+        data_type => {
+          return Err(AnalyzerDiagnosticError::InvalidTemplateExpression(
+            data_type,
+            expression.token.clone(),
+          ))
+        }
+      }
+
+      parts.push(instruction);
+    }
+
+    Ok(IRInstruction::TemplateLiteral(IRTemplateLiteral::new(
+      parts,
+      expression.token.clone(),
+    )))
+  }
+
+  fn visit_index_expression(&mut self, expression: &Index) -> AnalyzerResult {
+    let object = self.analyze_or_recover(&expression.object);
+    let object_type = self.extract_data_type(&object);
+    let index = self.analyze_index(&expression.index, &expression.bracket)?;
+
+    let data_type = match &object_type {
+      DataType::Array(element) => *element.clone(),
+      DataType::String => DataType::String,
+      _ if Self::has_error(&[&object]) => DataType::None,
+      _ => {
+        return Err(AnalyzerDiagnosticError::NotIndexable(
+          object_type,
+          expression.bracket.clone(),
+        ))
+      }
+    };
+
+    Ok(IRInstruction::Index(IRIndex::new(
+      Box::new(object),
+      Box::new(index),
+      data_type,
+      object_type,
+      expression.bracket.clone(),
+    )))
+  }
+
+  /**
+    A missing expression, the parser already reported it.
+  */
+  fn visit_error_expression(&mut self, _expression: &ErrorExpression) -> AnalyzerResult {
+    Ok(IRInstruction::Error(IRError::new()))
+  }
+
+  fn visit_set_index_expression(&mut self, expression: &SetIndex) -> AnalyzerResult {
+    let object = self.analyze_or_recover(&expression.object);
+    let object_type = self.extract_data_type(&object);
+    let index = self.analyze_index(&expression.index, &expression.bracket)?;
+
+    // Strings are immutable, only the elements of an array can be replaced.
+    let data_type = match &object_type {
+      DataType::Array(element) => *element.clone(),
+      _ if Self::has_error(&[&object]) => DataType::None,
+      _ => {
+        return Err(AnalyzerDiagnosticError::NotIndexable(
+          object_type,
+          expression.bracket.clone(),
+        ))
+      }
+    };
+
+    if let (IRInstruction::Variable(variable), Expression::Variable(name)) =
+      (&object, &*expression.object)
+    {
+      if !variable.metadata.is_mutable {
+        return Err(AnalyzerDiagnosticError::InvalidReassignedVariable(
+          name.name.span.clone(),
+        ));
+      }
+    }
+
+    let value = self.analyze_assigned_value(&expression.value, &data_type, &expression.bracket)?;
+
+    Ok(IRInstruction::SetIndex(IRSetIndex::new(
+      Box::new(object),
+      Box::new(index),
+      Box::new(value),
+      data_type,
+      expression.bracket.clone(),
+    )))
+  }
+
+  fn visit_interface_statement(&mut self, statement: &Interface) -> AnalyzerResult {
+    let name = statement.name.span.literal.clone();
+
+    if self.is_type_name(&name) || self.is_allready_declared(&name) {
+      return Err(AnalyzerDiagnosticError::TypeAlreadyDefined(
+        name,
+        statement.name.clone(),
+      ));
+    }
+
+    let mut fields: Vec<IRClassField> = Vec::new();
+    let mut methods: Vec<IRMethod> = Vec::new();
+
+    for parent in &statement.extends {
+      match self.interfaces.get(&parent.span.literal) {
+        Some(interface) => {
+          fields.extend(interface.fields.clone());
+          methods.extend(interface.methods.clone());
+        }
+        None => return Err(AnalyzerDiagnosticError::UndefinedInterface(parent.clone())),
+      }
+    }
+
+    let metadata = IRClassMemberMetadata::new(true, false, false);
+
+    for field in &statement.fields {
+      fields.retain(|f| f.name != field.name.span.literal);
+      fields.push(IRClassField::new(
+        field.name.span.literal.clone(),
+        self.resolve_type(&field.data_type),
+        None,
+        metadata.clone(),
+      ));
+    }
+
+    for method in &statement.methods {
+      let function = IRFunction::new(
+        method.name.span.literal.clone(),
+        Vec::new(),
+        method
+          .parameters
+          .iter()
+          .map(|param| self.parameter_signature(param))
+          .collect(),
+        self.resolve_type(&method.return_type),
+        None,
+        IRFunctionMetadata::new(false, false, false, None),
+        method.name.clone(),
+      );
+
+      methods.retain(|m| m.function.name != function.name);
+      methods.push(IRMethod::new(function, metadata.clone()));
+    }
+
+    let interface = IRInterface::new(name, fields, methods);
+    self.declare_interface(interface.clone());
+
+    Ok(IRInstruction::Interface(interface))
+  }
+
+  /**
+    A statement that could not be parsed, the parser already reported it.
+  */
+  fn visit_error_statement(&mut self, _statement: &ErrorStatement) -> AnalyzerResult {
+    Ok(IRInstruction::Error(IRError::new()))
+  }
+
+  fn visit_type_alias_statement(&mut self, statement: &TypeAlias) -> AnalyzerResult {
+    let name = statement.name.span.literal.clone();
+
+    if self.is_type_name(&name) || self.is_allready_declared(&name) {
+      return Err(AnalyzerDiagnosticError::TypeAlreadyDefined(
+        name,
+        statement.name.clone(),
+      ));
+    }
+
+    let alias = IRTypeAlias::new(name, self.resolve_type(&statement.value));
+    self.declare_type_alias(alias.clone());
+
+    Ok(IRInstruction::TypeAlias(alias))
+  }
+
+  fn visit_switch_statement(&mut self, statement: &Switch) -> AnalyzerResult {
+    let value = self.analyze_or_recover(&statement.value);
+    let data_type = self.extract_data_type(&value);
+
+    let mut cases: Vec<IRSwitchCase> = Vec::new();
+    let mut values: HashSet<String> = HashSet::new();
+
+    self.context.push(AnalyzerContext::Switch);
+
+    for case in &statement.cases {
+      let result = self.analyze_switch_case(case, &data_type);
+
+      let ir_case = match result {
+        Ok(ir_case) => ir_case,
+        Err(error) => {
+          self.context.pop();
+          return Err(error);
+        }
+      };
+
+      let key = match ir_case.value.as_deref() {
+        None => Some("default".to_string()),
+        Some(IRInstruction::Literal(literal)) => Some(format!("{:?}", literal.value)),
+        Some(IRInstruction::EnumValue(value)) if value.arguments.is_empty() => {
+          Some(format!("{}.{}", value.enum_name, value.variant))
+        }
+        _ => None,
+      };
+
+      if let Some(key) = key {
+        if !values.insert(key) {
+          let case_name = match &case.value {
+            Some(value) => value.to_string(),
+            None => "default".to_string(),
+          };
+
+          self
+            .diagnostics
+            .push(AnalyzerDiagnosticError::DuplicateSwitchCase(
+              case_name,
+              case.token.clone(),
+            ));
+        }
+      }
+
+      cases.push(ir_case);
+    }
+
+    self.context.pop();
+
+    Ok(IRInstruction::Switch(IRSwitch::new(
+      Box::new(value),
+      cases,
+      statement.keyword.clone(),
+    )))
+  }
+
+  fn visit_continue_statement(&mut self, statement: &Continue) -> AnalyzerResult {
+    let is_loop = self.enclosing_contexts().find(|context| match context {
+      AnalyzerContext::Loop => true,
+      _ => false,
+    });
+
+    if is_loop.is_none() {
+      return Err(AnalyzerDiagnosticError::ContinueOutsideLoop(
+        statement.token.clone(),
+      ));
+    }
+
+    Ok(IRInstruction::Continue(IRContinue::new(
+      statement.token.clone(),
+    )))
+  }
+}
+
+impl Analyzer {
+  pub fn new(current_file: String) -> Self {
+    let mut irs = HashMap::new();
+    let block_stack: HashMap<String, bool> = HashMap::new();
+
+    irs.insert(current_file.clone(), Vec::new());
+
+    Self {
+      irs,
+      diagnostics: Vec::new(),
+      warnings: Vec::new(),
+      block_stack: vec![block_stack],
+      scopes_variables: Vec::new(),
+      current_function: None,
+      current_file,
+      global_functions: Vec::new(),
+      classes: HashMap::new(),
+      enums: HashMap::new(),
+      interfaces: HashMap::new(),
+      type_aliases: HashMap::new(),
+      current_class: None,
+      context: Vec::new(),
+      function_scopes: vec![FunctionScope::new(false, None)],
+    }
+  }
+
+  /**
+    Declares a top level variable that is defined outside of the analyzed
+    code, for example by the application that embeds the VM.
+  */
+  pub fn declare_global(&mut self, name: &str, data_type: DataType, is_mutable: bool) {
+    let variable = IRVariable::new(
+      name.to_string(),
+      data_type,
+      None,
+      IRVariableMetadata::new(is_mutable, false, false, false, false, false),
+      None,
+    );
+
+    self.block_stack[0].insert(name.to_string(), true);
+    self.scopes_variables.push(variable);
+  }
+
+  /**
+    Declares a function that was compiled before, it can be called but no
+    code is generated for it again.
+  */
+  pub fn declare_function(&mut self, function: IRFunction) {
+    self.global_functions.retain(|f| f.name != function.name);
+    self.global_functions.push(function);
+  }
+
+  /**
+    Declares a class that was compiled before, like `declare_function`.
+  */
+  pub fn declare_class(&mut self, class: IRClass) {
+    let variable = IRVariable::new(
+      class.name.clone(),
+      DataType::ClassType(class.name.clone()),
+      None,
+      IRVariableMetadata::new(false, false, false, false, true, false),
+      None,
+    );
+
+    self.declare(&class.name);
+    self.define(&class.name);
+    self.scopes_variables.push(variable);
+    self.classes.insert(class.name.clone(), class);
+  }
+
+  /**
+    Declares an enum that was compiled before, like `declare_function`.
+  */
+  pub fn declare_enum(&mut self, ir_enum: IREnum) {
+    self.enums.insert(ir_enum.name.clone(), ir_enum);
+  }
+
+  /**
+    Declares an interface that was compiled before, like `declare_function`.
+  */
+  pub fn declare_interface(&mut self, interface: IRInterface) {
+    self.interfaces.insert(interface.name.clone(), interface);
+  }
+
+  /**
+    Declares a type alias that was compiled before, like `declare_function`.
+  */
+  pub fn declare_type_alias(&mut self, alias: IRTypeAlias) {
+    self.type_aliases.insert(alias.name, alias.data_type);
+  }
+
+  pub fn analyze(&mut self, statements: &Vec<Statement>) {
+    let errors = self.diagnostics.len();
+    let start = self.irs[&self.current_file].len();
+
+    for statement in statements {
+      let checkpoint = self.checkpoint();
+
+      match self.analyze_statement(statement) {
+        Ok(ir) => {
+          let mut current_ir = self.irs.get_mut(&self.current_file).unwrap();
+          current_ir.push(ir.clone());
+        }
+        Err(e) => {
+          self.recover(checkpoint, e);
+        }
+      }
+    }
+
+    if self.diagnostics.len() == errors {
+      let instructions = self.irs[&self.current_file][start..].to_vec();
+      self.check_control_flow(&instructions, None);
+    }
+  }
+
+  fn analyzer(&mut self, expression: &Expression) -> AnalyzerResult {
+    expression.accept(self)
+  }
+
+  fn analyze_statement(&mut self, statement: &Statement) -> AnalyzerResult {
+    statement.accept(self)
+  }
+
+  /**
+    Analyzes an expression, when it has an error the error is reported and
+    the expression is replaced by an `IRInstruction::Error`, so the analysis
+    of the code around it goes on.
+  */
+  fn analyze_or_recover(&mut self, expression: &Expression) -> IRInstruction {
+    let checkpoint = self.checkpoint();
+
+    match self.analyzer(expression) {
+      Ok(instruction) => instruction,
+      Err(error) => self.recover(checkpoint, error),
+    }
+  }
+
+  /**
+    Analyzes a statement of a block like `analyze_or_recover`, the statements
+    after one with an error are still analyzed.
+  */
+  fn analyze_statement_or_recover(&mut self, statement: &Statement) -> IRInstruction {
+    let checkpoint = self.checkpoint();
+
+    match self.analyze_statement(statement) {
+      Ok(instruction) => instruction,
+      Err(error) => self.recover(checkpoint, error),
+    }
+  }
+
+  fn checkpoint(&self) -> Checkpoint {
+    Checkpoint {
+      block_stack: self.block_stack.len(),
+      scopes_variables: self.scopes_variables.len(),
+      context: self.context.len(),
+      function_scopes: self.function_scopes.len(),
+    }
+  }
+
+  fn recover(&mut self, checkpoint: Checkpoint, error: AnalyzerDiagnosticError) -> IRInstruction {
+    self.block_stack.truncate(checkpoint.block_stack);
+    self.scopes_variables.truncate(checkpoint.scopes_variables);
+    self.context.truncate(checkpoint.context);
+    self.function_scopes.truncate(checkpoint.function_scopes);
+    self.diagnostics.push(error);
+
+    IRInstruction::Error(IRError::new())
+  }
+
+  /**
+    An expression with an operand that could not be analyzed is not checked,
+    the error of the operand is already reported.
+  */
+  fn has_error(instructions: &[&IRInstruction]) -> bool {
+    instructions
+      .iter()
+      .any(|instruction| matches!(instruction, IRInstruction::Error(_)))
+  }
+
+  fn begin_scope(&mut self) {
+    self
+      .block_stack
+      .push(self.block_stack.clone().last().unwrap().clone());
+  }
+
+  fn end_scope(&mut self) {
+    self.block_stack.pop().unwrap();
+  }
+
+  fn declare(&mut self, name: &String) {
+    if self.block_stack.is_empty() {
+      return;
+    }
+
+    let current_block = self.block_stack.last_mut().unwrap();
+
+    current_block.insert(name.clone(), false);
+  }
+
+  fn resolve_std_import(&mut self, module_path: &Token, block_stack: &mut HashMap<String, bool>) {
+    let lib = module_path.span.literal.clone();
     let mut current_ir = self.irs.get_mut(&self.current_file).unwrap();
     match lib.clone().as_str() {
       "std:io" => {
         current_ir.push(IRInstruction::Function(IRFunction::new(
           "println".to_string(),
+          Vec::new(),
           vec![IRVariable::new(
             "message".to_string(),
             DataType::None,
             None,
             IRVariableMetadata::new(false, false, true, false, false, false),
+            None,
           )],
           DataType::Void,
           None,
-          IRFunctionMetadata::new(false, true, true, true),
+          IRFunctionMetadata::new(false, true, true, Some(lib.clone())),
+          module_path.clone(),
         )));
 
         block_stack.insert("println".to_string(), true);
@@ -827,15 +1713,18 @@ impl Analyzer {
       "std:string" => {
         current_ir.push(IRInstruction::Function(IRFunction::new(
           "toString".to_string(),
+          Vec::new(),
           vec![IRVariable::new(
             "value".to_string(),
             DataType::None,
             None,
             IRVariableMetadata::new(false, false, true, false, false, false),
+            None,
           )],
           DataType::String,
           None,
-          IRFunctionMetadata::new(false, true, true, true),
+          IRFunctionMetadata::new(false, true, true, Some(lib.clone())),
+          module_path.clone(),
         )));
 
         block_stack.insert("toString".to_string(), true);
@@ -858,12 +1747,7 @@ impl Analyzer {
         let mut parser: Parser = Parser::new(lexer.tokens);
         let statements = parser.parse();
 
-        match statements {
-          Ok(parser_reult) => {
-            analyzer.analyze(&parser_reult);
-          }
-          Err(_) => {}
-        }
+        analyzer.analyze(&statements);
       }
       Err(_) => {
         return Err(AnalyzerDiagnosticError::ModuleNotFound(
@@ -875,6 +1759,7 @@ impl Analyzer {
     analyzer.diagnostics.iter().for_each(|d| {
       self.diagnostics.push(d.clone());
     });
+    self.warnings.extend(analyzer.warnings);
 
     let current_ir = analyzer
       .irs
@@ -919,10 +1804,12 @@ impl Analyzer {
               current_ir.push(
                 IRInstruction::Function(IRFunction::new(
                   symbol.alias.as_ref().unwrap().span.literal.clone(),
+                  f.generics.clone(),
                   f.parameters.clone(),
                   f.return_type.clone(),
                   f.body.clone(),
                   metadata,
+                  symbol.alias.clone().unwrap(),
                 ))
                 .clone(),
               );
@@ -932,10 +1819,12 @@ impl Analyzer {
               current_ir.push(
                 IRInstruction::Function(IRFunction::new(
                   symbol.name.span.literal.clone(),
+                  f.generics.clone(),
                   f.parameters.clone(),
                   f.return_type.clone(),
                   f.body.clone(),
                   metadata,
+                  symbol.name.clone(),
                 ))
                 .clone(),
               );
@@ -967,16 +1856,1385 @@ impl Analyzer {
     let current_block = self.block_stack.last_mut().unwrap();
 
     current_block.insert(name.clone(), true);
-  }
 
-  fn define_parameter(&mut self, name: &String) {
-    if self.block_stack.is_empty() {
+    if self.block_stack.len() == 1 {
       return;
     }
 
-    let current_block = self.block_stack.last_mut().unwrap();
+    if let Some(scope) = self.function_scopes.last_mut() {
+      scope.locals.insert(name.clone());
+    }
+  }
+
+  fn define_parameter(&mut self, name: &String) {
+    if self.block_stack.is_empty() {
+      return;
+    }
+
+    let current_block = self.block_stack.last_mut().unwrap();
+
+    current_block.insert(name.clone(), true);
+
+    if let Some(scope) = self.function_scopes.last_mut() {
+      scope.locals.insert(name.clone());
+    }
+  }
+
+  /**
+    Analyzes the statements of a function body, the variables it declares are
+    kept apart from the instructions. `name` is the token the errors of its
+    control flow point at.
+  */
+  fn analyze_body(
+    &mut self,
+    body: &Vec<Statement>,
+    name: &Token,
+  ) -> Result<IRBlock, AnalyzerDiagnosticError> {
+    let errors = self.diagnostics.len();
+    let mut ir: IRBlock = IRBlock::new(Vec::new(), Vec::new());
+
+    for statement in body {
+      let result = self.analyze_statement_or_recover(statement);
+
+      match result {
+        IRInstruction::Variable(v) => {
+          self.scopes_variables.push(v.clone());
+          ir.scopes_variables.push(v);
+        }
+        _ => {
+          ir.instructions.push(result);
+        }
+      };
+    }
+
+    let returns_value = matches!(
+      self.function_scopes.last().and_then(|scope| scope.return_type.as_ref()),
+      Some(return_type) if *return_type != DataType::Void
+    );
+
+    // Functions declared without a body are defined somewhere else.
+    if !body.is_empty() && self.diagnostics.len() == errors {
+      self.check_control_flow(&ir.instructions, returns_value.then_some(name));
+    }
+
+    Ok(ir)
+  }
+
+  /**
+    Reports the code that can not run and the variables read before they are
+    assigned. `returns` is the name of a function that must return a value,
+    its body must not end without a `return`.
+  */
+  fn check_control_flow(&mut self, instructions: &[IRInstruction], returns: Option<&Token>) {
+    let graph = ControlFlowGraph::new(instructions);
+
+    for token in graph.unreachable_code() {
+      self
+        .warnings
+        .push(AnalyzerDiagnosticWarning::UnreachableCode(token));
+    }
+
+    for token in graph.unassigned_reads() {
+      self
+        .diagnostics
+        .push(AnalyzerDiagnosticError::UnassignedVariable(token));
+    }
+
+    if let Some(name) = returns.filter(|_| graph.falls_through()) {
+      self
+        .diagnostics
+        .push(AnalyzerDiagnosticError::MissingReturn(name.clone()));
+    }
+  }
+
+  fn analyze_arguments(
+    &mut self,
+    parameters: &[IRVariable],
+    arguments: &[Expression],
+    token: &Token,
+  ) -> Result<Vec<IRInstruction>, AnalyzerDiagnosticError> {
+    let (instructions, _) = self.infer_arguments(parameters, &[], arguments, token)?;
+
+    Ok(instructions)
+  }
+
+  /**
+    Analyzes the arguments of a call to a function with the type parameters
+    `generics`, the type arguments are inferred from the arguments and are
+    returned with them. An argument with an error does not stop the analysis
+    of the others, its error is reported and the call keeps its return type.
+  */
+  fn infer_arguments(
+    &mut self,
+    parameters: &[IRVariable],
+    generics: &[String],
+    arguments: &[Expression],
+    token: &Token,
+  ) -> Result<(Vec<IRInstruction>, Vec<DataType>), AnalyzerDiagnosticError> {
+    if parameters.len() != arguments.len() {
+      return Err(AnalyzerDiagnosticError::InvalidNumberOfArguments(
+        parameters.len(),
+        arguments.len(),
+        token.clone(),
+      ));
+    }
+
+    let mut instructions = Vec::<IRInstruction>::new();
+    let mut type_arguments = vec![DataType::None; generics.len()];
+
+    for (parameter, arg) in parameters.iter().zip(arguments) {
+      let arg_type = Self::promote(self.analyze_or_recover(arg), &parameter.data_type);
+      let kind = self.extract_data_type(&arg_type);
+
+      if Self::has_error(&[&arg_type]) {
+        instructions.push(arg_type);
+        continue;
+      }
+
+      let is_valid = match &parameter.data_type {
+        DataType::None => true,
+        data_type if !generics.is_empty() => {
+          self.infer_type(data_type, &kind, generics, &mut type_arguments)
+        }
+        DataType::GenericType { .. } | DataType::UnionType(_) | DataType::ClassType(_) => {
+          self.is_assignable(&parameter.data_type, &kind)
+        }
+        data_type => *data_type == kind,
+      };
+
+      if !is_valid {
+        // The type parameters that were not inferred yet keep their names.
+        let inferred: Vec<DataType> = generics
+          .iter()
+          .zip(&type_arguments)
+          .map(|(generic, argument)| match argument {
+            DataType::None => DataType::ClassType(generic.clone()),
+            argument => argument.clone(),
+          })
+          .collect();
+
+        self
+          .diagnostics
+          .push(AnalyzerDiagnosticError::ArgumentTypeMismatch(
+            Self::substitute_type(generics, &inferred, &parameter.data_type),
+            kind,
+            token.clone(),
+          ));
+      }
+
+      match &arg_type {
+        IRInstruction::Variable(v) => {
+          if !v.metadata.is_mutable && parameter.metadata.is_mutable {
+            self.diagnostics.push(
+              AnalyzerDiagnosticError::ImmutableVariableAsMutableParameter(
+                parameter.name.clone(),
+                v.name.clone(),
+                token.clone(),
+              ),
+            );
+          }
+        }
+        _ => (),
+      };
+
+      instructions.push(arg_type);
+    }
+
+    Ok((instructions, type_arguments))
+  }
+
+  /**
+    Calls a value with a function type, a variable or a parameter holding a
+    lambda or the result of another call, `make()(1)`.
+  */
+  fn analyze_value_call(&mut self, callee: IRInstruction, call: &Call) -> AnalyzerResult {
+    let (parameters, return_type) = match self.extract_data_type(&callee) {
+      DataType::Callable(parameters, return_type) => (parameters, *return_type),
+      _ => return Err(AnalyzerDiagnosticError::NotCallable(call.paren.clone())),
+    };
+
+    let parameters: Vec<IRVariable> = parameters
+      .into_iter()
+      .map(|data_type| {
+        IRVariable::new(
+          String::new(),
+          data_type,
+          None,
+          IRVariableMetadata::new(false, false, true, false, false, false),
+          None,
+        )
+      })
+      .collect();
+
+    let arguments = self.analyze_arguments(&parameters, &call.arguments, &call.paren)?;
+
+    let instruction = match callee {
+      IRInstruction::Variable(variable) => IRInstruction::Call(IRCall::new(
+        variable.name,
+        arguments,
+        return_type,
+        call.paren.clone(),
+      )),
+      callee => IRInstruction::CallValue(IRCallValue::new(
+        Box::new(callee),
+        arguments,
+        return_type,
+        call.paren.clone(),
+      )),
+    };
+
+    Ok(instruction)
+  }
+
+  fn analyze_method_call(&mut self, get: &Get, call: &Call) -> AnalyzerResult {
+    let (object, class, is_static) = self.member_object(&get.object, &get.name)?;
+
+    let method = match class.method(&get.name.span.literal) {
+      Some(method) if method.metadata.is_static == is_static => method.clone(),
+      _ => {
+        return Err(AnalyzerDiagnosticError::UndefinedProperty(
+          class.name,
+          get.name.clone(),
+        ))
+      }
+    };
+
+    self.check_member_access(&class, &method.metadata, &get.name)?;
+
+    let (arguments, type_arguments) = self.infer_arguments(
+      &method.function.parameters,
+      &method.function.generics,
+      &call.arguments,
+      &call.paren,
+    )?;
+
+    let return_type = Self::substitute_type(
+      &method.function.generics,
+      &type_arguments,
+      &method.function.return_type,
+    );
+
+    Ok(IRInstruction::MethodCall(IRMethodCall::new(
+      Box::new(object),
+      method.function.name,
+      arguments,
+      return_type,
+      method.metadata,
+      call.paren.clone(),
+    )))
+  }
+
+  /**
+    Analyzes the object of a member access and finds its class, the members
+    of a generic class are typed with the type arguments of the object.
+    Static members are accessed through the class itself, `User.count`.
+  */
+  fn member_object(
+    &mut self,
+    object: &Expression,
+    name: &Token,
+  ) -> Result<(IRInstruction, IRClass, bool), AnalyzerDiagnosticError> {
+    let object = self.analyzer(object)?;
+    let is_static = matches!(&object, IRInstruction::Variable(v) if v.metadata.is_class);
+
+    let data_type = self.extract_data_type(&object);
+
+    let class = match &data_type {
+      DataType::ClassType(class_name) => self.class_members(class_name),
+      DataType::GenericType { base, parameters } => match &**base {
+        DataType::ClassType(class_name) => self
+          .class_members(class_name)
+          .map(|class| Self::instantiate_class(class, parameters)),
+        _ => None,
+      },
+      _ => None,
+    };
+
+    match class {
+      Some(class) => Ok((object, class, is_static)),
+      None => Err(AnalyzerDiagnosticError::InvalidMemberAccess(
+        data_type,
+        name.clone(),
+      )),
+    }
+  }
+
+  /**
+    The members of `class` for an instance with the type `arguments`, the
+    `value` of a `Box<int>` is an `int`.
+  */
+  fn instantiate_class(mut class: IRClass, arguments: &[DataType]) -> IRClass {
+    let generics = class.generics.clone();
+
+    for field in &mut class.fields {
+      field.data_type = Self::substitute_type(&generics, arguments, &field.data_type);
+    }
+
+    for method in class.methods.iter_mut().chain(class.constructor.as_mut()) {
+      let function = &mut method.function;
+
+      for parameter in &mut function.parameters {
+        parameter.data_type = Self::substitute_type(&generics, arguments, &parameter.data_type);
+      }
+
+      function.return_type = Self::substitute_type(&generics, arguments, &function.return_type);
+    }
+
+    class
+  }
+
+  /**
+    Returns the name of the enum when `object` refers to one, the variants
+    are accessed through it, `Option.NONE`.
+  */
+  fn enum_name(&self, object: &Expression) -> Option<String> {
+    match object {
+      Expression::Variable(variable) if self.enums.contains_key(&variable.name.span.literal) => {
+        Some(variable.name.span.literal.clone())
+      }
+      _ => None,
+    }
+  }
+
+  /**
+    Creates a variant of an enum. `call` is the call of the variants with a
+    payload, `Option.SOME(1)`.
+  */
+  fn enum_value(&mut self, enum_name: &str, name: &Token, call: Option<&Call>) -> AnalyzerResult {
+    let ir_enum = self.enums[enum_name].clone();
+
+    let variant = match ir_enum.variant(&name.span.literal) {
+      Some(variant) => variant.clone(),
+      None => {
+        return Err(AnalyzerDiagnosticError::UndefinedEnumVariant(
+          enum_name.to_string(),
+          name.clone(),
+        ))
+      }
+    };
+
+    let (arguments, token) = match call {
+      Some(_) if variant.fields.is_empty() => {
+        return Err(AnalyzerDiagnosticError::NotCallable(name.clone()))
+      }
+      Some(call) => (call.arguments.as_slice(), &call.paren),
+      None => (&[] as &[Expression], name),
+    };
+
+    if variant.fields.len() != arguments.len() {
+      return Err(AnalyzerDiagnosticError::InvalidNumberOfArguments(
+        variant.fields.len(),
+        arguments.len(),
+        token.clone(),
+      ));
+    }
+
+    let mut instructions = Vec::<IRInstruction>::new();
+    let mut type_arguments = vec![DataType::None; ir_enum.generics.len()];
+
+    for (field, argument) in variant.fields.iter().zip(arguments) {
+      let instruction = self.analyze_or_recover(argument);
+      let kind = self.extract_data_type(&instruction);
+
+      if !self.infer_type(field, &kind, &ir_enum.generics, &mut type_arguments) {
+        return Err(AnalyzerDiagnosticError::ArgumentTypeMismatch(
+          field.clone(),
+          kind,
+          token.clone(),
+        ));
+      }
+
+      instructions.push(instruction);
+    }
+
+    let data_type = if ir_enum.generics.is_empty() {
+      DataType::ClassType(ir_enum.name.clone())
+    } else {
+      DataType::GenericType {
+        base: Box::new(DataType::ClassType(ir_enum.name.clone())),
+        parameters: type_arguments,
+      }
+    };
+
+    Ok(IRInstruction::EnumValue(IREnumValue::new(
+      ir_enum.name,
+      variant.name,
+      instructions,
+      variant.value,
+      data_type,
+      name.clone(),
+    )))
+  }
+
+  /**
+    Returns the enum of the values of type `data_type`.
+  */
+  fn enum_of(&self, data_type: &DataType) -> Option<&IREnum> {
+    match data_type {
+      DataType::ClassType(name) => self.enums.get(name),
+      DataType::GenericType { base, .. } => self.enum_of(base),
+      _ => None,
+    }
+  }
+
+  /**
+    Type arguments of a value of type `data_type`, `int` in an `Option<int>`.
+  */
+  fn type_arguments(data_type: &DataType) -> &[DataType] {
+    match data_type {
+      DataType::GenericType { parameters, .. } => parameters,
+      _ => &[],
+    }
+  }
+
+  /**
+    Replaces the type parameters `generics` in `data_type` by the type
+    `arguments`, `T` is `int` in an `Option<int>`. The type parameters
+    without an argument are unknown.
+  */
+  fn substitute_type(
+    generics: &[String],
+    arguments: &[DataType],
+    data_type: &DataType,
+  ) -> DataType {
+    match data_type {
+      DataType::ClassType(name) if generics.contains(name) => {
+        let index = generics.iter().position(|g| g == name).unwrap();
+        arguments.get(index).cloned().unwrap_or(DataType::None)
+      }
+      DataType::Array(element) => DataType::Array(Box::new(Self::substitute_type(
+        generics, arguments, element,
+      ))),
+      DataType::Callable(parameters, return_type) => DataType::Callable(
+        Self::substitute_types(generics, arguments, parameters),
+        Box::new(Self::substitute_type(generics, arguments, return_type)),
+      ),
+      DataType::GenericType { base, parameters } => DataType::GenericType {
+        base: base.clone(),
+        parameters: Self::substitute_types(generics, arguments, parameters),
+      },
+      DataType::UnionType(types) => {
+        DataType::UnionType(Self::substitute_types(generics, arguments, types))
+      }
+      DataType::IntersectionType(types) => {
+        DataType::IntersectionType(Self::substitute_types(generics, arguments, types))
+      }
+      DataType::TupleType(types) => {
+        DataType::TupleType(Self::substitute_types(generics, arguments, types))
+      }
+      _ => data_type.clone(),
+    }
+  }
+
+  fn substitute_types(
+    generics: &[String],
+    arguments: &[DataType],
+    types: &[DataType],
+  ) -> Vec<DataType> {
+    types
+      .iter()
+      .map(|data_type| Self::substitute_type(generics, arguments, data_type))
+      .collect()
+  }
+
+  fn generic_names(generics: &[Token]) -> Vec<String> {
+    generics
+      .iter()
+      .map(|generic| generic.span.literal.clone())
+      .collect()
+  }
+
+  /**
+    Analyzes a case of a `switch`, its value must be comparable with the
+    value of the switch. Every case has its own scope.
+  */
+  fn analyze_switch_case(
+    &mut self,
+    case: &SwitchCase,
+    data_type: &DataType,
+  ) -> Result<IRSwitchCase, AnalyzerDiagnosticError> {
+    let value = match &case.value {
+      Some(value) => {
+        let value = self.analyze_or_recover(value);
+        let value_type = self.extract_data_type(&value);
+
+        if !self.check_equal_compatibility(data_type, &value_type).0 {
+          return Err(AnalyzerDiagnosticError::TypeMismatch(
+            data_type.clone(),
+            value_type,
+            case.token.clone(),
+          ));
+        }
+
+        Some(Box::new(value))
+      }
+      None => None,
+    };
+
+    let scopes_variables = self.scopes_variables.clone();
+    self.begin_scope();
+
+    let mut body: Vec<IRInstruction> = Vec::new();
+
+    for statement in &case.body {
+      body.push(self.analyze_statement_or_recover(statement));
+    }
+
+    self.end_scope();
+    self.scopes_variables = scopes_variables;
+
+    Ok(IRSwitchCase::new(value, body, case.token.clone()))
+  }
+
+  /**
+    Analyzes a `match`, `is_value` is false for a `match` statement, whose
+    value is not used and does not need an arm for every value.
+  */
+  fn analyze_match(&mut self, expression: &Match, is_value: bool) -> AnalyzerResult {
+    let value = self.analyze_or_recover(&expression.value);
+    let data_type = self.extract_data_type(&value);
+
+    let mut arms: Vec<IRMatchArm> = Vec::new();
+    let mut arm_types: Vec<DataType> = Vec::new();
+
+    self.context.push(AnalyzerContext::Match);
+
+    for arm in &expression.arms {
+      let scopes_variables = self.scopes_variables.clone();
+      self.begin_scope();
+
+      let result = self.analyze_match_arm(arm, &data_type);
+
+      self.end_scope();
+      self.scopes_variables = scopes_variables;
+
+      let arm = match result {
+        Ok(arm) => arm,
+        Err(error) => {
+          self.context.pop();
+          return Err(error);
+        }
+      };
+
+      if !matches!(*arm.body, IRInstruction::Block(_)) {
+        arm_types.push(self.extract_data_type(&arm.body));
+      }
+
+      arms.push(arm);
+    }
+
+    self.context.pop();
+
+    let is_exhaustive = self.check_match_arms(expression, &arms, &data_type, is_value);
+
+    let data_type = match arm_types.first() {
+      Some(first) if arm_types.iter().all(|t| t == first) => first.clone(),
+      _ => DataType::None,
+    };
+
+    Ok(IRInstruction::Match(IRMatch::new(
+      Box::new(value),
+      arms,
+      data_type,
+      is_exhaustive,
+      expression.keyword.clone(),
+    )))
+  }
+
+  /**
+    Analyzes an arm of a `match` on a value of type `data_type`, the
+    bindings of its pattern are declared in the current scope.
+  */
+  fn analyze_match_arm(
+    &mut self,
+    arm: &MatchArm,
+    data_type: &DataType,
+  ) -> Result<IRMatchArm, AnalyzerDiagnosticError> {
+    let pattern = match &arm.pattern {
+      MatchPattern::Wildcard(_) => IRMatchPattern::Wildcard,
+      MatchPattern::Literal(literal) => {
+        let value = AnalyzerValue::from_literation_value(literal.value.clone());
+        let value_type = value.to_data_type();
+
+        if !self.check_equal_compatibility(data_type, &value_type).0 {
+          return Err(AnalyzerDiagnosticError::TypeMismatch(
+            data_type.clone(),
+            value_type,
+            literal.token.clone(),
+          ));
+        }
+
+        IRMatchPattern::Literal(value)
+      }
+      MatchPattern::Variant {
+        enum_name,
+        variant,
+        bindings,
+      } => {
+        // The value of the match has an error, its enum is taken from the pattern.
+        let enum_type = match data_type {
+          DataType::None => DataType::ClassType(enum_name.span.literal.clone()),
+          _ => data_type.clone(),
+        };
+
+        let ir_enum = match self.enum_of(&enum_type) {
+          Some(ir_enum) if ir_enum.name == enum_name.span.literal => ir_enum.clone(),
+          _ => {
+            return Err(AnalyzerDiagnosticError::TypeMismatch(
+              data_type.clone(),
+              DataType::ClassType(enum_name.span.literal.clone()),
+              enum_name.clone(),
+            ))
+          }
+        };
+
+        let ir_variant = match ir_enum.variant(&variant.span.literal) {
+          Some(ir_variant) => ir_variant.clone(),
+          None => {
+            return Err(AnalyzerDiagnosticError::UndefinedEnumVariant(
+              ir_enum.name,
+              variant.clone(),
+            ))
+          }
+        };
+
+        if ir_variant.fields.len() != bindings.len() {
+          return Err(AnalyzerDiagnosticError::InvalidNumberOfArguments(
+            ir_variant.fields.len(),
+            bindings.len(),
+            variant.clone(),
+          ));
+        }
+
+        let mut names: Vec<Option<String>> = Vec::new();
+
+        for (binding, field) in bindings.iter().zip(&ir_variant.fields) {
+          let name = binding.span.literal.clone();
+
+          if name == "_" {
+            names.push(None);
+            continue;
+          }
+
+          self.define(&name);
+          self.scopes_variables.push(IRVariable::new(
+            name.clone(),
+            Self::substitute_type(&ir_enum.generics, Self::type_arguments(data_type), field),
+            None,
+            IRVariableMetadata::new(false, false, false, false, false, false),
+            Some(binding.clone()),
+          ));
+
+          names.push(Some(name));
+        }
+
+        IRMatchPattern::Variant {
+          enum_name: ir_enum.name,
+          variant: ir_variant.name,
+          value: ir_variant.value,
+          bindings: names,
+        }
+      }
+    };
+
+    let guard = match &arm.guard {
+      Some(guard) => {
+        let guard = self.analyze_or_recover(guard);
+        let guard_type = self.extract_data_type(&guard);
+
+        if guard_type != DataType::Boolean && guard_type != DataType::None {
+          return Err(AnalyzerDiagnosticError::TypeMismatch(
+            DataType::Boolean,
+            guard_type,
+            arm.pattern.token().clone(),
+          ));
+        }
+
+        Some(Box::new(guard))
+      }
+      None => None,
+    };
+
+    let body = self.analyze_statement_or_recover(&arm.body);
+
+    Ok(IRMatchArm::new(pattern, guard, Box::new(body)))
+  }
+
+  /**
+    Reports the arms that can not be reached because the arms before them
+    match every value they match, the matches on an enum that do not handle
+    every variant and the matches used as a value that have no `_` arm.
+    Arms with a guard do not cover their pattern. Returns whether every
+    value is matched by an arm.
+  */
+  fn check_match_arms(
+    &mut self,
+    expression: &Match,
+    arms: &[IRMatchArm],
+    data_type: &DataType,
+    is_value: bool,
+  ) -> bool {
+    let ir_enum = self.enum_of(data_type).cloned();
+
+    let mut covers_all = false;
+    let mut variants: HashSet<String> = HashSet::new();
+    let mut literals: HashSet<String> = HashSet::new();
+
+    for (arm, ir_arm) in expression.arms.iter().zip(arms) {
+      let is_covered = covers_all
+        || match &ir_arm.pattern {
+          IRMatchPattern::Wildcard => false,
+          IRMatchPattern::Literal(value) => literals.contains(&format!("{:?}", value)),
+          IRMatchPattern::Variant { variant, .. } => variants.contains(variant),
+        };
+
+      if is_covered {
+        self
+          .diagnostics
+          .push(AnalyzerDiagnosticError::UnreachableMatchArm(
+            arm.pattern.token().clone(),
+          ));
+      }
+
+      if ir_arm.guard.is_some() {
+        continue;
+      }
+
+      match &ir_arm.pattern {
+        IRMatchPattern::Wildcard => covers_all = true,
+        IRMatchPattern::Literal(value) => {
+          literals.insert(format!("{:?}", value));
+        }
+        IRMatchPattern::Variant { variant, .. } => {
+          variants.insert(variant.clone());
+        }
+      }
+
+      if let Some(ir_enum) = &ir_enum {
+        covers_all = covers_all || ir_enum.variants.iter().all(|v| variants.contains(&v.name));
+      }
+
+      if *data_type == DataType::Boolean {
+        covers_all = covers_all || literals.len() == 2;
+      }
+    }
+
+    match ir_enum {
+      _ if covers_all => (),
+      Some(ir_enum) => {
+        let missing = ir_enum
+          .variants
+          .iter()
+          .filter(|v| !variants.contains(&v.name))
+          .map(|v| v.name.clone())
+          .collect();
+
+        self
+          .diagnostics
+          .push(AnalyzerDiagnosticError::NonExhaustiveMatch(
+            ir_enum.name,
+            missing,
+            expression.keyword.clone(),
+          ));
+      }
+      None if is_value && *data_type != DataType::None => {
+        self
+          .diagnostics
+          .push(AnalyzerDiagnosticError::NonExhaustiveMatch(
+            data_type.to_string(),
+            vec!["_".to_string()],
+            expression.keyword.clone(),
+          ));
+      }
+      None => (),
+    }
+
+    covers_all
+  }
+
+  /**
+    Checks that `found` can be used where `expected` is written in terms of
+    the type parameters `generics`. The first use of a type parameter binds
+    it in `arguments`, the following ones must agree with it.
+  */
+  fn infer_type(
+    &self,
+    expected: &DataType,
+    found: &DataType,
+    generics: &[String],
+    arguments: &mut Vec<DataType>,
+  ) -> bool {
+    match (expected, found) {
+      (DataType::ClassType(name), _) if generics.contains(name) => {
+        let index = generics.iter().position(|generic| generic == name).unwrap();
+
+        if arguments[index] == DataType::None && *found != DataType::Null {
+          arguments[index] = found.clone();
+          return true;
+        }
+
+        self.is_assignable(&arguments[index], found)
+      }
+      (DataType::Array(expected), DataType::Array(found)) => {
+        self.infer_type(expected, found, generics, arguments)
+      }
+      (
+        DataType::Callable(expected_parameters, expected_return),
+        DataType::Callable(found_parameters, found_return),
+      ) if expected_parameters.len() == found_parameters.len() => {
+        expected_parameters
+          .iter()
+          .zip(found_parameters)
+          .all(|(expected, found)| self.infer_type(expected, found, generics, arguments))
+          && self.infer_type(expected_return, found_return, generics, arguments)
+      }
+      (
+        DataType::GenericType {
+          base: expected_base,
+          parameters: expected_parameters,
+        },
+        DataType::GenericType {
+          base: found_base,
+          parameters: found_parameters,
+        },
+      ) if expected_base == found_base && expected_parameters.len() == found_parameters.len() => {
+        expected_parameters
+          .iter()
+          .zip(found_parameters)
+          .all(|(expected, found)| self.infer_type(expected, found, generics, arguments))
+      }
+      _ => self.is_assignable(expected, found),
+    }
+  }
+
+  fn check_member_access(
+    &self,
+    class: &IRClass,
+    metadata: &IRClassMemberMetadata,
+    name: &Token,
+  ) -> Result<(), AnalyzerDiagnosticError> {
+    if metadata.is_public || self.current_class.as_ref() == Some(&class.name) {
+      return Ok(());
+    }
+
+    Err(AnalyzerDiagnosticError::PrivateMemberAccess(
+      class.name.clone(),
+      name.clone(),
+    ))
+  }
+
+  /**
+    Contexts inside the innermost function, a `break` can not leave a
+    function or a lambda.
+  */
+  fn enclosing_contexts(&self) -> impl Iterator<Item = &AnalyzerContext> {
+    self.context.iter().rev().take_while(|context| {
+      !matches!(
+        context,
+        AnalyzerContext::Function
+          | AnalyzerContext::Method
+          | AnalyzerContext::Initializer
+          | AnalyzerContext::Lambda
+      )
+    })
+  }
+
+  /**
+    Records `name` as captured by the lambdas between the use of the name and
+    the function that declares it. `this` belongs to the innermost method.
+  */
+  fn capture(&mut self, name: &String) {
+    let owner = self
+      .function_scopes
+      .iter()
+      .rposition(|scope| scope.locals.contains(name) || (name == "this" && !scope.is_lambda));
+
+    if let Some(owner) = owner {
+      for scope in &mut self.function_scopes[owner + 1..] {
+        if !scope.captures.contains(name) {
+          scope.captures.push(name.clone());
+        }
+      }
+    }
+  }
+
+  /**
+    Context of the innermost function, method or constructor being analyzed.
+  */
+  fn function_context(&self) -> Option<&AnalyzerContext> {
+    self.context.iter().rev().find(|context| {
+      matches!(
+        context,
+        AnalyzerContext::Function | AnalyzerContext::Method | AnalyzerContext::Initializer
+      )
+    })
+  }
+
+  fn class_signature(&self, statement: &Class) -> IRClass {
+    let name = statement.name.span.literal.clone();
+
+    let fields = statement
+      .fields
+      .iter()
+      .map(|field| {
+        IRClassField::new(
+          field.name.span.literal.clone(),
+          self.resolve_type(&field.data_type),
+          None,
+          Self::member_metadata(&field.modifiers),
+        )
+      })
+      .collect();
+
+    let constructor = statement.constructor.as_ref().map(|constructor| {
+      IRMethod::new(
+        self.method_signature(&constructor.function),
+        Self::member_metadata(&constructor.modifiers),
+      )
+    });
+
+    let methods = statement
+      .methods
+      .iter()
+      .map(|method| {
+        IRMethod::new(
+          self.method_signature(&method.function),
+          Self::member_metadata(&method.modifiers),
+        )
+      })
+      .collect();
+
+    IRClass::new(
+      name,
+      Self::generic_names(&statement.generics),
+      fields,
+      constructor,
+      methods,
+    )
+  }
+
+  /**
+    Members of the class or the interface named `name`.
+  */
+  fn class_members(&self, name: &str) -> Option<IRClass> {
+    match self.classes.get(name) {
+      Some(class) => Some(class.clone()),
+      None => self
+        .interfaces
+        .get(name)
+        .map(|interface| interface.as_class()),
+    }
+  }
+
+  fn is_type_name(&self, name: &str) -> bool {
+    self.classes.contains_key(name)
+      || self.enums.contains_key(name)
+      || self.interfaces.contains_key(name)
+      || self.type_aliases.contains_key(name)
+  }
+
+  /**
+    Reports the members of the interfaces implemented by `statement` that the
+    class does not declare or declares with another type.
+  */
+  fn check_implements(&mut self, statement: &Class, class: &IRClass) {
+    for name in &statement.implements {
+      let interface = match self.interfaces.get(&name.span.literal) {
+        Some(interface) => interface,
+        None => {
+          self
+            .diagnostics
+            .push(AnalyzerDiagnosticError::UndefinedInterface(name.clone()));
+          continue;
+        }
+      };
+
+      let errors: Vec<AnalyzerDiagnosticError> = self
+        .interface_mismatches(class, interface)
+        .into_iter()
+        .map(|(member, expected, found)| match found {
+          Some(found) => AnalyzerDiagnosticError::InterfaceMemberMismatch(
+            interface.name.clone(),
+            member.clone(),
+            expected,
+            found,
+            Self::member_token(statement, &member),
+          ),
+          None => AnalyzerDiagnosticError::MissingInterfaceMember(
+            class.name.clone(),
+            interface.name.clone(),
+            member,
+            name.clone(),
+          ),
+        })
+        .collect();
+
+      self.diagnostics.extend(errors);
+    }
+  }
+
+  /**
+    Members of `interface` that `class` does not declare as public instance
+    members, with the type declared by the class when it is not the type of
+    the interface. A class conforms to an interface when there are none.
+  */
+  fn interface_mismatches(
+    &self,
+    class: &IRClass,
+    interface: &IRInterface,
+  ) -> Vec<(String, DataType, Option<DataType>)> {
+    let mut mismatches = Vec::new();
+
+    for field in &interface.fields {
+      let found = class
+        .field(&field.name)
+        .filter(|f| f.metadata.is_public && !f.metadata.is_static)
+        .map(|f| f.data_type.clone());
+
+      if found.as_ref() != Some(&field.data_type) {
+        mismatches.push((field.name.clone(), field.data_type.clone(), found));
+      }
+    }
+
+    for method in &interface.methods {
+      let expected = Self::function_type(&method.function);
+      let found = class
+        .method(&method.function.name)
+        .filter(|m| m.metadata.is_public && !m.metadata.is_static)
+        .map(|m| Self::function_type(&m.function));
+
+      if found.as_ref() != Some(&expected) {
+        mismatches.push((method.function.name.clone(), expected, found));
+      }
+    }
+
+    mismatches
+  }
+
+  fn member_token(statement: &Class, member: &str) -> Token {
+    let field = statement
+      .fields
+      .iter()
+      .find(|field| field.name.span.literal == member)
+      .map(|field| &field.name);
+
+    let method = statement
+      .methods
+      .iter()
+      .find(|method| method.function.name.span.literal == member)
+      .map(|method| &method.function.name);
+
+    field.or(method).unwrap_or(&statement.name).clone()
+  }
+
+  fn function_type(function: &IRFunction) -> DataType {
+    DataType::Callable(
+      function
+        .parameters
+        .iter()
+        .map(|p| p.data_type.clone())
+        .collect(),
+      Box::new(function.return_type.clone()),
+    )
+  }
+
+  fn method_signature(&self, function: &FunctionStatement) -> IRFunction {
+    let parameters = function
+      .parameters
+      .iter()
+      .map(|param| self.parameter_signature(param))
+      .collect();
+
+    IRFunction::new(
+      function.name.span.literal.clone(),
+      Self::generic_names(&function.generics),
+      parameters,
+      self.resolve_type(&function.return_type.clone().unwrap_or(DataType::Void)),
+      None,
+      IRFunctionMetadata::new(false, false, false, None),
+      function.name.clone(),
+    )
+  }
+
+  /**
+    Type of a variable declared without annotation, the type of its value.
+    The values whose type is not known, `null` or `[]`, need an annotation.
+  */
+  fn infer_variable_type(
+    &self,
+    value: &IRInstruction,
+    name: &Token,
+  ) -> Result<DataType, AnalyzerDiagnosticError> {
+    let data_type = self.extract_data_type(value);
+
+    if data_type == DataType::Null || Self::has_unknown_type(&data_type) {
+      return Err(AnalyzerDiagnosticError::CannotInferType(name.clone()));
+    }
+
+    Ok(data_type)
+  }
+
+  fn has_unknown_type(data_type: &DataType) -> bool {
+    match data_type {
+      DataType::None => true,
+      DataType::Array(element) => Self::has_unknown_type(element),
+      DataType::Callable(parameters, return_type) => {
+        parameters.iter().any(Self::has_unknown_type) || Self::has_unknown_type(return_type)
+      }
+      DataType::GenericType { parameters, .. } => parameters.iter().any(Self::has_unknown_type),
+      DataType::UnionType(types)
+      | DataType::IntersectionType(types)
+      | DataType::TupleType(types) => types.iter().any(Self::has_unknown_type),
+      _ => false,
+    }
+  }
+
+  fn parameter_signature(&self, param: &FunctionParameter) -> IRVariable {
+    IRVariable::new(
+      param.name.span.literal.clone(),
+      self.resolve_type(&param.data_type),
+      None,
+      IRVariableMetadata::new(
+        param.is_mutable,
+        param.is_reference,
+        true,
+        false,
+        false,
+        false,
+      ),
+      Some(param.name.clone()),
+    )
+  }
+
+  /**
+    Replaces the type aliases used by `data_type` with the types they name.
+    The aliases are resolved when they are declared, so one lookup is enough.
+  */
+  fn resolve_type(&self, data_type: &DataType) -> DataType {
+    match data_type {
+      DataType::ClassType(name) => match self.type_aliases.get(name) {
+        Some(alias) => alias.clone(),
+        None => data_type.clone(),
+      },
+      DataType::Array(element) => DataType::Array(Box::new(self.resolve_type(element))),
+      DataType::Callable(parameters, return_type) => DataType::Callable(
+        self.resolve_types(parameters),
+        Box::new(self.resolve_type(return_type)),
+      ),
+      DataType::GenericType { base, parameters } => DataType::GenericType {
+        base: Box::new(self.resolve_type(base)),
+        parameters: self.resolve_types(parameters),
+      },
+      DataType::UnionType(types) => {
+        let mut members: Vec<DataType> = Vec::new();
+
+        for member in self.resolve_types(types) {
+          let member_types = match member {
+            DataType::UnionType(member_types) => member_types,
+            member => vec![member],
+          };
+
+          for member in member_types {
+            if !members.contains(&member) {
+              members.push(member);
+            }
+          }
+        }
 
-    current_block.insert(name.clone(), true);
+        DataType::UnionType(members)
+      }
+      DataType::IntersectionType(types) => DataType::IntersectionType(self.resolve_types(types)),
+      DataType::TupleType(types) => DataType::TupleType(self.resolve_types(types)),
+      _ => data_type.clone(),
+    }
+  }
+
+  fn resolve_types(&self, types: &[DataType]) -> Vec<DataType> {
+    types.iter().map(|t| self.resolve_type(t)).collect()
+  }
+
+  fn member_metadata(modifiers: &ClassMemberModifiers) -> IRClassMemberMetadata {
+    IRClassMemberMetadata::new(
+      modifiers.is_public,
+      modifiers.is_static,
+      modifiers.is_readonly,
+    )
+  }
+
+  /**
+    Analyzes the initializers and the method bodies of `statement`.
+    The instance fields are initialized at the start of the constructor, one
+    is generated when the class does not declare it.
+  */
+  fn analyze_class_body(
+    &mut self,
+    statement: &Class,
+    class: &mut IRClass,
+  ) -> Result<(), AnalyzerDiagnosticError> {
+    let mut initializers = Vec::<IRInstruction>::new();
+
+    for (field, ir_field) in statement.fields.iter().zip(class.fields.iter_mut()) {
+      let value = match &field.initializer {
+        Some(initializer) => {
+          self.analyze_assigned_value(initializer, &ir_field.data_type, &field.name)?
+        }
+        None => IRInstruction::Literal(IRLiteral::new(AnalyzerValue::Null, field.name.clone())),
+      };
+
+      if field.modifiers.is_static {
+        ir_field.value = Some(Box::new(value));
+        continue;
+      }
+
+      initializers.push(IRInstruction::Set(IRSet::new(
+        Box::new(IRInstruction::This(IRThis::new(
+          class.name.clone(),
+          field.name.clone(),
+        ))),
+        ir_field.name.clone(),
+        Box::new(value),
+        ir_field.data_type.clone(),
+        ir_field.metadata.clone(),
+        field.name.clone(),
+      )));
+    }
+
+    match (&statement.constructor, class.constructor.as_mut()) {
+      (Some(constructor), Some(ir_constructor)) => {
+        let mut function = self.analyze_method(
+          &constructor.function,
+          ir_constructor.function.clone(),
+          AnalyzerContext::Initializer,
+        )?;
+
+        if let Some(body) = function.body.as_mut() {
+          initializers.append(&mut body.instructions);
+          body.instructions = initializers;
+        }
+
+        ir_constructor.function = function;
+      }
+      _ if !initializers.is_empty() => {
+        let function = IRFunction::new(
+          class.name.clone(),
+          Vec::new(),
+          Vec::new(),
+          DataType::Void,
+          Some(Box::new(IRBlock::new(initializers, Vec::new()))),
+          IRFunctionMetadata::new(false, false, false, None),
+          statement.name.clone(),
+        );
+
+        class.constructor = Some(IRMethod::new(
+          function,
+          IRClassMemberMetadata::new(true, false, false),
+        ));
+      }
+      _ => (),
+    }
+
+    for (method, ir_method) in statement.methods.iter().zip(class.methods.iter_mut()) {
+      // Static methods have no instance, they are analyzed like functions.
+      let context = if method.modifiers.is_static {
+        AnalyzerContext::Function
+      } else {
+        AnalyzerContext::Method
+      };
+
+      ir_method.function =
+        self.analyze_method(&method.function, ir_method.function.clone(), context)?;
+    }
+
+    Ok(())
+  }
+
+  fn analyze_method(
+    &mut self,
+    statement: &FunctionStatement,
+    mut function: IRFunction,
+    context: AnalyzerContext,
+  ) -> Result<IRFunction, AnalyzerDiagnosticError> {
+    let scopes_variables = self.scopes_variables.clone();
+    let enclosing_function = self.current_function.take();
+
+    // Constructors return the instance, their `return` has no value.
+    let return_type = match context {
+      AnalyzerContext::Initializer => None,
+      _ => Some(function.return_type.clone()),
+    };
+
+    self.begin_scope();
+    self.context.push(context);
+    self
+      .function_scopes
+      .push(FunctionScope::new(false, return_type));
+
+    for parameter in &function.parameters {
+      self.define_parameter(&parameter.name);
+      self.scopes_variables.push(parameter.clone());
+    }
+
+    let body = self.analyze_body(&statement.body, &statement.name);
+
+    self.function_scopes.pop();
+    self.context.pop();
+    self.end_scope();
+
+    self.scopes_variables = scopes_variables;
+    self.current_function = enclosing_function;
+
+    function.body = Some(Box::new(body?));
+
+    Ok(function)
+  }
+
+  fn analyze_index(&mut self, index: &Expression, bracket: &Token) -> AnalyzerResult {
+    let index = self.analyze_or_recover(index);
+
+    match self.extract_data_type(&index) {
+      DataType::Int => Ok(index),
+      _ if Self::has_error(&[&index]) => Ok(index),
+      data_type => Err(AnalyzerDiagnosticError::InvalidIndexType(
+        data_type,
+        bracket.clone(),
+      )),
+    }
+  }
+
+  fn is_assignable(&self, expected: &DataType, found: &DataType) -> bool {
+    match (expected, found) {
+      (DataType::None, _) | (_, DataType::None) => true,
+      (DataType::Array(expected), DataType::Array(found)) => self.is_assignable(expected, found),
+      (DataType::UnionType(_), DataType::UnionType(found)) => found
+        .iter()
+        .all(|found| self.is_assignable(expected, found)),
+      (DataType::UnionType(types), _) => types.iter().any(|t| self.is_assignable(t, found)),
+      (
+        DataType::GenericType {
+          base: expected_base,
+          parameters: expected_parameters,
+        },
+        DataType::GenericType {
+          base: found_base,
+          parameters: found_parameters,
+        },
+      ) => {
+        expected_base == found_base
+          && expected_parameters.len() == found_parameters.len()
+          && expected_parameters
+            .iter()
+            .zip(found_parameters)
+            .all(|(expected, found)| self.is_assignable(expected, found))
+      }
+      (DataType::ClassType(name), DataType::ClassType(found_name)) if name != found_name => {
+        match (self.interfaces.get(name), self.class_members(found_name)) {
+          (Some(interface), Some(class)) => self.interface_mismatches(&class, interface).is_empty(),
+          _ => false,
+        }
+      }
+      (DataType::ClassType(_), DataType::GenericType { base, .. }) => **base == *expected,
+      _ => expected == found,
+    }
   }
 
   fn _find_function_in_ir(&self, name: String) -> Option<IRFunction> {
@@ -993,62 +3251,85 @@ impl Analyzer {
     }
   }
 
-  fn are_types_logical_compatibel(
-    &self,
-    left: &IRInstruction,
-    right: &IRInstruction,
-    operator: &IRInstructionType,
-  ) -> bool {
-    match operator {
-      IRInstructionType::And | IRInstructionType::Or => match (left, right) {
-        (
-          IRInstruction::Literal(IRLiteral {
-            value: AnalyzerValue::Boolean(_),
-          }),
-          IRInstruction::Literal(IRLiteral {
-            value: AnalyzerValue::Boolean(_),
-          }),
-        ) => true,
-        _ => false,
-      },
-      _ => false,
+  /**
+    Analyzes a value stored where a value of type `expected` is written, a
+    variable, a field or an element of an array.
+  */
+  fn analyze_assigned_value(
+    &mut self,
+    expression: &Expression,
+    expected: &DataType,
+    token: &Token,
+  ) -> AnalyzerResult {
+    let value = Self::promote(self.analyze_or_recover(expression), expected);
+    let value_type = self.extract_data_type(&value);
+
+    if !Self::has_error(&[&value]) && !self.is_assignable(expected, &value_type) {
+      return Err(AnalyzerDiagnosticError::AssingInvalidType(
+        value_type,
+        expected.clone(),
+        token.clone(),
+      ));
+    }
+
+    Ok(value)
+  }
+
+  /**
+    An `int` literal written where a `float` is expected is a `float`,
+    `let ratio: float = 1;`. Other `int` values are not converted.
+  */
+  fn promote(value: IRInstruction, expected: &DataType) -> IRInstruction {
+    if *expected != DataType::Float {
+      return value;
+    }
+
+    match value {
+      IRInstruction::Literal(IRLiteral {
+        value: AnalyzerValue::Int(int),
+        token,
+      }) => IRInstruction::Literal(IRLiteral::new(AnalyzerValue::Float(int as f64), token)),
+      IRInstruction::Unary(unary) if matches!(*unary.right, IRInstruction::Literal(_)) => {
+        let right = Self::promote(*unary.right, expected);
+
+        IRInstruction::Unary(IRUnary::new(
+          unary.instruction_type,
+          Box::new(right),
+          DataType::Float,
+          unary.token,
+        ))
+      }
+      value => value,
+    }
+  }
+
+  /**
+    The type of a value that is either of type `left` or `right`, the
+    branches of a ternary. An `int` and a `float` are a `float`.
+  */
+  fn common_type(&self, left: &DataType, right: &DataType) -> Option<DataType> {
+    match (left, right) {
+      (DataType::Int, DataType::Float) | (DataType::Float, DataType::Int) => Some(DataType::Float),
+      (DataType::Null, _) => Some(right.clone()),
+      _ if self.is_assignable(left, right) => Some(left.clone()),
+      _ if self.is_assignable(right, left) => Some(right.clone()),
+      _ => None,
     }
   }
 
-  fn are_types_unary_compatible(
+  fn check_unary_compatibility(
     &self,
-    right: &IRInstruction,
+    right: &DataType,
     operator: &IRInstructionType,
-  ) -> bool {
-    match operator {
-      IRInstructionType::Sub => match right {
-        IRInstruction::Literal(IRLiteral {
-          value: AnalyzerValue::Int(_),
-        })
-        | IRInstruction::Literal(IRLiteral {
-          value: AnalyzerValue::Float(_),
-        }) => true,
-        _ => false,
-      },
-      IRInstructionType::Not => match right {
-        IRInstruction::Literal(IRLiteral {
-          value: AnalyzerValue::Boolean(_),
-        })
-        | IRInstruction::Literal(IRLiteral {
-          value: AnalyzerValue::Int(_),
-        })
-        | IRInstruction::Literal(IRLiteral {
-          value: AnalyzerValue::String(_),
-        })
-        | IRInstruction::Literal(IRLiteral {
-          value: AnalyzerValue::Float(_),
-        })
-        | IRInstruction::Literal(IRLiteral {
-          value: AnalyzerValue::Null,
-        }) => true,
-        _ => false,
-      },
-      _ => false,
+  ) -> CheckCompatibility<DataType> {
+    match (operator, right) {
+      (IRInstructionType::Sub, DataType::Int | DataType::Float | DataType::None) => {
+        (true, right.clone())
+      }
+      // Both backends negate the truthiness of any value, only `null` and
+      // `false` are falsy.
+      (IRInstructionType::Not, _) => (true, DataType::Boolean),
+      _ => (false, DataType::None),
     }
   }
 
@@ -1056,14 +3337,27 @@ impl Analyzer {
     match instruction {
       IRInstruction::Literal(l) => l.value.to_data_type(),
       IRInstruction::Variable(v) => v.data_type.clone(),
-      IRInstruction::Function(f) => f.return_type.clone(),
+      IRInstruction::Function(f) => Self::function_type(f),
+      IRInstruction::Lambda(lambda) => lambda.data_type(),
+      IRInstruction::CallValue(call) => call.return_type.clone(),
+      IRInstruction::TemplateLiteral(_) => DataType::String,
+      IRInstruction::Index(index) => index.data_type.clone(),
+      IRInstruction::SetIndex(set) => set.data_type.clone(),
       IRInstruction::Binary(b) => b.data_type.clone(),
       IRInstruction::Unary(u) => u.data_type.clone(),
       IRInstruction::Logical(_) => DataType::Boolean,
+      IRInstruction::Ternary(ternary) => ternary.data_type.clone(),
       IRInstruction::Assign(a) => self.extract_data_type(&*a.value.clone()),
       IRInstruction::Call(c) => c.return_type.clone(),
       IRInstruction::Return(r) => r.data_type.clone(),
       IRInstruction::Array(array) => array.data_type.clone(),
+      IRInstruction::This(this) => DataType::ClassType(this.class_name.clone()),
+      IRInstruction::Get(get) => get.data_type.clone(),
+      IRInstruction::Set(set) => set.data_type.clone(),
+      IRInstruction::New(new) => new.data_type.clone(),
+      IRInstruction::MethodCall(call) => call.return_type.clone(),
+      IRInstruction::EnumValue(enum_value) => enum_value.data_type.clone(),
+      IRInstruction::Match(ir_match) => ir_match.data_type.clone(),
       _ => DataType::None,
     }
   }
@@ -1074,15 +3368,15 @@ impl Analyzer {
     right: &DataType,
   ) -> CheckCompatibility<DataType> {
     match (left, right) {
-      (DataType::Int, DataType::Int) => (true, DataType::Int),
-      (DataType::Float, DataType::Float) => (true, DataType::Float),
       (DataType::String, DataType::String) => (true, DataType::String),
-      (_, DataType::Null) => (true, left.clone()),
-      (DataType::Null, _) => (true, right.clone()),
-      _ => (false, DataType::None),
+      _ => self.check_arithmetic_compatibility(left, right),
     }
   }
 
+  /**
+    Operands of `-`, `*`, `/` and `%`. An `int` operand is promoted to
+    `float` when the other operand is a `float`.
+  */
   fn check_arithmetic_compatibility(
     &self,
     left: &DataType,
@@ -1093,8 +3387,8 @@ impl Analyzer {
       (DataType::Float, DataType::Float) => (true, DataType::Float),
       (DataType::Int, DataType::Float) => (true, DataType::Float),
       (DataType::Float, DataType::Int) => (true, DataType::Float),
-      (_, DataType::Null) => (true, left.clone()),
-      (DataType::Null, _) => (true, right.clone()),
+      (DataType::None, DataType::Int | DataType::Float) => (true, right.clone()),
+      (DataType::Int | DataType::Float | DataType::None, DataType::None) => (true, left.clone()),
       _ => (false, DataType::None),
     }
   }
@@ -1105,28 +3399,30 @@ impl Analyzer {
     right: &DataType,
   ) -> CheckCompatibility<DataType> {
     match (left, right) {
-      (DataType::Int, DataType::Int) => (true, DataType::Boolean),
-      (DataType::Float, DataType::Float) => (true, DataType::Boolean),
-      (DataType::Int, DataType::Float) => (true, DataType::Boolean),
-      (DataType::Float, DataType::Int) => (true, DataType::Boolean),
-      (_, DataType::Null) => (true, left.clone()),
-      (DataType::Null, _) => (true, right.clone()),
-      _ => (false, DataType::None),
+      (DataType::String, DataType::String) => (true, DataType::Boolean),
+      _ => match self.check_arithmetic_compatibility(left, right) {
+        (true, _) => (true, DataType::Boolean),
+        _ => (false, DataType::None),
+      },
     }
   }
 
+  /**
+    Values can be compared for equality when one of them can be assigned to
+    the other, `null` can be compared with every value.
+  */
   fn check_equal_compatibility(
     &self,
     left: &DataType,
     right: &DataType,
   ) -> CheckCompatibility<DataType> {
     match (left, right) {
-      (DataType::Int, DataType::Int) => (true, DataType::Boolean),
-      (DataType::Float, DataType::Float) => (true, DataType::Boolean),
-      (DataType::String, DataType::String) => (true, DataType::Boolean),
-      (DataType::Boolean, DataType::Boolean) => (true, DataType::Boolean),
-      (_, DataType::Null) => (true, left.clone()),
-      (DataType::Null, _) => (true, right.clone()),
+      (DataType::Int, DataType::Float) | (DataType::Float, DataType::Int) => {
+        (true, DataType::Boolean)
+      }
+      _ if self.is_assignable(left, right) || self.is_assignable(right, left) => {
+        (true, DataType::Boolean)
+      }
       _ => (false, DataType::None),
     }
   }
@@ -1137,7 +3433,9 @@ impl Analyzer {
     right: &DataType,
   ) -> CheckCompatibility<DataType> {
     match (left, right) {
-      (DataType::Boolean, DataType::Boolean) => (true, DataType::Boolean),
+      (DataType::Boolean | DataType::None, DataType::Boolean | DataType::None) => {
+        (true, DataType::Boolean)
+      }
       _ => (false, DataType::None),
     }
   }
@@ -1173,24 +3471,394 @@ impl Analyzer {
       IRInstructionType::And | IRInstructionType::Or => {
         self.check_logical_compatibility(&left_type, &right_type)
       }
-      IRInstructionType::Mod => {
-        if left_type == DataType::Int && right_type == DataType::Int {
-          (true, DataType::Int)
-        } else {
-          (false, DataType::None)
-        }
-      }
+      IRInstructionType::Mod => self.check_arithmetic_compatibility(&left_type, &right_type),
       _ => (false, DataType::None),
     }
   }
 
-  fn is_iterable(&self, iterable: &IRInstruction) -> bool {
+  /**
+    The type of the values a `for in` loop takes from an iterable of type
+    `iterable`, the characters of a string are strings. `None` when the
+    type can not be iterated.
+  */
+  fn element_type(iterable: &DataType) -> Option<DataType> {
     match iterable {
-      IRInstruction::Variable(var) => match var.data_type {
-        DataType::Array(_) => true,
-        _ => false,
-      },
-      _ => false,
+      DataType::Array(element) => Some(*element.clone()),
+      DataType::String => Some(DataType::String),
+      _ => None,
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn analyze(source: &str) -> Analyzer {
+    let mut lexer = Lexer::new(source, "main.ign".to_string());
+    lexer.scan_tokens();
+
+    let mut parser = Parser::new(lexer.tokens);
+    let statements = parser.parse();
+    assert!(
+      parser.diagnostics.is_empty(),
+      "Failed to parse {:?}",
+      source
+    );
+
+    let mut analyzer = Analyzer::new("main.ign".to_string());
+    analyzer.analyze(&statements);
+
+    analyzer
+  }
+
+  fn errors(source: &str) -> Vec<AnalyzerDiagnosticError> {
+    analyze(source).diagnostics
+  }
+
+  fn assert_compiles(source: &str) {
+    let errors = errors(source);
+
+    assert!(errors.is_empty(), "Unexpected errors {:?}", errors);
+  }
+
+  const COUNTER: &str = "class Counter {\n  public static created: int = 0;\n  private count: int = 0;\n\n  public Counter(start: int) {\n    this.count = start;\n    Counter.created = Counter.created + 1;\n  }\n\n  public increment(): int {\n    this.count = this.count + 1;\n    return this.count;\n  }\n}\nlet counter: Counter = new Counter(40);\n";
+
+  #[test]
+  fn test_classes() {
+    assert_compiles(&format!(
+      "{}counter.increment();\nCounter.created;",
+      COUNTER
+    ));
+
+    assert!(matches!(
+      &errors(&format!("{}counter.count;", COUNTER))[..],
+      [AnalyzerDiagnosticError::PrivateMemberAccess(_, _)]
+    ));
+    assert!(matches!(
+      &errors("this;")[..],
+      [AnalyzerDiagnosticError::ThisOutsideMethod(_)]
+    ));
+  }
+
+  const OPTION: &str = "enum Option<T> {\n  SOME(T),\n  NONE\n}\n\nenum Colors {\n  RED = \"red\",\n  GREEN = \"green\",\n}\n";
+
+  #[test]
+  fn test_enums() {
+    assert_compiles(&format!(
+      "{}let value: Option<int> = Option.SOME(42);\nvalue == Option.NONE;\nColors.GREEN;",
+      OPTION
+    ));
+
+    assert!(matches!(
+      &errors(&format!("{}Option.SOME(1, 2);", OPTION))[..],
+      [AnalyzerDiagnosticError::InvalidNumberOfArguments(1, 2, _)]
+    ));
+    assert!(matches!(
+      &errors(&format!("{}Colors.BLUE;", OPTION))[..],
+      [AnalyzerDiagnosticError::UndefinedEnumVariant(_, _)]
+    ));
+  }
+
+  const SHAPE: &str = "enum Shape {\n  CIRCLE(int),\n  RECT(int, int),\n  EMPTY\n}\n";
+
+  #[test]
+  fn test_match() {
+    assert_compiles(&format!(
+      "{}function area(shape: Shape): int {{\n  return match shape {{\n    Shape.CIRCLE(r) if r > 10 -> 1000,\n    Shape.CIRCLE(r) -> r * r,\n    Shape.RECT(w, h) -> w * h,\n    Shape.EMPTY -> 0,\n  }};\n}}",
+      SHAPE
+    ));
+
+    assert!(matches!(
+      &errors(&format!(
+        "{}match Shape.EMPTY {{ Shape.EMPTY -> 0 }};",
+        SHAPE
+      ))[..],
+      [AnalyzerDiagnosticError::NonExhaustiveMatch(_, _, _)]
+    ));
+    assert!(matches!(
+      &errors("match 1 { _ -> 0, 1 -> 1 };")[..],
+      [AnalyzerDiagnosticError::UnreachableMatchArm(_)]
+    ));
+
+    assert_compiles("match 1 { 1 -> 10 };\nlet b: int = match true { true -> 1, false -> 0 };");
+    assert!(matches!(
+      &errors("let r: int = match 1 { 1 -> 10 };")[..],
+      [AnalyzerDiagnosticError::NonExhaustiveMatch(name, missing, _)]
+        if name == "Int" && missing == &["_"]
+    ));
+
+    assert_compiles(&format!(
+      "{}function size(shape: Shape): int {{\n  match shape {{\n    Shape.CIRCLE(r) -> {{ return r; }},\n    Shape.RECT(w, h) -> {{ return w; }},\n    Shape.EMPTY -> {{ return 0; }},\n  }}\n}}",
+      SHAPE
+    ));
+    assert_compiles(
+      "function int_of(b: boolean): int {\n  match b {\n    true -> { return 1; },\n    false -> { return 0; },\n  }\n}",
+    );
+    assert!(matches!(
+      &errors("function one(n: int): int {\n  match n {\n    1 -> { return 1; },\n  }\n}")[..],
+      [AnalyzerDiagnosticError::MissingReturn(_)]
+    ));
+  }
+
+  #[test]
+  fn test_switch() {
+    assert_compiles(
+      "let mut result: string = \"\";\nswitch 2 {\n  case 1:\n  case 2:\n    result = \"small\";\n    break;\n  default:\n    result = \"!\";\n}",
+    );
+
+    assert!(matches!(
+      &errors("switch 1 { case \"a\": break; }")[..],
+      [AnalyzerDiagnosticError::TypeMismatch(
+        DataType::Int,
+        DataType::String,
+        _
+      )]
+    ));
+    assert!(matches!(
+      &errors("switch 1 { case 1: break; case 1: break; }")[..],
+      [AnalyzerDiagnosticError::DuplicateSwitchCase(_, _)]
+    ));
+  }
+
+  #[test]
+  fn test_lambdas() {
+    assert_compiles(
+      "function makeCounter(): () -> int {\n  let mut count: int = 0;\n  return (): int -> {\n    count = count + 1;\n    return count;\n  };\n}\nfunction adder(n: int): (int) -> int {\n  return (x: int): int -> x + n;\n}\nadder(10)(5);",
+    );
+
+    assert!(matches!(
+      &errors("let x: int = 1;\nx();")[..],
+      [AnalyzerDiagnosticError::NotCallable(_)]
+    ));
+  }
+
+  #[test]
+  fn test_template_literals() {
+    assert_compiles("let age: int = 3;\n`${age + 1}, ${`nested {${age}}`}`;");
+
+    assert!(matches!(
+      &errors("function f(): void {}\n`${f()}`;")[..],
+      [AnalyzerDiagnosticError::InvalidTemplateExpression(
+        DataType::Void,
+        _
+      )]
+    ));
+  }
+
+  #[test]
+  fn test_index_expressions() {
+    let prelude = "let mut numbers: int[] = [1, 2, 3];\nlet name: string = \"ignis\";\n";
+
+    assert_compiles(&format!(
+      "{}numbers[1] = numbers[0] + numbers[2];\nname[1];",
+      prelude
+    ));
+
+    let errors = errors(&format!(
+      "{}numbers[true];\nname[0] = \"a\";\nnumbers[0] = \"a\";",
+      prelude
+    ));
+
+    assert!(matches!(
+      &errors[..],
+      [
+        AnalyzerDiagnosticError::InvalidIndexType(DataType::Boolean, _),
+        AnalyzerDiagnosticError::NotIndexable(DataType::String, _),
+        AnalyzerDiagnosticError::AssingInvalidType(DataType::String, DataType::Int, _),
+      ]
+    ));
+  }
+
+  #[test]
+  fn test_type_annotations() {
+    assert_compiles(
+      "let grid: int[][] = [[1, 2], [3, 4]];\nlet mut id: int | string = 1;\nid = \"one\";\nlet name: string? = null;\nlet pick: (int[][]) -> int = (rows: int[][]): int -> rows[1][0];",
+    );
+
+    assert!(matches!(
+      &errors("function f(value: int | string?): int { return 1; }\nf(true);")[..],
+      [AnalyzerDiagnosticError::ArgumentTypeMismatch(
+        _,
+        DataType::Boolean,
+        _
+      )]
+    ));
+
+    assert_compiles(
+      "let mut id: int | null = null;\nid = 1;\nfunction name(): string? {\n  return null;\n}",
+    );
+
+    assert!(matches!(
+      &errors("let s: string = null;\nlet mut n: int = 1;\nn = null;")[..],
+      [
+        AnalyzerDiagnosticError::AssingInvalidType(DataType::Null, DataType::String, _),
+        AnalyzerDiagnosticError::AssingInvalidType(DataType::Null, DataType::Int, _),
+      ]
+    ));
+  }
+
+  const SQUARE: &str = "type Size = int;\ninterface Shape {\n  area(): Size;\n}\nclass Square implements Shape {\n  public side: Size;\n  public Square(side: int) {\n    this.side = side;\n  }\n  public area(): int {\n    return this.side * this.side;\n  }\n}\n";
+
+  #[test]
+  fn test_interfaces_and_type_aliases() {
+    assert_compiles(&format!(
+      "{}function area(shape: Shape): Size {{\n  return shape.area();\n}}\narea(new Square(3));",
+      SQUARE
+    ));
+
+    assert!(matches!(
+      &errors(&format!(
+        "{}class Line implements Shape {{\n  public area(): string {{\n    return \"\";\n  }}\n}}",
+        SQUARE
+      ))[..],
+      [AnalyzerDiagnosticError::InterfaceMemberMismatch(
+        _,
+        _,
+        _,
+        _,
+        _
+      )]
+    ));
+  }
+
+  #[test]
+  fn test_generics() {
+    let prelude = "function apply<T, U>(x: T, f: (T) -> U): U {\n  return f(x);\n}\nclass Box<T> {\n  public value: T;\n  public Box(value: T) {\n    this.value = value;\n  }\n  public get(): T {\n    return this.value;\n  }\n}\n";
+
+    assert_compiles(&format!(
+      "{}let a: int = apply(20, (x: int): int -> x * 2) + 2;\nlet b: int = new Box(4).get() + 1;",
+      prelude
+    ));
+
+    assert!(matches!(
+      &errors(&format!("{}apply(1, (x: string): int -> 2);", prelude))[..],
+      [AnalyzerDiagnosticError::ArgumentTypeMismatch(_, _, _)]
+    ));
+  }
+
+  #[test]
+  fn test_inferred_variable_types() {
+    let analyzer = analyze("let total = 2 * 21;\nlet names = [\"a\", \"b\"];");
+
+    assert!(analyzer.diagnostics.is_empty());
+    assert_eq!(
+      analyzer.irs["main.ign"]
+        .iter()
+        .map(|instruction| analyzer.extract_data_type(instruction))
+        .collect::<Vec<DataType>>(),
+      vec![DataType::Int, DataType::Array(Box::new(DataType::String))]
+    );
+
+    assert!(matches!(
+      &errors("let empty = [];")[..],
+      [AnalyzerDiagnosticError::CannotInferType(_)]
+    ));
+    assert!(matches!(
+      &errors(
+        "function twice(x: int): int {\n  return x * 2;\n}\nlet names = [\"a\"];\ntwice(names[0]);"
+      )[..],
+      [AnalyzerDiagnosticError::ArgumentTypeMismatch(
+        DataType::Int,
+        DataType::String,
+        _
+      )]
+    ));
+  }
+
+  #[test]
+  fn test_type_checking() {
+    assert_compiles(
+      "function half(x: float): float {\n  return x / 2;\n}\nlet mut a = 3;\nlet b: float = 1;\nlet flag = !(a > 2) || false;\nhalf(a + b);\nlet c: float = flag ? 1 : 2.5;",
+    );
+
+    let errors = errors(
+      "let flag = true;\nlet mut a = 3;\nfunction f(): int {\n  return \"a\";\n}\nlet c = 1 + null;\nlet d = flag ? 1 : \"b\";\na = \"text\";",
+    );
+
+    assert!(matches!(
+      &errors[..],
+      [
+        AnalyzerDiagnosticError::ReturnTypeMismatch(DataType::Int, DataType::String, _),
+        AnalyzerDiagnosticError::TypeMismatch(DataType::Int, DataType::Null, _),
+        AnalyzerDiagnosticError::TypeMismatch(DataType::Int, DataType::String, _),
+        AnalyzerDiagnosticError::AssingInvalidType(DataType::String, DataType::Int, _),
+      ]
+    ));
+  }
+
+  #[test]
+  fn test_for_in_element_type() {
+    assert_compiles(
+      "let a: int[] = [1, 2];\nlet mut x: int = 0;\nfor (let i in a) {\n  x = i;\n}\nlet mut c: string = \"\";\nfor (let letter in \"ab\") {\n  c = letter;\n}",
+    );
+
+    assert!(matches!(
+      &errors("let a: string[] = [\"a\"];\nfor (let i in a) {\n  let x: int = i;\n}")[..],
+      [AnalyzerDiagnosticError::AssingInvalidType(
+        DataType::String,
+        DataType::Int,
+        _
+      )]
+    ));
+    assert!(matches!(
+      &errors("for (let i in 1) {}")[..],
+      [AnalyzerDiagnosticError::NotIterable(_)]
+    ));
+  }
+
+  #[test]
+  fn test_all_errors_are_reported() {
+    let errors = errors(
+      "function add(a: int, b: int): int {\n  let x = missing + 1;\n  let y = x * 2;\n  return add(true, \"b\");\n}\nlet z: string = 1;",
+    );
+
+    assert!(matches!(
+      &errors[..],
+      [
+        AnalyzerDiagnosticError::UndeclaredVariable(_),
+        AnalyzerDiagnosticError::ArgumentTypeMismatch(DataType::Int, DataType::Boolean, _),
+        AnalyzerDiagnosticError::ArgumentTypeMismatch(DataType::Int, DataType::String, _),
+        AnalyzerDiagnosticError::AssingInvalidType(DataType::Int, DataType::String, _),
+      ]
+    ));
+  }
+
+  #[test]
+  fn test_errors_of_operands_are_reported() {
+    let errors = errors(&format!(
+      "{}match a {{ Shape.EMPTY if b -> c, _ -> d }};\nswitch e {{ case f: break; }}\nlet s: Shape = Shape.RECT(g, h);\ni[j];\nk[l] = 1;\nfor (let x in 1) {{\n  m;\n}}",
+      SHAPE
+    ));
+
+    assert_eq!(errors.len(), 14, "{:?}", errors);
+    assert!(matches!(
+      errors.last(),
+      Some(AnalyzerDiagnosticError::NotIterable(_))
+    ));
+  }
+
+  #[test]
+  fn test_control_flow() {
+    assert_compiles(
+      "function pick(flag: boolean): int {\n  let mut x: int;\n  if (flag) {\n    x = 1;\n  } else {\n    x = 2;\n  }\n  return x;\n}",
+    );
+
+    assert!(matches!(
+      &errors("function sign(n: int): int {\n  if (n > 0) {\n    return 1;\n  }\n}")[..],
+      [AnalyzerDiagnosticError::MissingReturn(_)]
+    ));
+    assert!(matches!(
+      &errors("function first(flag: boolean): int {\n  let mut x: int;\n  if (flag) {\n    x = 1;\n  }\n  return x;\n}")[..],
+      [AnalyzerDiagnosticError::UnassignedVariable(_)]
+    ));
+
+    let analyzer = analyze("function one(): int {\n  return 1;\n  one();\n}");
+
+    assert!(analyzer.diagnostics.is_empty());
+    assert!(matches!(
+      &analyzer.warnings[..],
+      [AnalyzerDiagnosticWarning::UnreachableCode(token)] if token.span.line == 3
+    ));
+  }
+}
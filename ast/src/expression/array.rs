@@ -1,4 +1,3 @@
-use enums::data_type::DataType;
 use lexer::token::Token;
 
 use super::Expression;
@@ -7,15 +6,10 @@ use super::Expression;
 pub struct Array {
   pub token: Token,
   pub elements: Vec<Expression>,
-  pub data_type: DataType,
 }
 
 impl Array {
-  pub fn new(token: Token, elements: Vec<Expression>, data_type: DataType) -> Self {
-    Self {
-      token,
-      elements,
-      data_type,
-    }
+  pub fn new(token: Token, elements: Vec<Expression>) -> Self {
+    Self { token, elements }
   }
 }
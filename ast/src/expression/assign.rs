@@ -1,5 +1,4 @@
 use lexer::token::Token;
-use enums::data_type::DataType;
 
 use super::Expression;
 
@@ -7,15 +6,10 @@ use super::Expression;
 pub struct Assign {
   pub name: Token,
   pub value: Box<Expression>,
-  pub data_type: DataType,
 }
 
 impl Assign {
-  pub fn new(name: Token, value: Box<Expression>, data_type: DataType) -> Self {
-    Self {
-      name,
-      value,
-      data_type,
-    }
+  pub fn new(name: Token, value: Box<Expression>) -> Self {
+    Self { name, value }
   }
 }
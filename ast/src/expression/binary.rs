@@ -1,5 +1,4 @@
 use lexer::token::Token;
-use enums::data_type::DataType;
 use super::Expression;
 
 #[derive(Debug, PartialEq, Clone)]
@@ -7,21 +6,14 @@ pub struct Binary {
   pub left: Box<Expression>,
   pub operator: Token,
   pub right: Box<Expression>,
-  pub data_type: DataType,
 }
 
 impl Binary {
-  pub fn new(
-    left: Box<Expression>,
-    operator: Token,
-    right: Box<Expression>,
-    data_type: DataType,
-  ) -> Self {
+  pub fn new(left: Box<Expression>, operator: Token, right: Box<Expression>) -> Self {
     Self {
       left,
       operator,
       right,
-      data_type,
     }
   }
 }
@@ -1,5 +1,4 @@
 use lexer::token::Token;
-use enums::data_type::DataType;
 
 use super::Expression;
 
@@ -8,21 +7,14 @@ pub struct Call {
   pub callee: Box<Expression>,
   pub paren: Token,
   pub arguments: Vec<Expression>,
-  pub return_type: DataType,
 }
 
 impl Call {
-  pub fn new(
-    callee: Box<Expression>,
-    paren: Token,
-    arguments: Vec<Expression>,
-    return_type: DataType,
-  ) -> Self {
+  pub fn new(callee: Box<Expression>, paren: Token, arguments: Vec<Expression>) -> Self {
     Self {
       callee,
       paren,
       arguments,
-      return_type,
     }
   }
 }
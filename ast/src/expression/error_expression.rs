@@ -0,0 +1,16 @@
+use lexer::token::Token;
+
+/**
+  Takes the place of a missing expression, `token` is the token found
+  instead. Its error is already reported.
+*/
+#[derive(Debug, PartialEq, Clone)]
+pub struct ErrorExpression {
+  pub token: Token,
+}
+
+impl ErrorExpression {
+  pub fn new(token: Token) -> Self {
+    Self { token }
+  }
+}
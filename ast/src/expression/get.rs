@@ -0,0 +1,18 @@
+use lexer::token::Token;
+
+use super::Expression;
+
+/**
+  Reads the member `name` of `object`, `object.name`.
+*/
+#[derive(Debug, PartialEq, Clone)]
+pub struct Get {
+  pub object: Box<Expression>,
+  pub name: Token,
+}
+
+impl Get {
+  pub fn new(object: Box<Expression>, name: Token) -> Self {
+    Self { object, name }
+  }
+}
@@ -0,0 +1,23 @@
+use lexer::token::Token;
+
+use super::Expression;
+
+/**
+  Reads the element at `index` of `object`, `object[index]`.
+*/
+#[derive(Debug, PartialEq, Clone)]
+pub struct Index {
+  pub object: Box<Expression>,
+  pub index: Box<Expression>,
+  pub bracket: Token,
+}
+
+impl Index {
+  pub fn new(object: Box<Expression>, index: Box<Expression>, bracket: Token) -> Self {
+    Self {
+      object,
+      index,
+      bracket,
+    }
+  }
+}
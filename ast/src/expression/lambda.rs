@@ -0,0 +1,33 @@
+use enums::data_type::DataType;
+use lexer::token::Token;
+
+use crate::statement::{function::FunctionParameter, Statement};
+
+/**
+  An arrow function, `(a: int, b: int): int -> a + b`. The body of the
+  lambdas written with an expression is a `return` of that expression.
+  Without a return type it is the type of that expression.
+*/
+#[derive(Debug, PartialEq, Clone)]
+pub struct Lambda {
+  pub token: Token,
+  pub parameters: Vec<FunctionParameter>,
+  pub return_type: Option<DataType>,
+  pub body: Vec<Statement>,
+}
+
+impl Lambda {
+  pub fn new(
+    token: Token,
+    parameters: Vec<FunctionParameter>,
+    return_type: Option<DataType>,
+    body: Vec<Statement>,
+  ) -> Self {
+    Self {
+      token,
+      parameters,
+      return_type,
+      body,
+    }
+  }
+}
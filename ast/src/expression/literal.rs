@@ -1,12 +1,14 @@
 use enums::literal_value::LiteralValue;
+use lexer::token::Token;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Literal {
   pub value: LiteralValue,
+  pub token: Token,
 }
 
 impl Literal {
-  pub fn new(value: LiteralValue) -> Self {
-    Self { value }
+  pub fn new(value: LiteralValue, token: Token) -> Self {
+    Self { value, token }
   }
 }
@@ -1,5 +1,4 @@
 use lexer::token::Token;
-use enums::data_type::DataType;
 
 use super::Expression;
 
@@ -8,7 +7,6 @@ pub struct Logical {
   pub left: Box<Expression>,
   pub operator: Token,
   pub right: Box<Expression>,
-  pub data_type: DataType,
 }
 
 impl Logical {
@@ -17,7 +15,6 @@ impl Logical {
       left,
       operator,
       right,
-      data_type: DataType::Boolean,
     }
   }
 }
@@ -0,0 +1,119 @@
+use std::fmt::{self, Display, Formatter};
+
+use lexer::token::Token;
+use serde_json::json;
+
+use crate::statement::Statement;
+
+use super::{literal::Literal, Expression};
+
+/**
+  Pattern of an arm of a `match`.
+
+  - Literal: `"John"`, `1`, `true`.
+  - Wildcard: `_`, matches every value.
+  - Variant: a variant of an enum, `Option.SOME(value)`. The bindings are
+    the names given to the payload, `_` ignores a value.
+*/
+#[derive(Debug, PartialEq, Clone)]
+pub enum MatchPattern {
+  Literal(Literal),
+  Wildcard(Token),
+  Variant {
+    enum_name: Token,
+    variant: Token,
+    bindings: Vec<Token>,
+  },
+}
+
+impl MatchPattern {
+  pub fn token(&self) -> &Token {
+    match self {
+      MatchPattern::Literal(literal) => &literal.token,
+      MatchPattern::Wildcard(token) => token,
+      MatchPattern::Variant { variant, .. } => variant,
+    }
+  }
+}
+
+impl Display for MatchPattern {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    match self {
+      MatchPattern::Literal(literal) => write!(f, "{}", literal.value.to_string()),
+      MatchPattern::Wildcard(_) => write!(f, "_"),
+      MatchPattern::Variant {
+        enum_name,
+        variant,
+        bindings,
+      } if bindings.is_empty() => write!(f, "{}.{}", enum_name.span.literal, variant.span.literal),
+      MatchPattern::Variant {
+        enum_name,
+        variant,
+        bindings,
+      } => write!(
+        f,
+        "{}.{}({})",
+        enum_name.span.literal,
+        variant.span.literal,
+        bindings
+          .iter()
+          .map(|x| x.span.literal.clone())
+          .collect::<Vec<String>>()
+          .join(", ")
+      ),
+    }
+  }
+}
+
+/**
+  An arm of a `match`, `pattern if guard -> body`. The body is an
+  expression or a block.
+*/
+#[derive(Debug, PartialEq, Clone)]
+pub struct MatchArm {
+  pub pattern: MatchPattern,
+  pub guard: Option<Expression>,
+  pub body: Box<Statement>,
+}
+
+impl MatchArm {
+  pub fn new(pattern: MatchPattern, guard: Option<Expression>, body: Box<Statement>) -> Self {
+    Self {
+      pattern,
+      guard,
+      body,
+    }
+  }
+
+  pub fn to_json(&self) -> serde_json::Value {
+    json!({
+      "pattern": self.pattern.to_string(),
+      "guard": match &self.guard {
+        Some(guard) => guard.to_json(),
+        None => json!(null),
+      },
+      "body": self.body.to_json(),
+    })
+  }
+}
+
+/**
+  Compares `value` with the pattern of every arm in order, the value of the
+  `match` is the body of the first arm that matches.
+*/
+#[derive(Debug, PartialEq, Clone)]
+pub struct Match {
+  pub keyword: Token,
+  pub value: Box<Expression>,
+  pub arms: Vec<MatchArm>,
+}
+
+impl Match {
+  pub fn new(keyword: Token, value: Box<Expression>, arms: Vec<MatchArm>) -> Self {
+    Self {
+      keyword,
+      value,
+      arms,
+    }
+  }
+}
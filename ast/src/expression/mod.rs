@@ -2,7 +2,10 @@ use serde_json::json;
 
 use self::{
   binary::Binary, grouping::Grouping, literal::Literal, unary::Unary, variable::VariableExpression,
-  logical::Logical, assign::Assign, ternary::Ternary, call::Call, array::Array,
+  logical::Logical, assign::Assign, ternary::Ternary, call::Call, array::Array, this::This,
+  get::Get, set::Set, new::NewExpression, match_expression::Match, lambda::Lambda,
+  template_literal::{TemplateLiteral, TemplatePart}, index::Index, set_index::SetIndex,
+  error_expression::ErrorExpression,
 };
 
 use super::visitor::Visitor;
@@ -11,10 +14,20 @@ pub mod array;
 pub mod assign;
 pub mod binary;
 pub mod call;
+pub mod error_expression;
+pub mod get;
 pub mod grouping;
+pub mod index;
+pub mod lambda;
 pub mod literal;
 pub mod logical;
+pub mod match_expression;
+pub mod new;
+pub mod set;
+pub mod set_index;
+pub mod template_literal;
 pub mod ternary;
+pub mod this;
 pub mod unary;
 pub mod variable;
 
@@ -30,6 +43,16 @@ pub enum Expression {
   Ternary(Ternary),
   Call(Call),
   Array(Array),
+  This(This),
+  Get(Get),
+  Set(Set),
+  New(NewExpression),
+  Match(Match),
+  Lambda(Lambda),
+  TemplateLiteral(TemplateLiteral),
+  Index(Index),
+  SetIndex(SetIndex),
+  Error(ErrorExpression),
 }
 
 impl Expression {
@@ -45,6 +68,16 @@ impl Expression {
       Expression::Ternary(ternary) => visitor.visit_ternary_expression(ternary),
       Expression::Call(call) => visitor.visit_call_expression(call),
       Expression::Array(array) => visitor.visit_array_expression(array),
+      Expression::This(this) => visitor.visit_this_expression(this),
+      Expression::Get(get) => visitor.visit_get_expression(get),
+      Expression::Set(set) => visitor.visit_set_expression(set),
+      Expression::New(new) => visitor.visit_new_expression(new),
+      Expression::Match(expression) => visitor.visit_match_expression(expression),
+      Expression::Lambda(lambda) => visitor.visit_lambda_expression(lambda),
+      Expression::TemplateLiteral(template) => visitor.visit_template_literal_expression(template),
+      Expression::Index(index) => visitor.visit_index_expression(index),
+      Expression::SetIndex(set) => visitor.visit_set_index_expression(set),
+      Expression::Error(error) => visitor.visit_error_expression(error),
     }
   }
 
@@ -75,14 +108,12 @@ impl Expression {
           "type": "Unary",
           "operator": unary.operator.span.literal,
           "right": unary.right.to_json(),
-          "data_type": unary.data_type.to_string(),
         })
       }
       Expression::Variable(variable) => {
         json!({
           "type": "Variable",
           "name": variable.name.span.literal,
-          "data_type": variable.data_type.to_string(),
         })
       }
       Expression::Assign(assign) => {
@@ -90,7 +121,6 @@ impl Expression {
           "type": "Assign",
           "name": assign.name.span.literal,
           "value": assign.value.to_json(),
-          "data_type": assign.data_type.to_string(),
         })
       }
       Expression::Logical(logical) => {
@@ -99,7 +129,6 @@ impl Expression {
           "left": logical.left.to_json(),
           "operator": logical.operator.span.literal,
           "right": logical.right.to_json(),
-          "data_type": logical.data_type.to_string(),
         })
       }
       Expression::Ternary(ternary) => {
@@ -108,7 +137,6 @@ impl Expression {
           "condition": ternary.condition.to_json(),
           "then_branch": ternary.then_branch.to_json(),
           "else_branch": ternary.else_branch.to_json(),
-          "data_type": ternary.data_type.to_string(),
         })
       }
       Expression::Call(call) => {
@@ -116,14 +144,83 @@ impl Expression {
           "type": "Call",
           "callee": call.callee.to_json(),
           "arguments": call.arguments.iter().map(|x| x.to_json()).collect::<Vec<serde_json::Value>>(),
-          "return_type": call.return_type.to_string(),
         })
       }
       Expression::Array(array) => {
         json!({
           "type": "Array",
           "elements": array.elements.iter().map(|x| x.to_json()).collect::<Vec<serde_json::Value>>(),
-          "data_type": array.data_type.to_string(),
+        })
+      }
+      Expression::This(_) => {
+        json!({
+          "type": "This",
+        })
+      }
+      Expression::Get(get) => {
+        json!({
+          "type": "Get",
+          "object": get.object.to_json(),
+          "name": get.name.span.literal,
+        })
+      }
+      Expression::Set(set) => {
+        json!({
+          "type": "Set",
+          "object": set.object.to_json(),
+          "name": set.name.span.literal,
+          "value": set.value.to_json(),
+        })
+      }
+      Expression::New(new) => {
+        json!({
+          "type": "New",
+          "class_name": new.class_name.span.literal,
+          "arguments": new.arguments.iter().map(|x| x.to_json()).collect::<Vec<serde_json::Value>>(),
+        })
+      }
+      Expression::Match(expression) => {
+        json!({
+          "type": "Match",
+          "value": expression.value.to_json(),
+          "arms": expression.arms.iter().map(|x| x.to_json()).collect::<Vec<serde_json::Value>>(),
+        })
+      }
+      Expression::Lambda(lambda) => {
+        json!({
+          "type": "Lambda",
+          "parameters": lambda.parameters.iter().map(|x| x.to_json()).collect::<Vec<serde_json::Value>>(),
+          "return_type": match &lambda.return_type {
+            Some(return_type) => return_type.to_string(),
+            None => String::new(),
+          },
+          "body": lambda.body.iter().map(|x| x.to_json()).collect::<Vec<serde_json::Value>>(),
+        })
+      }
+      Expression::TemplateLiteral(template) => {
+        json!({
+          "type": "TemplateLiteral",
+          "parts": template.parts.iter().map(|x| x.to_json()).collect::<Vec<serde_json::Value>>(),
+        })
+      }
+      Expression::Index(index) => {
+        json!({
+          "type": "Index",
+          "object": index.object.to_json(),
+          "index": index.index.to_json(),
+        })
+      }
+      Expression::SetIndex(set) => {
+        json!({
+          "type": "SetIndex",
+          "object": set.object.to_json(),
+          "index": set.index.to_json(),
+          "value": set.value.to_json(),
+        })
+      }
+      Expression::Error(_) => {
+        json!({
+          "type": "Error",
         })
       }
     }
@@ -135,43 +232,27 @@ impl Expression {
         left,
         operator,
         right,
-        data_type,
       }) => format!(
-        "({} {} {}): {}",
+        "({} {} {})",
         operator.span.literal,
         left.to_string(),
         right.to_string(),
-        data_type.to_string(),
       ),
       Expression::Grouping(Grouping { expression }) => {
         format!("(group {})", (*expression).to_string())
       }
-      Expression::Literal(Literal { value }) => format!("{}", value.to_string()),
-      Expression::Unary(Unary {
-        operator,
-        right,
-        data_type,
-      }) => {
-        let operator_str = operator.span.literal.clone();
-        let right_str = (*right).to_string();
-        format!(
-          "({} {}): {}",
-          operator_str,
-          right_str,
-          data_type.to_string()
-        )
-      }
-      Expression::Variable(VariableExpression { name, data_type }) => {
-        format!("{:?}: {:?}", name, data_type)
+      Expression::Literal(Literal { value, .. }) => format!("{}", value.to_string()),
+      Expression::Unary(Unary { operator, right }) => {
+        format!("({} {})", operator.span.literal, (*right).to_string())
       }
-      Expression::Assign(Assign { name, value, .. }) => {
+      Expression::Variable(VariableExpression { name }) => name.span.literal.clone(),
+      Expression::Assign(Assign { name, value }) => {
         format!("{} = {}", name.span.literal, value.to_string())
       }
       Expression::Logical(Logical {
         left,
         operator,
         right,
-        ..
       }) => format!(
         "({} {} {})",
         left.to_string(),
@@ -210,6 +291,64 @@ impl Expression {
             .join(", ")
         )
       }
+      Expression::This(_) => "this".to_string(),
+      Expression::Get(get) => format!("{}.{}", get.object.to_string(), get.name.span.literal),
+      Expression::Set(set) => format!(
+        "{}.{} = {}",
+        set.object.to_string(),
+        set.name.span.literal,
+        set.value.to_string()
+      ),
+      Expression::New(new) => format!(
+        "new {}({})",
+        new.class_name.span.literal,
+        new
+          .arguments
+          .iter()
+          .map(|x| x.to_string())
+          .collect::<Vec<String>>()
+          .join(", ")
+      ),
+      Expression::Match(expression) => format!(
+        "match {} {{ {} }}",
+        expression.value.to_string(),
+        expression
+          .arms
+          .iter()
+          .map(|x| x.pattern.to_string())
+          .collect::<Vec<String>>()
+          .join(", ")
+      ),
+      Expression::Lambda(lambda) => format!(
+        "({}) -> ...",
+        lambda
+          .parameters
+          .iter()
+          .map(|x| x.to_string())
+          .collect::<Vec<String>>()
+          .join(", ")
+      ),
+      Expression::TemplateLiteral(template) => format!(
+        "`{}`",
+        template
+          .parts
+          .iter()
+          .map(|x| match x {
+            TemplatePart::Text(text) => text.span.literal.clone(),
+            TemplatePart::Expression(expression) => format!("${{{}}}", expression.to_string()),
+          })
+          .collect::<String>()
+      ),
+      Expression::Index(index) => {
+        format!("{}[{}]", index.object.to_string(), index.index.to_string())
+      }
+      Expression::SetIndex(set) => format!(
+        "{}[{}] = {}",
+        set.object.to_string(),
+        set.index.to_string(),
+        set.value.to_string()
+      ),
+      Expression::Error(_) => "<error>".to_string(),
     }
   }
 }
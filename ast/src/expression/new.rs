@@ -0,0 +1,23 @@
+use lexer::token::Token;
+
+use super::Expression;
+
+/**
+  Creates an instance of `class_name`, `new User(...)`.
+*/
+#[derive(Debug, PartialEq, Clone)]
+pub struct NewExpression {
+  pub keyword: Token,
+  pub class_name: Token,
+  pub arguments: Vec<Expression>,
+}
+
+impl NewExpression {
+  pub fn new(keyword: Token, class_name: Token, arguments: Vec<Expression>) -> Self {
+    Self {
+      keyword,
+      class_name,
+      arguments,
+    }
+  }
+}
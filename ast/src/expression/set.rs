@@ -0,0 +1,23 @@
+use lexer::token::Token;
+
+use super::Expression;
+
+/**
+  Assigns the member `name` of `object`, `object.name = value`.
+*/
+#[derive(Debug, PartialEq, Clone)]
+pub struct Set {
+  pub object: Box<Expression>,
+  pub name: Token,
+  pub value: Box<Expression>,
+}
+
+impl Set {
+  pub fn new(object: Box<Expression>, name: Token, value: Box<Expression>) -> Self {
+    Self {
+      object,
+      name,
+      value,
+    }
+  }
+}
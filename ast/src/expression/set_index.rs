@@ -0,0 +1,30 @@
+use lexer::token::Token;
+
+use super::Expression;
+
+/**
+  Assigns the element at `index` of `object`, `object[index] = value`.
+*/
+#[derive(Debug, PartialEq, Clone)]
+pub struct SetIndex {
+  pub object: Box<Expression>,
+  pub index: Box<Expression>,
+  pub value: Box<Expression>,
+  pub bracket: Token,
+}
+
+impl SetIndex {
+  pub fn new(
+    object: Box<Expression>,
+    index: Box<Expression>,
+    value: Box<Expression>,
+    bracket: Token,
+  ) -> Self {
+    Self {
+      object,
+      index,
+      value,
+      bracket,
+    }
+  }
+}
@@ -0,0 +1,42 @@
+use lexer::token::Token;
+use serde_json::json;
+
+use super::Expression;
+
+/**
+  A part of a template string, the text between the expressions or an
+  expression written inside `${}`.
+*/
+#[derive(Debug, PartialEq, Clone)]
+pub enum TemplatePart {
+  Text(Token),
+  Expression(Expression),
+}
+
+impl TemplatePart {
+  pub fn to_json(&self) -> serde_json::Value {
+    match self {
+      TemplatePart::Text(text) => json!({
+        "type": "Text",
+        "value": text.span.literal,
+      }),
+      TemplatePart::Expression(expression) => expression.to_json(),
+    }
+  }
+}
+
+/**
+  A template string, `` `Name: ${name}` ``. Its value is the text of every
+  part joined in order.
+*/
+#[derive(Debug, PartialEq, Clone)]
+pub struct TemplateLiteral {
+  pub token: Token,
+  pub parts: Vec<TemplatePart>,
+}
+
+impl TemplateLiteral {
+  pub fn new(token: Token, parts: Vec<TemplatePart>) -> Self {
+    Self { token, parts }
+  }
+}
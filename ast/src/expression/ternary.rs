@@ -1,4 +1,4 @@
-use enums::data_type::DataType;
+use lexer::token::Token;
 
 use super::Expression;
 
@@ -7,7 +7,7 @@ pub struct Ternary {
   pub condition: Box<Expression>,
   pub then_branch: Box<Expression>,
   pub else_branch: Box<Expression>,
-  pub data_type: DataType,
+  pub token: Token,
 }
 
 impl Ternary {
@@ -15,13 +15,13 @@ impl Ternary {
     condition: Box<Expression>,
     then_branch: Box<Expression>,
     else_branch: Box<Expression>,
-    data_type: DataType,
+    token: Token,
   ) -> Self {
     Self {
       condition,
       then_branch,
       else_branch,
-      data_type,
+      token,
     }
   }
 }
@@ -0,0 +1,12 @@
+use lexer::token::Token;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct This {
+  pub keyword: Token,
+}
+
+impl This {
+  pub fn new(keyword: Token) -> Self {
+    Self { keyword }
+  }
+}
@@ -1,13 +1,12 @@
-use {lexer::token::Token, enums::data_type::DataType};
+use lexer::token::Token;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct VariableExpression {
   pub name: Token,
-  pub data_type: DataType,
 }
 
 impl VariableExpression {
-  pub fn new(name: Token, data_type: DataType) -> Self {
-    Self { name, data_type }
+  pub fn new(name: Token) -> Self {
+    Self { name }
   }
 }
@@ -1,15 +1,140 @@
+use enums::data_type::DataType;
 use lexer::token::Token;
+use serde_json::json;
+
+use crate::expression::Expression;
 
 use super::function::FunctionStatement;
 
+/**
+  Modifiers written before a field or a method, members are public unless
+  they are marked `private`.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassMemberModifiers {
+  pub is_public: bool,
+  pub is_static: bool,
+  pub is_readonly: bool,
+}
+
+impl ClassMemberModifiers {
+  pub fn new(is_public: bool, is_static: bool, is_readonly: bool) -> Self {
+    Self {
+      is_public,
+      is_static,
+      is_readonly,
+    }
+  }
+
+  pub fn to_json(&self) -> serde_json::Value {
+    json!({
+      "is_public": self.is_public,
+      "is_static": self.is_static,
+      "is_readonly": self.is_readonly,
+    })
+  }
+}
+
+impl Default for ClassMemberModifiers {
+  fn default() -> Self {
+    Self::new(true, false, false)
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassField {
+  pub name: Token,
+  pub data_type: DataType,
+  pub initializer: Option<Expression>,
+  pub modifiers: ClassMemberModifiers,
+}
+
+impl ClassField {
+  pub fn new(
+    name: Token,
+    data_type: DataType,
+    initializer: Option<Expression>,
+    modifiers: ClassMemberModifiers,
+  ) -> Self {
+    Self {
+      name,
+      data_type,
+      initializer,
+      modifiers,
+    }
+  }
+
+  pub fn to_json(&self) -> serde_json::Value {
+    json!({
+      "name": self.name.span.literal,
+      "data_type": self.data_type.to_string(),
+      "initializer": match &self.initializer {
+        Some(initializer) => initializer.to_json(),
+        None => json!(null),
+      },
+      "modifiers": self.modifiers.to_json(),
+    })
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassMethod {
+  pub function: FunctionStatement,
+  pub modifiers: ClassMemberModifiers,
+}
+
+impl ClassMethod {
+  pub fn new(function: FunctionStatement, modifiers: ClassMemberModifiers) -> Self {
+    Self {
+      function,
+      modifiers,
+    }
+  }
+
+  pub fn to_json(&self) -> serde_json::Value {
+    json!({
+      "name": self.function.name.span.literal,
+      "parameters": self.function.parameters.iter().map(|x| x.to_json()).collect::<Vec<serde_json::Value>>(),
+      "return_type": match &self.function.return_type {
+        Some(return_type) => return_type.to_string(),
+        None => String::new(),
+      },
+      "modifiers": self.modifiers.to_json(),
+    })
+  }
+}
+
+/**
+  A class declaration, the constructor is the method named after the class.
+  `generics` are its type parameters, `class Box<T>`, and `implements` are
+  the interfaces the class declares to conform to.
+*/
 #[derive(Debug, Clone, PartialEq)]
 pub struct Class {
   pub name: Token,
-  pub methods: Vec<FunctionStatement>,
+  pub generics: Vec<Token>,
+  pub implements: Vec<Token>,
+  pub fields: Vec<ClassField>,
+  pub constructor: Option<ClassMethod>,
+  pub methods: Vec<ClassMethod>,
 }
 
 impl Class {
-  pub fn new(name: Token, methods: Vec<FunctionStatement>) -> Self {
-    Self { name, methods }
+  pub fn new(
+    name: Token,
+    generics: Vec<Token>,
+    implements: Vec<Token>,
+    fields: Vec<ClassField>,
+    constructor: Option<ClassMethod>,
+    methods: Vec<ClassMethod>,
+  ) -> Self {
+    Self {
+      name,
+      generics,
+      implements,
+      fields,
+      constructor,
+      methods,
+    }
   }
 }
@@ -0,0 +1,58 @@
+use enums::data_type::DataType;
+use lexer::token::Token;
+use serde_json::json;
+
+use crate::expression::Expression;
+
+/**
+  A variant of an enum, it is either a unit variant `NONE`, a variant with
+  a payload `SOME(T)` or a variant with a constant value `RED = "RED"`.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumVariant {
+  pub name: Token,
+  pub fields: Vec<DataType>,
+  pub value: Option<Expression>,
+}
+
+impl EnumVariant {
+  pub fn new(name: Token, fields: Vec<DataType>, value: Option<Expression>) -> Self {
+    Self {
+      name,
+      fields,
+      value,
+    }
+  }
+
+  pub fn to_json(&self) -> serde_json::Value {
+    json!({
+      "name": self.name.span.literal,
+      "fields": self.fields.iter().map(|x| x.to_string()).collect::<Vec<String>>(),
+      "value": match &self.value {
+        Some(value) => value.to_json(),
+        None => json!(null),
+      },
+    })
+  }
+}
+
+/**
+  An enum declaration, `generics` are the type parameters used by the
+  payloads of its variants, `enum Option<T> { SOME(T), NONE }`.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct Enum {
+  pub name: Token,
+  pub generics: Vec<Token>,
+  pub variants: Vec<EnumVariant>,
+}
+
+impl Enum {
+  pub fn new(name: Token, generics: Vec<Token>, variants: Vec<EnumVariant>) -> Self {
+    Self {
+      name,
+      generics,
+      variants,
+    }
+  }
+}
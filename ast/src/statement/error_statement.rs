@@ -0,0 +1,16 @@
+use lexer::token::Token;
+
+/**
+  Takes the place of a statement that could not be parsed, `token` is where
+  the statement starts. Its error is already reported.
+*/
+#[derive(Debug, PartialEq, Clone)]
+pub struct ErrorStatement {
+  pub token: Token,
+}
+
+impl ErrorStatement {
+  pub fn new(token: Token) -> Self {
+    Self { token }
+  }
+}
@@ -43,9 +43,14 @@ impl FunctionParameter {
   }
 }
 
+/**
+  A function declaration, `generics` are the type parameters written after
+  its name, `function map<T, U>(...)`.
+*/
 #[derive(Debug, Clone, PartialEq)]
 pub struct FunctionStatement {
   pub name: Token,
+  pub generics: Vec<Token>,
   pub parameters: Vec<FunctionParameter>,
   pub body: Vec<Statement>,
   pub return_type: Option<DataType>,
@@ -56,6 +61,7 @@ pub struct FunctionStatement {
 impl FunctionStatement {
   pub fn new(
     name: Token,
+    generics: Vec<Token>,
     parameters: Vec<FunctionParameter>,
     body: Vec<Statement>,
     return_type: Option<DataType>,
@@ -64,6 +70,7 @@ impl FunctionStatement {
   ) -> Self {
     Self {
       name,
+      generics,
       parameters,
       body,
       return_type,
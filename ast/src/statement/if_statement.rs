@@ -1,3 +1,5 @@
+use lexer::token::Token;
+
 use crate::expression::Expression;
 
 use super::Statement;
@@ -7,6 +9,7 @@ pub struct IfStatement {
   pub condition: Box<Expression>,
   pub then_branch: Box<Statement>,
   pub else_branch: Option<Box<Statement>>,
+  pub token: Token,
 }
 
 impl IfStatement {
@@ -14,11 +17,13 @@ impl IfStatement {
     condition: Box<Expression>,
     then_branch: Box<Statement>,
     else_branch: Option<Box<Statement>>,
+    token: Token,
   ) -> Self {
     Self {
       condition,
       then_branch,
       else_branch,
+      token,
     }
   }
 }
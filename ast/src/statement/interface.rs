@@ -0,0 +1,81 @@
+use enums::data_type::DataType;
+use lexer::token::Token;
+use serde_json::json;
+
+use super::function::FunctionParameter;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InterfaceField {
+  pub name: Token,
+  pub data_type: DataType,
+}
+
+impl InterfaceField {
+  pub fn new(name: Token, data_type: DataType) -> Self {
+    Self { name, data_type }
+  }
+
+  pub fn to_json(&self) -> serde_json::Value {
+    json!({
+      "name": self.name.span.literal,
+      "data_type": self.data_type.to_string(),
+    })
+  }
+}
+
+/**
+  A method signature of an interface, `area(): float;`.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct InterfaceMethod {
+  pub name: Token,
+  pub parameters: Vec<FunctionParameter>,
+  pub return_type: DataType,
+}
+
+impl InterfaceMethod {
+  pub fn new(name: Token, parameters: Vec<FunctionParameter>, return_type: DataType) -> Self {
+    Self {
+      name,
+      parameters,
+      return_type,
+    }
+  }
+
+  pub fn to_json(&self) -> serde_json::Value {
+    json!({
+      "name": self.name.span.literal,
+      "parameters": self.parameters.iter().map(|x| x.to_json()).collect::<Vec<serde_json::Value>>(),
+      "return_type": self.return_type.to_string(),
+    })
+  }
+}
+
+/**
+  An interface declaration, the members that a class must declare to be
+  used as the interface. `extends` are the interfaces whose members are
+  included in this one.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct Interface {
+  pub name: Token,
+  pub extends: Vec<Token>,
+  pub fields: Vec<InterfaceField>,
+  pub methods: Vec<InterfaceMethod>,
+}
+
+impl Interface {
+  pub fn new(
+    name: Token,
+    extends: Vec<Token>,
+    fields: Vec<InterfaceField>,
+    methods: Vec<InterfaceMethod>,
+  ) -> Self {
+    Self {
+      name,
+      extends,
+      fields,
+      methods,
+    }
+  }
+}
@@ -2,6 +2,8 @@ pub mod block;
 pub mod break_statement;
 pub mod class;
 pub mod continue_statement;
+pub mod enum_statement;
+pub mod error_statement;
 pub mod export;
 pub mod expression;
 pub mod extern_statement;
@@ -10,7 +12,10 @@ pub mod forof;
 pub mod function;
 pub mod if_statement;
 pub mod import;
+pub mod interface;
 pub mod return_statement;
+pub mod switch;
+pub mod type_alias;
 pub mod variable;
 pub mod while_statement;
 
@@ -20,7 +25,8 @@ use self::{
   expression::ExpressionStatement, variable::Variable, if_statement::IfStatement, block::Block,
   while_statement::WhileStatement, function::FunctionStatement, return_statement::Return,
   class::Class, for_in::ForIn, import::Import, break_statement::BreakStatement,
-  continue_statement::Continue,
+  continue_statement::Continue, enum_statement::Enum, switch::Switch, interface::Interface,
+  type_alias::TypeAlias, error_statement::ErrorStatement,
 };
 
 use crate::{visitor::Visitor, statement::import::ImportSource};
@@ -39,6 +45,11 @@ pub enum Statement {
   Import(Import),
   Break(BreakStatement),
   Continue(Continue),
+  Enum(Enum),
+  Switch(Switch),
+  Interface(Interface),
+  TypeAlias(TypeAlias),
+  Error(ErrorStatement),
 }
 
 impl Statement {
@@ -60,6 +71,11 @@ impl Statement {
       Statement::Continue(continue_statement) => {
         visitor.visit_continue_statement(continue_statement)
       }
+      Statement::Enum(enum_statement) => visitor.visit_enum_statement(enum_statement),
+      Statement::Switch(switch) => visitor.visit_switch_statement(switch),
+      Statement::Interface(interface) => visitor.visit_interface_statement(interface),
+      Statement::TypeAlias(alias) => visitor.visit_type_alias_statement(alias),
+      Statement::Error(error) => visitor.visit_error_statement(error),
     }
   }
 
@@ -76,7 +92,10 @@ impl Statement {
           "type": "Variable",
             "name": variable.name.span.literal,
             "initializer": initializer,
-            "type_annotation": variable.type_annotation.to_string(),
+            "type_annotation": match &variable.type_annotation {
+              Some(type_annotation) => type_annotation.to_string(),
+              None => String::new(),
+            },
             "is_mutable": variable.metadata.is_mutable,
             "is_global": variable.metadata.is_global,
             "is_static": variable.metadata.is_static,
@@ -112,6 +131,7 @@ impl Statement {
         json!({
           "type": "FunctionStatement",
           "name": function.name.span.literal,
+          "generics": function.generics.iter().map(|x| x.span.literal.clone()).collect::<Vec<String>>(),
           "parameters": function.parameters.iter().map(|x| x.to_json()).collect::<Vec<serde_json::Value>>(),
           "body": function.body.iter().map(|x| x.to_json()).collect::<Vec<serde_json::Value>>(),
           "return_type": match &function.return_type {
@@ -133,8 +153,14 @@ impl Statement {
         json!({
           "type": "Class",
           "name": class.name.span.literal,
-          // "methods": class.methods.iter().map(|x| x.to_json()).collect::<Vec<serde_json::Value>>(),
-          // "properties": class.properties.iter().map(|x| x.to_json()).collect::<Vec<serde_json::Value>>(),
+          "generics": class.generics.iter().map(|x| x.span.literal.clone()).collect::<Vec<String>>(),
+          "implements": class.implements.iter().map(|x| x.span.literal.clone()).collect::<Vec<String>>(),
+          "fields": class.fields.iter().map(|x| x.to_json()).collect::<Vec<serde_json::Value>>(),
+          "constructor": match &class.constructor {
+            Some(constructor) => constructor.to_json(),
+            None => json!(null),
+          },
+          "methods": class.methods.iter().map(|x| x.to_json()).collect::<Vec<serde_json::Value>>(),
         })
       }
       Statement::ForIn(for_in) => {
@@ -173,6 +199,42 @@ impl Statement {
             "type": "Continue",
         })
       }
+      Statement::Enum(enum_statement) => {
+        json!({
+          "type": "Enum",
+          "name": enum_statement.name.span.literal,
+          "generics": enum_statement.generics.iter().map(|x| x.span.literal.clone()).collect::<Vec<String>>(),
+          "variants": enum_statement.variants.iter().map(|x| x.to_json()).collect::<Vec<serde_json::Value>>(),
+        })
+      }
+      Statement::Switch(switch) => {
+        json!({
+          "type": "Switch",
+          "value": switch.value.to_json(),
+          "cases": switch.cases.iter().map(|x| x.to_json()).collect::<Vec<serde_json::Value>>(),
+        })
+      }
+      Statement::Interface(interface) => {
+        json!({
+          "type": "Interface",
+          "name": interface.name.span.literal,
+          "extends": interface.extends.iter().map(|x| x.span.literal.clone()).collect::<Vec<String>>(),
+          "fields": interface.fields.iter().map(|x| x.to_json()).collect::<Vec<serde_json::Value>>(),
+          "methods": interface.methods.iter().map(|x| x.to_json()).collect::<Vec<serde_json::Value>>(),
+        })
+      }
+      Statement::TypeAlias(alias) => {
+        json!({
+          "type": "TypeAlias",
+          "name": alias.name.span.literal,
+          "value": alias.value.to_string(),
+        })
+      }
+      Statement::Error(_) => {
+        json!({
+          "type": "Error",
+        })
+      }
     }
   }
 }
@@ -0,0 +1,55 @@
+use lexer::token::Token;
+use serde_json::json;
+
+use crate::expression::Expression;
+
+use super::Statement;
+
+/**
+  A `case value:` of a `switch`, `value` is `None` for `default:`. An empty
+  body shares the body of the next case.
+*/
+#[derive(Debug, PartialEq, Clone)]
+pub struct SwitchCase {
+  pub token: Token,
+  pub value: Option<Expression>,
+  pub body: Vec<Statement>,
+}
+
+impl SwitchCase {
+  pub fn new(token: Token, value: Option<Expression>, body: Vec<Statement>) -> Self {
+    Self { token, value, body }
+  }
+
+  pub fn to_json(&self) -> serde_json::Value {
+    json!({
+      "value": match &self.value {
+        Some(value) => value.to_json(),
+        None => json!("default"),
+      },
+      "body": self.body.iter().map(|x| x.to_json()).collect::<Vec<serde_json::Value>>(),
+    })
+  }
+}
+
+/**
+  Runs the body of the case equal to `value`, or of `default` if there is
+  none, like in C the execution continues with the next case until a
+  `break`.
+*/
+#[derive(Debug, PartialEq, Clone)]
+pub struct Switch {
+  pub keyword: Token,
+  pub value: Box<Expression>,
+  pub cases: Vec<SwitchCase>,
+}
+
+impl Switch {
+  pub fn new(keyword: Token, value: Box<Expression>, cases: Vec<SwitchCase>) -> Self {
+    Self {
+      keyword,
+      value,
+      cases,
+    }
+  }
+}
@@ -0,0 +1,17 @@
+use enums::data_type::DataType;
+use lexer::token::Token;
+
+/**
+  A new name for a type, `type Id = int | string;`.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeAlias {
+  pub name: Token,
+  pub value: DataType,
+}
+
+impl TypeAlias {
+  pub fn new(name: Token, value: DataType) -> Self {
+    Self { name, value }
+  }
+}
@@ -30,11 +30,15 @@ impl VariableMetadata {
   }
 }
 
+/**
+  A variable declaration, the type of a variable without `type_annotation`
+  is inferred from its initializer.
+*/
 #[derive(Debug, PartialEq, Clone)]
 pub struct Variable {
   pub name: Box<Token>,
   pub initializer: Option<Box<Expression>>,
-  pub type_annotation: DataType,
+  pub type_annotation: Option<DataType>,
   pub metadata: VariableMetadata,
 }
 
@@ -42,7 +46,7 @@ impl Variable {
   pub fn new(
     name: Box<Token>,
     initializer: Option<Box<Expression>>,
-    type_annotation: DataType,
+    type_annotation: Option<DataType>,
     metadata: VariableMetadata,
   ) -> Self {
     Self {
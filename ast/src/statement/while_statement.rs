@@ -1,3 +1,5 @@
+use lexer::token::Token;
+
 use crate::expression::Expression;
 
 use super::Statement;
@@ -6,10 +8,15 @@ use super::Statement;
 pub struct WhileStatement {
   pub condition: Box<Expression>,
   pub body: Box<Statement>,
+  pub token: Token,
 }
 
 impl WhileStatement {
-  pub fn new(condition: Box<Expression>, body: Box<Statement>) -> Self {
-    Self { condition, body }
+  pub fn new(condition: Box<Expression>, body: Box<Statement>, token: Token) -> Self {
+    Self {
+      condition,
+      body,
+      token,
+    }
   }
 }
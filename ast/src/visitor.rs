@@ -2,12 +2,16 @@ use crate::{
   expression::{
     binary::Binary, literal::Literal, unary::Unary, grouping::Grouping,
     variable::VariableExpression, assign::Assign, logical::Logical, ternary::Ternary, call::Call,
-    array::Array,
+    array::Array, this::This, get::Get, set::Set, new::NewExpression, match_expression::Match,
+    lambda::Lambda, template_literal::TemplateLiteral, index::Index, set_index::SetIndex,
+    error_expression::ErrorExpression,
   },
   statement::{
     expression::ExpressionStatement, variable::Variable, if_statement::IfStatement, block::Block,
     while_statement::WhileStatement, function::FunctionStatement, return_statement::Return,
     class::Class, for_in::ForIn, import::Import, break_statement::BreakStatement, continue_statement::Continue,
+    enum_statement::Enum, switch::Switch, interface::Interface,
+    type_alias::TypeAlias, error_statement::ErrorStatement,
   },
 };
 
@@ -23,6 +27,16 @@ pub trait Visitor<R> {
   fn visit_ternary_expression(&mut self, expression: &Ternary) -> R;
   fn visit_call_expression(&mut self, expression: &Call) -> R;
   fn visit_array_expression(&mut self, expression: &Array) -> R;
+  fn visit_this_expression(&mut self, expression: &This) -> R;
+  fn visit_get_expression(&mut self, expression: &Get) -> R;
+  fn visit_set_expression(&mut self, expression: &Set) -> R;
+  fn visit_new_expression(&mut self, expression: &NewExpression) -> R;
+  fn visit_match_expression(&mut self, expression: &Match) -> R;
+  fn visit_lambda_expression(&mut self, expression: &Lambda) -> R;
+  fn visit_template_literal_expression(&mut self, expression: &TemplateLiteral) -> R;
+  fn visit_index_expression(&mut self, expression: &Index) -> R;
+  fn visit_set_index_expression(&mut self, expression: &SetIndex) -> R;
+  fn visit_error_expression(&mut self, expression: &ErrorExpression) -> R;
 
   // Statements
   fn visit_expression_statement(&mut self, statement: &ExpressionStatement) -> R;
@@ -37,4 +51,9 @@ pub trait Visitor<R> {
   fn visit_import_statement(&mut self, statement: &Import) -> R;
   fn visit_break_statement(&mut self, statement: &BreakStatement) -> R;
   fn visit_continue_statement(&mut self, statement: &Continue) -> R;
+  fn visit_enum_statement(&mut self, statement: &Enum) -> R;
+  fn visit_switch_statement(&mut self, statement: &Switch) -> R;
+  fn visit_interface_statement(&mut self, statement: &Interface) -> R;
+  fn visit_type_alias_statement(&mut self, statement: &TypeAlias) -> R;
+  fn visit_error_statement(&mut self, statement: &ErrorStatement) -> R;
 }
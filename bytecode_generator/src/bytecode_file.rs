@@ -0,0 +1,901 @@
+use std::{fmt::Display, rc::Rc};
+
+use crate::{Bytecode, FunctionPrototype, NativeDeclaration, OpCode, Value, VariantDeclaration};
+
+/**
+  Layout of an `.ignc` file, every integer is little endian and `varint` is an
+  unsigned LEB128 number:
+
+  - header: magic `IGNC`, format version (u16) and number of sections (varint).
+  - section: module path (string) followed by the top level function.
+  - function: name (string), arity (varint), code length (varint) and code
+    bytes, line table, constant count (varint) and constants.
+  - line table: number of runs (varint) and `(line, length)` pairs (varints),
+    consecutive bytes of the same line are stored once.
+  - constant: tag byte followed by an i64, f64, string, nested function or
+    native declaration (module and name strings, arity varint).
+  - string: byte length (varint) followed by UTF-8 bytes.
+*/
+pub const MAGIC: &[u8; 4] = b"IGNC";
+pub const FORMAT_VERSION: u16 = 1;
+
+const TAG_INT: u8 = 0;
+const TAG_FLOAT: u8 = 1;
+const TAG_STRING: u8 = 2;
+const TAG_FUNCTION: u8 = 3;
+const TAG_NATIVE: u8 = 4;
+const TAG_VARIANT: u8 = 5;
+
+const MAX_FUNCTION_DEPTH: usize = 256;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BytecodeFileError {
+  InvalidMagic,
+  UnsupportedVersion(u16),
+  UnexpectedEnd,
+  InvalidVarint(usize),
+  InvalidUtf8(usize),
+  InvalidConstantTag(u8, usize),
+  InvalidOpCode(u8, usize),
+  TruncatedInstruction(OpCode, usize),
+  InvalidConstantIndex(OpCode, usize, usize),
+  InvalidJumpTarget(OpCode, usize),
+  InvalidLocalSlot(OpCode, usize, usize),
+  UncapturedUpvalues(String, usize, usize),
+  StackUnderflow(OpCode, usize),
+  StackMismatch(usize),
+  LineTableMismatch(usize, usize),
+  FunctionTooDeep,
+  TrailingBytes(usize),
+}
+
+impl Display for BytecodeFileError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      BytecodeFileError::InvalidMagic => write!(f, "Not an ignc file"),
+      BytecodeFileError::UnsupportedVersion(version) => write!(
+        f,
+        "Unsupported ignc version {}, expected {}",
+        version, FORMAT_VERSION
+      ),
+      BytecodeFileError::UnexpectedEnd => write!(f, "Unexpected end of file"),
+      BytecodeFileError::InvalidVarint(offset) => {
+        write!(f, "Invalid number at byte {}", offset)
+      }
+      BytecodeFileError::InvalidUtf8(offset) => {
+        write!(f, "Invalid UTF-8 string at byte {}", offset)
+      }
+      BytecodeFileError::InvalidConstantTag(tag, offset) => {
+        write!(f, "Invalid constant tag {} at byte {}", tag, offset)
+      }
+      BytecodeFileError::InvalidOpCode(byte, offset) => {
+        write!(f, "Invalid opcode {} at offset {}", byte, offset)
+      }
+      BytecodeFileError::TruncatedInstruction(op, offset) => {
+        write!(f, "Missing operands for {:?} at offset {}", op, offset)
+      }
+      BytecodeFileError::InvalidConstantIndex(op, index, offset) => write!(
+        f,
+        "Invalid constant {} for {:?} at offset {}",
+        index, op, offset
+      ),
+      BytecodeFileError::InvalidJumpTarget(op, offset) => {
+        write!(
+          f,
+          "{:?} at offset {} does not jump to an instruction",
+          op, offset
+        )
+      }
+      BytecodeFileError::InvalidLocalSlot(op, slot, offset) => write!(
+        f,
+        "Invalid local slot {} for {:?} at offset {}",
+        slot, op, offset
+      ),
+      BytecodeFileError::UncapturedUpvalues(name, used, captured) => write!(
+        f,
+        "'{}' uses {} upvalues but only {} are captured",
+        name, used, captured
+      ),
+      BytecodeFileError::StackUnderflow(op, offset) => {
+        write!(f, "{:?} at offset {} pops an empty stack", op, offset)
+      }
+      BytecodeFileError::StackMismatch(offset) => write!(
+        f,
+        "The stack has different sizes on the paths that reach offset {}",
+        offset
+      ),
+      BytecodeFileError::LineTableMismatch(lines, code) => write!(
+        f,
+        "Line table covers {} bytes but the code has {}",
+        lines, code
+      ),
+      BytecodeFileError::FunctionTooDeep => write!(f, "Functions are nested too deeply"),
+      BytecodeFileError::TrailingBytes(offset) => {
+        write!(f, "Unexpected data after byte {}", offset)
+      }
+    }
+  }
+}
+
+pub type BytecodeFileResult<T> = Result<T, BytecodeFileError>;
+
+/**
+  One compiled module stored in an `.ignc` file.
+*/
+#[derive(Debug, Clone)]
+pub struct ModuleSection {
+  pub module_path: String,
+  pub bytecode: Bytecode,
+}
+
+impl ModuleSection {
+  pub fn new(module_path: String, bytecode: Bytecode) -> Self {
+    Self {
+      module_path,
+      bytecode,
+    }
+  }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BytecodeFile {
+  pub sections: Vec<ModuleSection>,
+}
+
+impl BytecodeFile {
+  pub fn new() -> Self {
+    Self { sections: vec![] }
+  }
+
+  pub fn add_section(&mut self, module_path: String, bytecode: Bytecode) {
+    self.sections.push(ModuleSection::new(module_path, bytecode));
+  }
+
+  pub fn section(&self, module_path: &str) -> Option<&ModuleSection> {
+    self.sections.iter().find(|x| x.module_path == module_path)
+  }
+
+  pub fn to_bytes(&self) -> Vec<u8> {
+    let mut writer = Writer::new();
+
+    writer.bytes.extend_from_slice(MAGIC);
+    writer.bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+    writer.varint(self.sections.len());
+
+    for section in &self.sections {
+      writer.string(&section.module_path);
+      writer.function("<script>", 0, &section.bytecode);
+    }
+
+    writer.bytes
+  }
+
+  /**
+    Reads and validates an `.ignc` file.
+    Every instruction is decoded, its operands are checked and the stack is
+    followed along every path, so a file that loads cannot make the VM read
+    outside of its code, constants or frames.
+  */
+  pub fn from_bytes(bytes: &[u8]) -> BytecodeFileResult<Self> {
+    let mut reader = Reader::new(bytes);
+
+    if reader.take(MAGIC.len())? != MAGIC {
+      return Err(BytecodeFileError::InvalidMagic);
+    }
+
+    let version = u16::from_le_bytes(reader.array::<2>()?);
+
+    if version != FORMAT_VERSION {
+      return Err(BytecodeFileError::UnsupportedVersion(version));
+    }
+
+    let count = reader.varint()?;
+    let mut file = BytecodeFile::new();
+
+    for _ in 0..count {
+      let module_path = reader.string()?;
+      let (function, upvalues) = reader.function(0)?;
+
+      if upvalues > 0 {
+        return Err(BytecodeFileError::UncapturedUpvalues(
+          function.name,
+          upvalues,
+          0,
+        ));
+      }
+
+      file.add_section(module_path, function.bytecode);
+    }
+
+    if reader.offset != bytes.len() {
+      return Err(BytecodeFileError::TrailingBytes(reader.offset));
+    }
+
+    Ok(file)
+  }
+}
+
+struct Writer {
+  bytes: Vec<u8>,
+}
+
+impl Writer {
+  fn new() -> Self {
+    Self { bytes: vec![] }
+  }
+
+  fn varint(&mut self, value: usize) {
+    let mut value = value as u64;
+
+    loop {
+      let byte = (value & 0x7f) as u8;
+      value >>= 7;
+
+      if value == 0 {
+        self.bytes.push(byte);
+        break;
+      }
+
+      self.bytes.push(byte | 0x80);
+    }
+  }
+
+  fn string(&mut self, string: &str) {
+    self.varint(string.len());
+    self.bytes.extend_from_slice(string.as_bytes());
+  }
+
+  fn function(&mut self, name: &str, arity: usize, bytecode: &Bytecode) {
+    self.string(name);
+    self.varint(arity);
+
+    self.varint(bytecode.code.len());
+    self.bytes.extend_from_slice(&bytecode.code);
+
+    let runs = compress_lines(&bytecode.lines);
+    self.varint(runs.len());
+
+    for (line, length) in runs {
+      self.varint(line);
+      self.varint(length);
+    }
+
+    self.varint(bytecode.constants.len());
+
+    for constant in &bytecode.constants {
+      match constant {
+        Value::Int(int) => {
+          self.bytes.push(TAG_INT);
+          self.bytes.extend_from_slice(&int.to_le_bytes());
+        }
+        Value::Float(float) => {
+          self.bytes.push(TAG_FLOAT);
+          self.bytes.extend_from_slice(&float.to_le_bytes());
+        }
+        Value::String(string) => {
+          self.bytes.push(TAG_STRING);
+          self.string(string);
+        }
+        Value::Function(function) => {
+          self.bytes.push(TAG_FUNCTION);
+          self.function(&function.name, function.arity, &function.bytecode);
+        }
+        Value::Native(native) => {
+          self.bytes.push(TAG_NATIVE);
+          self.string(&native.module);
+          self.string(&native.name);
+          self.varint(native.arity);
+        }
+        Value::Variant(variant) => {
+          self.bytes.push(TAG_VARIANT);
+          self.string(&variant.enum_name);
+          self.string(&variant.name);
+          self.varint(variant.arity);
+        }
+      }
+    }
+  }
+}
+
+struct Reader<'a> {
+  bytes: &'a [u8],
+  offset: usize,
+}
+
+impl<'a> Reader<'a> {
+  fn new(bytes: &'a [u8]) -> Self {
+    Self { bytes, offset: 0 }
+  }
+
+  fn take(&mut self, length: usize) -> BytecodeFileResult<&'a [u8]> {
+    let end = self
+      .offset
+      .checked_add(length)
+      .filter(|end| *end <= self.bytes.len())
+      .ok_or(BytecodeFileError::UnexpectedEnd)?;
+
+    let bytes = &self.bytes[self.offset..end];
+    self.offset = end;
+
+    Ok(bytes)
+  }
+
+  fn array<const N: usize>(&mut self) -> BytecodeFileResult<[u8; N]> {
+    let mut array = [0; N];
+    array.copy_from_slice(self.take(N)?);
+
+    Ok(array)
+  }
+
+  fn byte(&mut self) -> BytecodeFileResult<u8> {
+    Ok(self.take(1)?[0])
+  }
+
+  fn varint(&mut self) -> BytecodeFileResult<usize> {
+    let start = self.offset;
+    let mut value: u64 = 0;
+    let mut shift = 0;
+
+    loop {
+      let byte = self.byte()?;
+
+      if shift >= 64 || (shift == 63 && byte > 1) {
+        return Err(BytecodeFileError::InvalidVarint(start));
+      }
+
+      value |= ((byte & 0x7f) as u64) << shift;
+      shift += 7;
+
+      if byte & 0x80 == 0 {
+        break;
+      }
+    }
+
+    usize::try_from(value).map_err(|_| BytecodeFileError::InvalidVarint(start))
+  }
+
+  fn string(&mut self) -> BytecodeFileResult<String> {
+    let length = self.varint()?;
+    let start = self.offset;
+    let bytes = self.take(length)?;
+
+    String::from_utf8(bytes.to_vec()).map_err(|_| BytecodeFileError::InvalidUtf8(start))
+  }
+
+  /**
+    Reads a function and returns it with the number of upvalues it uses.
+  */
+  fn function(&mut self, depth: usize) -> BytecodeFileResult<(FunctionPrototype, usize)> {
+    if depth > MAX_FUNCTION_DEPTH {
+      return Err(BytecodeFileError::FunctionTooDeep);
+    }
+
+    let name = self.string()?;
+    let arity = self.varint()?;
+
+    let length = self.varint()?;
+    let code = self.take(length)?.to_vec();
+
+    let runs = self.varint()?;
+    let mut lines: Vec<usize> = Vec::with_capacity(code.len());
+
+    for _ in 0..runs {
+      let line = self.varint()?;
+      let run = self.varint()?;
+
+      if lines.len() + run > code.len() {
+        return Err(BytecodeFileError::LineTableMismatch(
+          lines.len().saturating_add(run),
+          code.len(),
+        ));
+      }
+
+      lines.extend(std::iter::repeat_n(line, run));
+    }
+
+    if lines.len() != code.len() {
+      return Err(BytecodeFileError::LineTableMismatch(lines.len(), code.len()));
+    }
+
+    let count = self.varint()?;
+    let mut constants: Vec<Value> = vec![];
+    let mut upvalues: Vec<usize> = vec![];
+
+    for _ in 0..count {
+      let offset = self.offset;
+
+      let constant = match self.byte()? {
+        TAG_INT => Value::Int(i64::from_le_bytes(self.array::<8>()?)),
+        TAG_FLOAT => Value::Float(f64::from_le_bytes(self.array::<8>()?)),
+        TAG_STRING => Value::String(self.string()?),
+        TAG_FUNCTION => {
+          let (function, count) = self.function(depth + 1)?;
+          upvalues.resize(constants.len(), 0);
+          upvalues.push(count);
+
+          Value::Function(Rc::new(function))
+        }
+        TAG_NATIVE => Value::Native(NativeDeclaration::new(
+          self.string()?,
+          self.string()?,
+          self.varint()?,
+        )),
+        TAG_VARIANT => Value::Variant(VariantDeclaration::new(
+          self.string()?,
+          self.string()?,
+          self.varint()?,
+        )),
+        tag => return Err(BytecodeFileError::InvalidConstantTag(tag, offset)),
+      };
+
+      constants.push(constant);
+    }
+
+    let mut bytecode = Bytecode::new();
+    bytecode.count = code.len();
+    bytecode.capacity = code.len();
+    bytecode.code = code;
+    bytecode.lines = lines;
+    bytecode.constants = constants;
+
+    upvalues.resize(bytecode.constants.len(), 0);
+    let count = validate(&bytecode, arity, &upvalues)?;
+
+    Ok((FunctionPrototype::new(name, arity, bytecode), count))
+  }
+}
+
+fn compress_lines(lines: &[usize]) -> Vec<(usize, usize)> {
+  let mut runs: Vec<(usize, usize)> = vec![];
+
+  for line in lines {
+    match runs.last_mut() {
+      Some((last, length)) if last == line => *length += 1,
+      _ => runs.push((*line, 1)),
+    }
+  }
+
+  runs
+}
+
+/**
+  Checks that every instruction is known, has all its operands, only refers
+  to constants of the right kind and only jumps to the start of an
+  instruction. The stack height is then followed along every path, so no
+  instruction reads more values than its frame holds or a local slot outside
+  of it, and every closure captures the upvalues its function uses.
+
+  `upvalues` holds the number of upvalues used by each function constant,
+  the result is the number of upvalues used by this code.
+*/
+fn validate(bytecode: &Bytecode, arity: usize, upvalues: &[usize]) -> BytecodeFileResult<usize> {
+  let code = &bytecode.code;
+  let mut instructions: Vec<(usize, OpCode)> = vec![];
+  let mut starts: Vec<Option<usize>> = vec![None; code.len() + 1];
+  let mut offset = 0;
+
+  while offset < code.len() {
+    let byte = code[offset];
+    let op = OpCode::from_byte(byte).ok_or(BytecodeFileError::InvalidOpCode(byte, offset))?;
+    let end = offset + 1 + op.operand_width();
+
+    if end > code.len() {
+      return Err(BytecodeFileError::TruncatedInstruction(op, offset));
+    }
+
+    if op.has_constant_operand() {
+      let index = bytecode.read_operand(offset + 1, op.operand_width());
+
+      let valid = matches!(
+        (op.short_form(), bytecode.constants.get(index)),
+        (OpCode::ConstantInt, Some(Value::Int(_)))
+          | (OpCode::ConstantFloat, Some(Value::Float(_)))
+          | (OpCode::Function, Some(Value::Function(_)))
+          | (OpCode::Native, Some(Value::Native(_)))
+          | (
+            OpCode::Variant | OpCode::CheckVariant,
+            Some(Value::Variant(_))
+          )
+          | (
+            OpCode::ConstantString
+              | OpCode::DefineGlobal
+              | OpCode::GetGlobal
+              | OpCode::SetGlobal
+              | OpCode::Class
+              | OpCode::GetProperty
+              | OpCode::SetProperty
+              | OpCode::Method
+              | OpCode::StaticMethod,
+            Some(Value::String(_))
+          )
+      );
+
+      if !valid {
+        return Err(BytecodeFileError::InvalidConstantIndex(op, index, offset));
+      }
+    }
+
+    starts[offset] = Some(instructions.len());
+    instructions.push((offset, op));
+    offset = end;
+  }
+
+  starts[code.len()] = Some(instructions.len());
+
+  let mut used = 0;
+
+  for (i, (offset, op)) in instructions.iter().enumerate() {
+    match op {
+      OpCode::Jump | OpCode::JumpIfFalse | OpCode::Loop
+        if jump_target(bytecode, *offset, *op)
+          .and_then(|x| starts[x])
+          .is_none() =>
+      {
+        return Err(BytecodeFileError::InvalidJumpTarget(*op, *offset));
+      }
+      OpCode::JumpTable
+        if jump_table_targets(bytecode, *offset)
+          .any(|target| starts.get(target).copied().flatten().is_none()) =>
+      {
+        return Err(BytecodeFileError::InvalidJumpTarget(*op, *offset));
+      }
+      OpCode::GetUpvalue | OpCode::SetUpvalue | OpCode::CaptureUpvalue => {
+        used = used.max(code[offset + 1] as usize + 1);
+      }
+      OpCode::Function | OpCode::FunctionLong => {
+        let index = bytecode.read_operand(offset + 1, op.operand_width());
+        let captured = instructions[i + 1..]
+          .iter()
+          .take_while(|(_, op)| matches!(op, OpCode::CaptureLocal | OpCode::CaptureUpvalue))
+          .count();
+
+        if let Some(Value::Function(function)) = bytecode.constants.get(index) {
+          if upvalues[index] > captured {
+            return Err(BytecodeFileError::UncapturedUpvalues(
+              function.name.clone(),
+              upvalues[index],
+              captured,
+            ));
+          }
+        }
+      }
+      _ => (),
+    }
+  }
+
+  check_stack(bytecode, arity, &instructions, &starts)?;
+
+  Ok(used)
+}
+
+/**
+  Offset an instruction jumps to, `None` when it is outside of the code.
+*/
+fn jump_target(bytecode: &Bytecode, offset: usize, op: OpCode) -> Option<usize> {
+  let end = offset + 3;
+  let jump = bytecode.read_operand(offset + 1, 2);
+
+  if op == OpCode::Loop {
+    end.checked_sub(jump)
+  } else {
+    Some(end + jump).filter(|target| *target <= bytecode.code.len())
+  }
+}
+
+/**
+  Offsets a `JumpTable` can jump to, the `Jump` of every entry of the table
+  and the one taken by the values outside of it.
+*/
+fn jump_table_targets(bytecode: &Bytecode, offset: usize) -> impl Iterator<Item = usize> {
+  let size = bytecode.read_operand(offset + 1, 2);
+
+  (0..=size).map(move |entry| offset + 3 + entry * 3)
+}
+
+/**
+  Follows the stack height of the frame through every path of the code, the
+  frame starts with the called function and its arguments.
+*/
+fn check_stack(
+  bytecode: &Bytecode,
+  arity: usize,
+  instructions: &[(usize, OpCode)],
+  starts: &[Option<usize>],
+) -> BytecodeFileResult<()> {
+  let code = &bytecode.code;
+  let mut heights: Vec<Option<usize>> = vec![None; instructions.len() + 1];
+  let mut pending: Vec<usize> = vec![0];
+  heights[0] = Some(1 + arity);
+
+  while let Some(i) = pending.pop() {
+    if i == instructions.len() {
+      continue;
+    }
+
+    let (offset, op) = instructions[i];
+    let height = heights[i].unwrap_or_default();
+
+    let (pops, pushes) = match op {
+      OpCode::ConstantInt
+      | OpCode::ConstantFloat
+      | OpCode::ConstantString
+      | OpCode::Function
+      | OpCode::ConstantIntLong
+      | OpCode::ConstantFloatLong
+      | OpCode::ConstantStringLong
+      | OpCode::FunctionLong
+      | OpCode::Native
+      | OpCode::NativeLong
+      | OpCode::Null
+      | OpCode::True
+      | OpCode::False
+      | OpCode::GetGlobal
+      | OpCode::GetGlobalLong
+      | OpCode::GetLocal
+      | OpCode::GetUpvalue
+      | OpCode::Class
+      | OpCode::ClassLong => (0, 1),
+      OpCode::Pop
+      | OpCode::DefineGlobal
+      | OpCode::DefineGlobalLong
+      | OpCode::Print
+      | OpCode::CloseUpvalue
+      | OpCode::Return => (1, 0),
+      OpCode::Add
+      | OpCode::Subtract
+      | OpCode::Multiply
+      | OpCode::Divide
+      | OpCode::Modulo
+      | OpCode::Equal
+      | OpCode::NotEqual
+      | OpCode::Greater
+      | OpCode::GreaterEqual
+      | OpCode::Less
+      | OpCode::LessEqual
+      | OpCode::Concatenate
+      | OpCode::Index
+      | OpCode::SetProperty
+      | OpCode::SetPropertyLong
+      | OpCode::Method
+      | OpCode::MethodLong
+      | OpCode::StaticMethod
+      | OpCode::StaticMethodLong => (2, 1),
+      OpCode::Negate
+      | OpCode::Not
+      | OpCode::Length
+      | OpCode::ToString
+      | OpCode::SetGlobal
+      | OpCode::SetGlobalLong
+      | OpCode::SetLocal
+      | OpCode::SetUpvalue
+      | OpCode::CaptureLocal
+      | OpCode::CaptureUpvalue
+      | OpCode::JumpIfFalse
+      | OpCode::CheckVariant
+      | OpCode::CheckVariantLong
+      | OpCode::GetVariantValue
+      | OpCode::GetProperty
+      | OpCode::GetPropertyLong => (1, 1),
+      OpCode::SetIndex => (3, 1),
+      OpCode::JumpTable => (2, 0),
+      OpCode::Jump | OpCode::Loop => (0, 0),
+      OpCode::Call => (code[offset + 1] as usize + 1, 1),
+      OpCode::Array => (bytecode.read_operand(offset + 1, 2), 1),
+      OpCode::Variant | OpCode::VariantLong => {
+        match &bytecode.constants[bytecode.read_operand(offset + 1, op.operand_width())] {
+          Value::Variant(variant) => (variant.arity, 1),
+          _ => (0, 1),
+        }
+      }
+    };
+
+    if height < pops {
+      return Err(BytecodeFileError::StackUnderflow(op, offset));
+    }
+
+    if matches!(
+      op,
+      OpCode::GetLocal | OpCode::SetLocal | OpCode::CaptureLocal
+    ) {
+      let slot = code[offset + 1] as usize;
+
+      if slot >= height {
+        return Err(BytecodeFileError::InvalidLocalSlot(op, slot, offset));
+      }
+    }
+
+    let height = height - pops + pushes;
+
+    let next = match op {
+      OpCode::Return => vec![],
+      OpCode::Jump | OpCode::Loop => vec![jump_target(bytecode, offset, op)],
+      OpCode::JumpIfFalse => vec![Some(offset + 3), jump_target(bytecode, offset, op)],
+      OpCode::JumpTable => jump_table_targets(bytecode, offset).map(Some).collect(),
+      _ => vec![Some(offset + 1 + op.operand_width())],
+    };
+
+    for target in next.into_iter().flatten() {
+      let Some(j) = starts.get(target).copied().flatten() else {
+        return Err(BytecodeFileError::InvalidJumpTarget(op, offset));
+      };
+
+      match heights[j] {
+        None => {
+          heights[j] = Some(height);
+          pending.push(j);
+        }
+        Some(other) if other != height && j < instructions.len() => {
+          return Err(BytecodeFileError::StackMismatch(target));
+        }
+        Some(_) => (),
+      }
+    }
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn sample() -> Bytecode {
+    let mut function = Bytecode::new();
+    function.write(OpCode::GetLocal as u8, 2);
+    function.write(1, 2);
+    function.write(OpCode::Return as u8, 3);
+
+    let mut bytecode = Bytecode::new();
+    let index = bytecode.add_constant(Value::Function(Rc::new(FunctionPrototype::new(
+      "identity".to_string(),
+      1,
+      function,
+    ))));
+    bytecode.write(OpCode::Function as u8, 1);
+    bytecode.write(index as u8, 1);
+
+    let index = bytecode.add_constant(Value::String("identity".to_string()));
+    bytecode.write(OpCode::DefineGlobal as u8, 1);
+    bytecode.write(index as u8, 1);
+
+    let index = bytecode.add_constant(Value::Native(NativeDeclaration::new(
+      "std:io".to_string(),
+      "println".to_string(),
+      1,
+    )));
+    bytecode.write(OpCode::Native as u8, 1);
+    bytecode.write(index as u8, 1);
+
+    let index = bytecode.add_constant(Value::String("println".to_string()));
+    bytecode.write(OpCode::DefineGlobal as u8, 1);
+    bytecode.write(index as u8, 1);
+
+    let index = bytecode.add_constant(Value::Float(1.5));
+    bytecode.write(OpCode::ConstantFloat as u8, 4);
+    bytecode.write(index as u8, 4);
+    bytecode.write(OpCode::Return as u8, 4);
+
+    bytecode
+  }
+
+  #[test]
+  fn test_round_trip() {
+    let mut file = BytecodeFile::new();
+    file.add_section("main.ign".to_string(), sample());
+
+    let bytes = file.to_bytes();
+    let loaded = BytecodeFile::from_bytes(&bytes).unwrap();
+    let bytecode = &loaded.section("main.ign").unwrap().bytecode;
+
+    assert_eq!(bytecode.code, sample().code);
+    assert_eq!(bytecode.lines, sample().lines);
+    assert_eq!(bytecode.constants.len(), 5);
+
+    match &bytecode.constants[0] {
+      Value::Function(function) => {
+        assert_eq!(function.name, "identity");
+        assert_eq!(function.arity, 1);
+        assert_eq!(function.bytecode.lines, vec![2, 2, 3]);
+      }
+      _ => panic!("Expected a function constant"),
+    }
+
+    match &bytecode.constants[2] {
+      Value::Native(native) => assert_eq!(
+        native,
+        &NativeDeclaration::new("std:io".to_string(), "println".to_string(), 1)
+      ),
+      _ => panic!("Expected a native constant"),
+    }
+  }
+
+  #[test]
+  fn test_rejects_invalid_files() {
+    let mut file = BytecodeFile::new();
+    file.add_section("main.ign".to_string(), sample());
+    let bytes = file.to_bytes();
+
+    let mut magic = bytes.clone();
+    magic[0] = b'X';
+    assert_eq!(
+      BytecodeFile::from_bytes(&magic).unwrap_err(),
+      BytecodeFileError::InvalidMagic
+    );
+
+    let mut version = bytes.clone();
+    version[4] = 99;
+    assert_eq!(
+      BytecodeFile::from_bytes(&version).unwrap_err(),
+      BytecodeFileError::UnsupportedVersion(99)
+    );
+
+    assert_eq!(
+      BytecodeFile::from_bytes(&bytes[..bytes.len() - 1]).unwrap_err(),
+      BytecodeFileError::UnexpectedEnd
+    );
+
+    let mut bytecode = Bytecode::new();
+    bytecode.write(OpCode::ConstantInt as u8, 1);
+    bytecode.write(7, 1);
+
+    let mut file = BytecodeFile::new();
+    file.add_section("main.ign".to_string(), bytecode);
+
+    assert_eq!(
+      BytecodeFile::from_bytes(&file.to_bytes()).unwrap_err(),
+      BytecodeFileError::InvalidConstantIndex(OpCode::ConstantInt, 7, 0)
+    );
+  }
+
+  fn load(bytecode: Bytecode) -> BytecodeFileResult<BytecodeFile> {
+    let mut file = BytecodeFile::new();
+    file.add_section("main.ign".to_string(), bytecode);
+
+    BytecodeFile::from_bytes(&file.to_bytes())
+  }
+
+  #[test]
+  fn test_rejects_invalid_code() {
+    let mut bytecode = Bytecode::new();
+    bytecode.write(OpCode::GetLocal as u8, 1);
+    bytecode.write(200, 1);
+    bytecode.write(OpCode::Return as u8, 1);
+
+    assert_eq!(
+      load(bytecode).unwrap_err(),
+      BytecodeFileError::InvalidLocalSlot(OpCode::GetLocal, 200, 0)
+    );
+
+    let mut bytecode = Bytecode::new();
+    bytecode.write(OpCode::Jump as u8, 1);
+    bytecode.write(0, 1);
+    bytecode.write(1, 1);
+    bytecode.write(OpCode::GetLocal as u8, 1);
+    bytecode.write(0, 1);
+    bytecode.write(OpCode::Return as u8, 1);
+
+    assert_eq!(
+      load(bytecode).unwrap_err(),
+      BytecodeFileError::InvalidJumpTarget(OpCode::Jump, 0)
+    );
+
+    let mut bytecode = Bytecode::new();
+    bytecode.write(OpCode::Pop as u8, 1);
+    bytecode.write(OpCode::Pop as u8, 1);
+
+    assert_eq!(
+      load(bytecode).unwrap_err(),
+      BytecodeFileError::StackUnderflow(OpCode::Pop, 1)
+    );
+
+    let mut bytecode = Bytecode::new();
+    bytecode.write(OpCode::GetUpvalue as u8, 1);
+    bytecode.write(0, 1);
+    bytecode.write(OpCode::Return as u8, 1);
+
+    assert_eq!(
+      load(bytecode).unwrap_err(),
+      BytecodeFileError::UncapturedUpvalues("<script>".to_string(), 1, 0)
+    );
+  }
+}
@@ -1,6 +1,6 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt::Write};
 
-use crate::{Bytecode, OpCode};
+use crate::{Bytecode, OpCode, Value};
 
 pub struct BytecodeDebug {
   pub bytecodes: HashMap<String, Bytecode>,
@@ -12,32 +12,149 @@ impl BytecodeDebug {
   }
 
   pub fn print_bytecode(&self) {
-    for (file_name, code) in self.bytecodes.iter() {
-      println!("Bytecode for {}", file_name);
-
-      let mut i = 0;
-
-      for byte in code.code.iter() {
-        let op = OpCode::from(byte.clone());
-        match op {
-          OpCode::ConstantInt | OpCode::ConstantFloat => {
-            let constant = code.constants[i];
-            println!("{:05}\t{:?}\t{}", i, op, constant);
-          }
-          OpCode::Add => todo!(),
-          OpCode::Subtract => todo!(),
-          OpCode::Multiply => todo!(),
-          OpCode::Divide => todo!(),
-          OpCode::Negate => todo!(),
-          OpCode::Return => todo!(),
-        };
-
-        i += 1;
-
-        if i == code.count {
-          break;
-        }
-      }
+    let mut modules = self.bytecodes.keys().collect::<Vec<&String>>();
+    modules.sort();
+
+    for module in modules {
+      print!("{}", disassemble(&self.bytecodes[module], module));
+    }
+  }
+}
+
+/**
+  Disassembles `bytecode` and every function declared in its constant pool.
+
+  Each line shows the byte offset, the source line (`|` when it is the same as
+  the previous instruction), the opcode and its decoded operands.
+*/
+pub fn disassemble(bytecode: &Bytecode, name: &str) -> String {
+  let mut output = String::new();
+  writeln!(output, "== {} ==", name).unwrap();
+
+  let mut offset = 0;
+
+  while offset < bytecode.code.len() {
+    offset = disassemble_instruction(bytecode, offset, &mut output);
+  }
+
+  for constant in &bytecode.constants {
+    if let Value::Function(function) = constant {
+      output.push('\n');
+      output.push_str(&disassemble(&function.bytecode, &function.name));
+    }
+  }
+
+  output
+}
+
+/**
+  Writes the instruction at `offset` to `output` and returns the offset of the
+  next instruction.
+*/
+pub fn disassemble_instruction(bytecode: &Bytecode, offset: usize, output: &mut String) -> usize {
+  write!(output, "{:04} ", offset).unwrap();
+
+  let line = bytecode.lines.get(offset).copied().unwrap_or(0);
+
+  if offset > 0 && bytecode.lines.get(offset - 1) == Some(&line) {
+    write!(output, "   | ").unwrap();
+  } else {
+    write!(output, "{:4} ", line).unwrap();
+  }
+
+  let byte = bytecode.code[offset];
+
+  let op = match OpCode::from_byte(byte) {
+    Some(op) => op,
+    None => {
+      writeln!(output, "Unknown opcode {}", byte).unwrap();
+      return offset + 1;
     }
+  };
+
+  let name = format!("{:?}", op);
+  let next = offset + 1 + op.operand_width();
+
+  if next > bytecode.code.len() {
+    writeln!(output, "{:<18} <missing operands>", name).unwrap();
+    return bytecode.code.len();
+  }
+
+  match op {
+    op if op.has_constant_operand() => {
+      let index = bytecode.read_operand(offset + 1, op.operand_width());
+
+      let constant = match bytecode.constants.get(index) {
+        Some(constant) => constant.to_string(),
+        None => "<invalid>".to_string(),
+      };
+
+      writeln!(output, "{:<18} {:4} '{}'", name, index, constant).unwrap();
+    }
+    OpCode::GetLocal
+    | OpCode::SetLocal
+    | OpCode::Call
+    | OpCode::GetVariantValue
+    | OpCode::CaptureLocal
+    | OpCode::CaptureUpvalue
+    | OpCode::GetUpvalue
+    | OpCode::SetUpvalue => {
+      writeln!(output, "{:<18} {:4}", name, bytecode.code[offset + 1]).unwrap();
+    }
+    OpCode::Jump | OpCode::JumpIfFalse | OpCode::Loop => {
+      let jump = bytecode.read_operand(offset + 1, 2);
+
+      let target = if op == OpCode::Loop {
+        next as isize - jump as isize
+      } else {
+        (next + jump) as isize
+      };
+
+      writeln!(output, "{:<18} {:4} -> {}", name, offset, target).unwrap();
+    }
+    OpCode::Array | OpCode::JumpTable => {
+      let count = bytecode.read_operand(offset + 1, 2);
+      writeln!(output, "{:<18} {:4}", name, count).unwrap();
+    }
+    _ => {
+      writeln!(output, "{}", name).unwrap();
+    }
+  }
+
+  next
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_disassemble() {
+    let mut bytecode = Bytecode::new();
+    let index = bytecode.add_constant(Value::Int(1));
+    bytecode.write(OpCode::ConstantInt as u8, 1);
+    bytecode.write(index as u8, 1);
+    bytecode.write(OpCode::JumpIfFalse as u8, 1);
+    bytecode.write(0, 1);
+    bytecode.write(1, 1);
+    bytecode.write(OpCode::Pop as u8, 2);
+    bytecode.write(OpCode::ConstantStringLong as u8, 3);
+    bytecode.write(0, 3);
+    bytecode.write(0, 3);
+    bytecode.write(1, 3);
+    bytecode.write(OpCode::Return as u8, 3);
+    bytecode.add_constant(Value::String("ignis".to_string()));
+
+    let output = disassemble(&bytecode, "main.ign");
+
+    assert_eq!(
+      output,
+      "== main.ign ==\n\
+       0000    1 ConstantInt           0 '1'\n\
+       0002    | JumpIfFalse           2 -> 6\n\
+       0005    2 Pop\n\
+       0006    3 ConstantStringLong    1 'ignis'\n\
+       0010    | Return\n"
+    );
   }
 }
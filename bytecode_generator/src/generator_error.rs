@@ -0,0 +1,74 @@
+use std::fmt::Display;
+
+use diagnostic::{Diagnostic, DiagnosticLevel};
+use lexer::text_span::TextSpan;
+
+/**
+  A limit of the bytecode format exceeded by a valid program.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub enum GeneratorErrorKind {
+  TooManyLocals,
+  TooManyUpvalues,
+  TooManyConstants,
+  TooManyElements,
+  JumpTooLarge,
+  LoopTooLarge,
+}
+
+impl Display for GeneratorErrorKind {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      GeneratorErrorKind::TooManyLocals => write!(f, "Too many local variables in function"),
+      GeneratorErrorKind::TooManyUpvalues => write!(f, "Too many closure variables in function"),
+      GeneratorErrorKind::TooManyConstants => write!(f, "Too many constants in one module"),
+      GeneratorErrorKind::TooManyElements => {
+        write!(f, "Can't have more than 65535 elements in an array literal")
+      }
+      GeneratorErrorKind::JumpTooLarge => write!(f, "Too much code to jump over"),
+      GeneratorErrorKind::LoopTooLarge => write!(f, "Loop body too large"),
+    }
+  }
+}
+
+/**
+  An error found while lowering the IR of `module_path`, `line` is the line
+  of the instruction being generated.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratorError {
+  pub kind: GeneratorErrorKind,
+  pub line: usize,
+  pub module_path: String,
+}
+
+impl GeneratorError {
+  pub fn new(kind: GeneratorErrorKind, line: usize, module_path: String) -> Self {
+    Self {
+      kind,
+      line,
+      module_path,
+    }
+  }
+
+  pub fn to_diagnostic(&self) -> Diagnostic {
+    let span = TextSpan::new(0, 0, self.line, String::new(), 0, self.module_path.clone());
+
+    Diagnostic::new(
+      DiagnosticLevel::Error,
+      Box::new(span),
+      Some(self.kind.to_string()),
+      Some(self.module_path.clone()),
+    )
+  }
+}
+
+impl Display for GeneratorError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(
+      f,
+      "Compile error: {}\n[line {}] in {}",
+      self.kind, self.line, self.module_path
+    )
+  }
+}
@@ -1,10 +1,30 @@
-use std::{collections::HashMap, fmt::Display};
+use std::{collections::HashMap, fmt::Display, rc::Rc};
 
+pub mod bytecode_file;
 pub mod debug;
+pub mod generator_error;
 
-use analyzer::{ir::instruction::IRInstruction, analyzer_value::AnalyzerValue};
+use bytecode_file::BytecodeFile;
+use code_generator::{CodeGenerator, CodeResult};
+use diagnostic::Diagnostic;
+use generator_error::{GeneratorError, GeneratorErrorKind};
+use analyzer::{
+  ir::{
+    instruction::{
+      IRInstruction, function::IRFunction, call::IRCall, variable::IRVariable, ir_for_in::IRForIn,
+      ir_if::IRIf, ir_while::IRWhile, logical::IRLogical, ternary::IRTernary, block::IRBlock,
+      class::IRClass, ir_method_call::IRMethodCall, ir_enum_value::IREnumValue,
+      ir_match::{IRMatch, IRMatchPattern},
+      ir_switch::IRSwitch,
+      ir_lambda::IRLambda,
+      ir_template_literal::IRTemplateLiteral,
+    },
+    instruction_type::IRInstructionType,
+  },
+  analyzer_value::AnalyzerValue,
+};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum OpCode {
   ConstantInt = 0,
   ConstantFloat,
@@ -14,11 +34,74 @@ pub enum OpCode {
   Divide,
   Negate,
   Return,
+  ConstantString,
+  Null,
+  True,
+  False,
+  Pop,
+  Not,
+  Modulo,
+  Equal,
+  NotEqual,
+  Greater,
+  GreaterEqual,
+  Less,
+  LessEqual,
+  Concatenate,
+  DefineGlobal,
+  GetGlobal,
+  SetGlobal,
+  GetLocal,
+  SetLocal,
+  Jump,
+  JumpIfFalse,
+  Loop,
+  Call,
+  Function,
+  Array,
+  Index,
+  Length,
+  Print,
+  ToString,
+  ConstantIntLong,
+  ConstantFloatLong,
+  ConstantStringLong,
+  FunctionLong,
+  DefineGlobalLong,
+  GetGlobalLong,
+  SetGlobalLong,
+  Native,
+  NativeLong,
+  Class,
+  ClassLong,
+  GetProperty,
+  GetPropertyLong,
+  SetProperty,
+  SetPropertyLong,
+  Method,
+  MethodLong,
+  StaticMethod,
+  StaticMethodLong,
+  Variant,
+  VariantLong,
+  CheckVariant,
+  CheckVariantLong,
+  GetVariantValue,
+  CaptureLocal,
+  CaptureUpvalue,
+  GetUpvalue,
+  SetUpvalue,
+  CloseUpvalue,
+  SetIndex,
+  JumpTable,
 }
 
-impl From<u8> for OpCode {
-  fn from(byte: u8) -> Self {
-    match byte {
+impl OpCode {
+  /**
+    Decodes an opcode, `None` when `byte` is not a known instruction.
+  */
+  pub fn from_byte(byte: u8) -> Option<Self> {
+    let op = match byte {
       0 => OpCode::ConstantInt,
       1 => OpCode::ConstantFloat,
       2 => OpCode::Add,
@@ -27,15 +110,266 @@ impl From<u8> for OpCode {
       5 => OpCode::Divide,
       6 => OpCode::Negate,
       7 => OpCode::Return,
-      _ => panic!("Invalid OpCode {}", byte),
+      8 => OpCode::ConstantString,
+      9 => OpCode::Null,
+      10 => OpCode::True,
+      11 => OpCode::False,
+      12 => OpCode::Pop,
+      13 => OpCode::Not,
+      14 => OpCode::Modulo,
+      15 => OpCode::Equal,
+      16 => OpCode::NotEqual,
+      17 => OpCode::Greater,
+      18 => OpCode::GreaterEqual,
+      19 => OpCode::Less,
+      20 => OpCode::LessEqual,
+      21 => OpCode::Concatenate,
+      22 => OpCode::DefineGlobal,
+      23 => OpCode::GetGlobal,
+      24 => OpCode::SetGlobal,
+      25 => OpCode::GetLocal,
+      26 => OpCode::SetLocal,
+      27 => OpCode::Jump,
+      28 => OpCode::JumpIfFalse,
+      29 => OpCode::Loop,
+      30 => OpCode::Call,
+      31 => OpCode::Function,
+      32 => OpCode::Array,
+      33 => OpCode::Index,
+      34 => OpCode::Length,
+      35 => OpCode::Print,
+      36 => OpCode::ToString,
+      37 => OpCode::ConstantIntLong,
+      38 => OpCode::ConstantFloatLong,
+      39 => OpCode::ConstantStringLong,
+      40 => OpCode::FunctionLong,
+      41 => OpCode::DefineGlobalLong,
+      42 => OpCode::GetGlobalLong,
+      43 => OpCode::SetGlobalLong,
+      44 => OpCode::Native,
+      45 => OpCode::NativeLong,
+      46 => OpCode::Class,
+      47 => OpCode::ClassLong,
+      48 => OpCode::GetProperty,
+      49 => OpCode::GetPropertyLong,
+      50 => OpCode::SetProperty,
+      51 => OpCode::SetPropertyLong,
+      52 => OpCode::Method,
+      53 => OpCode::MethodLong,
+      54 => OpCode::StaticMethod,
+      55 => OpCode::StaticMethodLong,
+      56 => OpCode::Variant,
+      57 => OpCode::VariantLong,
+      58 => OpCode::CheckVariant,
+      59 => OpCode::CheckVariantLong,
+      60 => OpCode::GetVariantValue,
+      61 => OpCode::CaptureLocal,
+      62 => OpCode::CaptureUpvalue,
+      63 => OpCode::GetUpvalue,
+      64 => OpCode::SetUpvalue,
+      65 => OpCode::CloseUpvalue,
+      66 => OpCode::SetIndex,
+      67 => OpCode::JumpTable,
+      _ => return None,
+    };
+
+    Some(op)
+  }
+
+  /**
+    Number of operand bytes that follow the opcode.
+  */
+  pub fn operand_width(&self) -> usize {
+    match self {
+      OpCode::ConstantInt
+      | OpCode::ConstantFloat
+      | OpCode::ConstantString
+      | OpCode::Function
+      | OpCode::DefineGlobal
+      | OpCode::GetGlobal
+      | OpCode::SetGlobal
+      | OpCode::Native
+      | OpCode::Class
+      | OpCode::GetProperty
+      | OpCode::SetProperty
+      | OpCode::Method
+      | OpCode::StaticMethod
+      | OpCode::Variant
+      | OpCode::CheckVariant
+      | OpCode::GetVariantValue
+      | OpCode::GetLocal
+      | OpCode::SetLocal
+      | OpCode::CaptureLocal
+      | OpCode::CaptureUpvalue
+      | OpCode::GetUpvalue
+      | OpCode::SetUpvalue
+      | OpCode::Call => 1,
+      OpCode::Jump | OpCode::JumpIfFalse | OpCode::Loop | OpCode::Array | OpCode::JumpTable => 2,
+      OpCode::ConstantIntLong
+      | OpCode::ConstantFloatLong
+      | OpCode::ConstantStringLong
+      | OpCode::FunctionLong
+      | OpCode::DefineGlobalLong
+      | OpCode::GetGlobalLong
+      | OpCode::SetGlobalLong
+      | OpCode::NativeLong
+      | OpCode::ClassLong
+      | OpCode::GetPropertyLong
+      | OpCode::SetPropertyLong
+      | OpCode::MethodLong
+      | OpCode::StaticMethodLong
+      | OpCode::VariantLong
+      | OpCode::CheckVariantLong => 3,
+      _ => 0,
+    }
+  }
+
+  /**
+    Variant of an instruction that takes a 3-byte constant index, used once
+    the constant pool grows past 256 entries.
+  */
+  pub fn long_form(&self) -> Option<OpCode> {
+    match self {
+      OpCode::ConstantInt => Some(OpCode::ConstantIntLong),
+      OpCode::ConstantFloat => Some(OpCode::ConstantFloatLong),
+      OpCode::ConstantString => Some(OpCode::ConstantStringLong),
+      OpCode::Function => Some(OpCode::FunctionLong),
+      OpCode::DefineGlobal => Some(OpCode::DefineGlobalLong),
+      OpCode::GetGlobal => Some(OpCode::GetGlobalLong),
+      OpCode::SetGlobal => Some(OpCode::SetGlobalLong),
+      OpCode::Native => Some(OpCode::NativeLong),
+      OpCode::Class => Some(OpCode::ClassLong),
+      OpCode::GetProperty => Some(OpCode::GetPropertyLong),
+      OpCode::SetProperty => Some(OpCode::SetPropertyLong),
+      OpCode::Method => Some(OpCode::MethodLong),
+      OpCode::StaticMethod => Some(OpCode::StaticMethodLong),
+      OpCode::Variant => Some(OpCode::VariantLong),
+      OpCode::CheckVariant => Some(OpCode::CheckVariantLong),
+      _ => None,
+    }
+  }
+
+  /**
+    Instruction with the short operand form, `self` for every instruction
+    that has no long form.
+  */
+  pub fn short_form(&self) -> OpCode {
+    match self {
+      OpCode::ConstantIntLong => OpCode::ConstantInt,
+      OpCode::ConstantFloatLong => OpCode::ConstantFloat,
+      OpCode::ConstantStringLong => OpCode::ConstantString,
+      OpCode::FunctionLong => OpCode::Function,
+      OpCode::DefineGlobalLong => OpCode::DefineGlobal,
+      OpCode::GetGlobalLong => OpCode::GetGlobal,
+      OpCode::SetGlobalLong => OpCode::SetGlobal,
+      OpCode::NativeLong => OpCode::Native,
+      OpCode::ClassLong => OpCode::Class,
+      OpCode::GetPropertyLong => OpCode::GetProperty,
+      OpCode::SetPropertyLong => OpCode::SetProperty,
+      OpCode::MethodLong => OpCode::Method,
+      OpCode::StaticMethodLong => OpCode::StaticMethod,
+      OpCode::VariantLong => OpCode::Variant,
+      OpCode::CheckVariantLong => OpCode::CheckVariant,
+      _ => *self,
+    }
+  }
+
+  /**
+    Whether the operand of the instruction is an index in the constant pool.
+  */
+  pub fn has_constant_operand(&self) -> bool {
+    matches!(
+      self.short_form(),
+      OpCode::ConstantInt
+        | OpCode::ConstantFloat
+        | OpCode::ConstantString
+        | OpCode::Function
+        | OpCode::DefineGlobal
+        | OpCode::GetGlobal
+        | OpCode::SetGlobal
+        | OpCode::Native
+        | OpCode::Class
+        | OpCode::GetProperty
+        | OpCode::SetProperty
+        | OpCode::Method
+        | OpCode::StaticMethod
+        | OpCode::Variant
+        | OpCode::CheckVariant
+    )
+  }
+}
+
+/**
+  A function compiled to its own `Bytecode`.
+  It is stored in the constant pool of the code that declares it.
+*/
+#[derive(Debug, Clone)]
+pub struct FunctionPrototype {
+  pub name: String,
+  pub arity: usize,
+  pub bytecode: Bytecode,
+}
+
+impl FunctionPrototype {
+  pub fn new(name: String, arity: usize, bytecode: Bytecode) -> Self {
+    Self {
+      name,
+      arity,
+      bytecode,
+    }
+  }
+}
+
+/**
+  A function declared with `@extern`, its code is provided by the host and
+  looked up by `module` and `name` when the declaration runs.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct NativeDeclaration {
+  pub module: String,
+  pub name: String,
+  pub arity: usize,
+}
+
+impl NativeDeclaration {
+  pub fn new(module: String, name: String, arity: usize) -> Self {
+    Self {
+      module,
+      name,
+      arity,
+    }
+  }
+}
+
+/**
+  A variant of an enum with a payload of `arity` values, the instruction
+  that uses it takes the payload from the stack.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariantDeclaration {
+  pub enum_name: String,
+  pub name: String,
+  pub arity: usize,
+}
+
+impl VariantDeclaration {
+  pub fn new(enum_name: String, name: String, arity: usize) -> Self {
+    Self {
+      enum_name,
+      name,
+      arity,
     }
   }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub enum Value {
   Int(i64),
   Float(f64),
+  String(String),
+  Function(Rc<FunctionPrototype>),
+  Native(NativeDeclaration),
+  Variant(VariantDeclaration),
 }
 
 impl Display for Value {
@@ -43,16 +377,56 @@ impl Display for Value {
     match self {
       Value::Int(int) => write!(f, "{}", int),
       Value::Float(float) => write!(f, "{}", float),
+      Value::String(string) => write!(f, "{}", string),
+      Value::Function(function) => write!(f, "<fn {}>", function.name),
+      Value::Native(native) => write!(f, "<native {}.{}>", native.module, native.name),
+      Value::Variant(variant) => write!(f, "<variant {}.{}>", variant.enum_name, variant.name),
+    }
+  }
+}
+
+/**
+  Largest constant pool addressable by the 3-byte operand of the long
+  instructions.
+*/
+pub const MAX_CONSTANTS: usize = 1 << 24;
+
+/**
+  Number of `int` cases from which a `switch` is dispatched with a jump
+  table, fewer cases are compared one by one.
+*/
+const JUMP_TABLE_MIN_CASES: usize = 3;
+
+/**
+  Hashable form of the constants that can be shared inside a pool.
+  Floats are compared by their bits so `0.0` and `-0.0` stay different.
+*/
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum ConstantKey {
+  Int(i64),
+  Float(u64),
+  String(String),
+}
+
+impl ConstantKey {
+  fn from_value(value: &Value) -> Option<Self> {
+    match value {
+      Value::Int(int) => Some(ConstantKey::Int(*int)),
+      Value::Float(float) => Some(ConstantKey::Float(float.to_bits())),
+      Value::String(string) => Some(ConstantKey::String(string.clone())),
+      Value::Function(_) | Value::Native(_) | Value::Variant(_) => None,
     }
   }
 }
 
+#[derive(Debug, Clone)]
 pub struct Bytecode {
   pub code: Vec<u8>,
   pub capacity: usize,
   pub count: usize,
   pub lines: Vec<usize>,
   pub constants: Vec<Value>,
+  interned: HashMap<ConstantKey, usize>,
 }
 
 impl Bytecode {
@@ -63,23 +437,156 @@ impl Bytecode {
       count: 0,
       lines: Vec::new(),
       constants: Vec::new(),
+      interned: HashMap::new(),
     }
   }
 
   pub fn write(&mut self, byte: u8, line: usize) {
     self.code.push(byte);
     self.lines.push(line);
+
+    self.count = self.code.len();
+    self.capacity = self.code.capacity();
   }
 
+  /**
+    Adds `value` to the constant pool and returns its index.
+    Identical ints, floats and strings share the same entry.
+  */
   pub fn add_constant(&mut self, value: Value) -> usize {
+    let key = ConstantKey::from_value(&value);
+
+    if let Some(index) = key.as_ref().and_then(|key| self.interned.get(key)) {
+      return *index;
+    }
+
     self.constants.push(value);
-    self.constants.len() - 1
+    let index = self.constants.len() - 1;
+
+    if let Some(key) = key {
+      self.interned.insert(key, index);
+    }
+
+    index
+  }
+
+  /**
+    Reads the big endian operand of `width` bytes that starts at `offset`.
+  */
+  pub fn read_operand(&self, offset: usize, width: usize) -> usize {
+    self.code[offset..offset + width]
+      .iter()
+      .fold(0, |operand, byte| (operand << 8) | *byte as usize)
+  }
+}
+
+impl Default for Bytecode {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+/*
+ * - is_captured: a lambda uses the local, it is moved to the heap when it
+ *   goes out of scope instead of being popped.
+*/
+struct Local {
+  name: String,
+  depth: usize,
+  is_captured: bool,
+}
+
+/*
+ * A variable of an enclosing function used by a lambda.
+ *
+ * - index: slot of the local in the enclosing function when `is_local`,
+ *   otherwise index of an upvalue of the enclosing function.
+*/
+struct Upvalue {
+  name: String,
+  index: u8,
+  is_local: bool,
+}
+
+/*
+ * What the slot 0 of the function being generated holds, the function
+ * itself or the instance in methods and constructors.
+*/
+#[derive(Clone, Copy, PartialEq)]
+enum FunctionKind {
+  Function,
+  Method,
+  Initializer,
+}
+
+/*
+ * Jumps that are waiting for the end of a loop to be patched.
+ *
+ * - scope_depth: depth of the scope that encloses the loop, every local
+ *   declared deeper than it has to be popped before leaving the loop.
+ * - is_switch: a `switch` is only a target for `break`, `continue` goes to
+ *   the loop that encloses it.
+*/
+struct LoopContext {
+  scope_depth: usize,
+  breaks: Vec<usize>,
+  continues: Vec<usize>,
+  is_switch: bool,
+}
+
+/*
+ * State of the function being generated. The top level code of every module
+ * is generated as a function without parameters.
+ *
+ * The slot 0 of every function is reserved for the function itself, or for
+ * the instance in methods and constructors.
+ *
+ * - is_initializer: constructors return the instance instead of `null`.
+ * - upvalues: variables of the enclosing functions captured by a lambda.
+*/
+struct FunctionContext {
+  bytecode: Bytecode,
+  locals: Vec<Local>,
+  upvalues: Vec<Upvalue>,
+  scope_depth: usize,
+  loops: Vec<LoopContext>,
+  is_initializer: bool,
+}
+
+impl FunctionContext {
+  fn new(kind: FunctionKind) -> Self {
+    let receiver = match kind {
+      FunctionKind::Function => "",
+      FunctionKind::Method | FunctionKind::Initializer => "this",
+    };
+
+    Self {
+      is_initializer: kind == FunctionKind::Initializer,
+      bytecode: Bytecode::new(),
+      locals: vec![Local {
+        name: receiver.to_string(),
+        depth: 0,
+        is_captured: false,
+      }],
+      upvalues: Vec::new(),
+      scope_depth: 0,
+      loops: Vec::new(),
+    }
   }
 }
 
 pub struct BytecodeGenerator {
   pub bytecodes: HashMap<String, Bytecode>,
   pub irs: HashMap<String, Vec<IRInstruction>>,
+  /// Return the value of the last top level expression instead of `null`,
+  /// used by the REPL to print it.
+  pub return_last_expression: bool,
+  /// Limits of the bytecode format exceeded by the program, the bytecode of
+  /// a module with errors can not be run.
+  pub errors: Vec<GeneratorError>,
+  functions: Vec<FunctionContext>,
+  module_path: String,
+  line: usize,
 }
 
 impl BytecodeGenerator {
@@ -87,43 +594,1543 @@ impl BytecodeGenerator {
     Self {
       bytecodes: HashMap::new(),
       irs: HashMap::new(),
+      return_last_expression: false,
+      errors: Vec::new(),
+      functions: Vec::new(),
+      module_path: String::new(),
+      line: 0,
     }
   }
 
-  pub fn generate(&mut self, irs: HashMap<String, Vec<IRInstruction>>) {
-    for (file_name, ir) in irs.into_iter() {
-      self.bytecodes.insert(file_name.clone(), Bytecode::new());
-      let bytecode = self.bytecodes.get_mut(file_name.as_str()).unwrap();
-
-      for code in ir {
-        if bytecode.capacity < bytecode.count + 1 {
-          bytecode.capacity = bytecode.count + 1;
-        }
-
-        match code {
-          IRInstruction::Literal(literal) => {
-            match literal.value {
-              AnalyzerValue::String(_) => todo!(),
-              AnalyzerValue::Int(int) => {
-                bytecode.write(OpCode::ConstantInt as u8, 123);
-                bytecode.add_constant(Value::Int(int));
-              }
-              AnalyzerValue::Float(float) => {
-                bytecode.write(OpCode::ConstantFloat as u8, 123);
-                bytecode.add_constant(Value::Float(float));
-              }
-              AnalyzerValue::Boolean(_) => todo!(),
-              AnalyzerValue::Return(_) => todo!(),
-              AnalyzerValue::Function(_) => todo!(),
-              AnalyzerValue::Null => todo!(),
-              AnalyzerValue::None => todo!(),
-            };
-          }
-          _ => todo!(),
+  pub fn generate(&mut self, irs: &HashMap<String, Vec<IRInstruction>>) {
+    for (file_name, ir) in irs {
+      self
+        .functions
+        .push(FunctionContext::new(FunctionKind::Function));
+      self.module_path = file_name.clone();
+      self.line = 0;
+
+      for (i, instruction) in ir.iter().enumerate() {
+        if self.return_last_expression && i == ir.len() - 1 && Self::is_expression(instruction) {
+          self.generate_expression(instruction);
+          self.emit_op(OpCode::Return);
+          continue;
+        }
+
+        self.generate_statement(instruction);
+      }
+
+      self.emit_op(OpCode::Null);
+      self.emit_op(OpCode::Return);
+
+      let context = self.functions.pop().unwrap();
+      self.bytecodes.insert(file_name.clone(), context.bytecode);
+    }
+  }
+
+  /**
+    Whether `generate_statement` lowers `instruction` as an expression whose
+    value is discarded.
+  */
+  fn is_expression(instruction: &IRInstruction) -> bool {
+    match instruction {
+      IRInstruction::Variable(variable) => !variable.metadata.is_declaration,
+      IRInstruction::Function(_)
+      | IRInstruction::Block(_)
+      | IRInstruction::If(_)
+      | IRInstruction::While(_)
+      | IRInstruction::ForIn(_)
+      | IRInstruction::Return(_)
+      | IRInstruction::Break(_)
+      | IRInstruction::Continue(_)
+      | IRInstruction::Import(_)
+      | IRInstruction::Class(_)
+      | IRInstruction::Enum(_)
+      | IRInstruction::Interface(_)
+      | IRInstruction::TypeAlias(_)
+      | IRInstruction::Switch(_) => false,
+      _ => true,
+    }
+  }
+
+  fn generate_statement(&mut self, instruction: &IRInstruction) {
+    self.set_line(instruction);
+
+    match instruction {
+      IRInstruction::Variable(variable) if variable.metadata.is_declaration => {
+        self.generate_variable_declaration(variable);
+      }
+      IRInstruction::Function(function) => self.generate_function_declaration(function),
+      IRInstruction::Block(block) => {
+        self.begin_scope();
+        self.generate_block(block);
+        self.end_scope();
+      }
+      IRInstruction::If(ir_if) => self.generate_if(ir_if),
+      IRInstruction::While(ir_while) => self.generate_while(ir_while),
+      IRInstruction::ForIn(for_in) => self.generate_for_in(for_in),
+      IRInstruction::Return(ir_return) => {
+        if self.current().is_initializer {
+          self.emit_bytes(OpCode::GetLocal, 0);
+        } else {
+          self.generate_expression(&ir_return.value);
+        }
+
+        self.line = ir_return.token.span.line;
+        self.emit_op(OpCode::Return);
+      }
+      IRInstruction::Break(_) => {
+        let index = self.current().loops.len() - 1;
+        self.pop_loop_locals(index);
+
+        let jump = self.emit_jump(OpCode::Jump);
+        self.current().loops[index].breaks.push(jump);
+      }
+      IRInstruction::Continue(_) => {
+        let index = self
+          .current()
+          .loops
+          .iter()
+          .rposition(|context| !context.is_switch)
+          .unwrap();
+        self.pop_loop_locals(index);
+
+        let jump = self.emit_jump(OpCode::Jump);
+        self.current().loops[index].continues.push(jump);
+      }
+      IRInstruction::Switch(switch) => self.generate_switch(switch),
+      IRInstruction::Import(_) => {
+        // Imported symbols are already part of the IR of the current module.
+      }
+      IRInstruction::Class(class) => self.generate_class(class),
+      IRInstruction::Enum(_) => {
+        // The variants are created by the instructions that use them.
+      }
+      IRInstruction::Interface(_) | IRInstruction::TypeAlias(_) => {
+        // Types are only checked by the analyzer.
+      }
+      _ => {
+        self.generate_expression(instruction);
+        self.emit_op(OpCode::Pop);
+      }
+    }
+  }
+
+  fn generate_expression(&mut self, instruction: &IRInstruction) {
+    self.set_line(instruction);
+
+    match instruction {
+      IRInstruction::Literal(literal) => self.generate_literal(&literal.value),
+      IRInstruction::Binary(binary) => match binary.instruction_type {
+        IRInstructionType::And | IRInstructionType::Or => {
+          let logical = IRLogical::new(
+            binary.instruction_type.clone(),
+            binary.left.clone(),
+            binary.right.clone(),
+            binary.token.clone(),
+          );
+
+          self.generate_logical(&logical);
+        }
+        _ => {
+          self.generate_operand(&binary.left);
+          self.generate_expression(&binary.right);
+          self.release_operands(1);
+
+          self.line = binary.token.span.line;
+          self.emit_op(Self::binary_op_code(&binary.instruction_type));
+        }
+      },
+      IRInstruction::Unary(unary) => {
+        self.generate_expression(&unary.right);
+
+        self.line = unary.token.span.line;
+        match unary.instruction_type {
+          IRInstructionType::Not => self.emit_op(OpCode::Not),
+          _ => self.emit_op(OpCode::Negate),
+        };
+      }
+      IRInstruction::Variable(variable) => self.generate_get_variable(&variable.name),
+      IRInstruction::Function(function) => self.generate_get_variable(&function.name),
+      IRInstruction::Logical(logical) => self.generate_logical(logical),
+      IRInstruction::Ternary(ternary) => self.generate_ternary(ternary),
+      IRInstruction::Call(call) => self.generate_call(call),
+      IRInstruction::Lambda(lambda) => self.generate_lambda(lambda),
+      IRInstruction::TemplateLiteral(template) => self.generate_template_literal(template),
+      IRInstruction::CallValue(call) => {
+        self.generate_operand(&call.callee);
+
+        for argument in &call.arguments {
+          self.generate_operand(argument);
+        }
+
+        self.release_operands(call.arguments.len() + 1);
+
+        self.line = call.token.span.line;
+        self.emit_bytes(OpCode::Call, call.arguments.len() as u8);
+      }
+      IRInstruction::Assign(assign) => {
+        self.generate_expression(&assign.value);
+
+        self.line = assign.token.span.line;
+        if let Some(slot) = self.resolve_local(&assign.name) {
+          self.emit_bytes(OpCode::SetLocal, slot);
+        } else if let Some(index) = self.resolve_upvalue(&assign.name) {
+          self.emit_bytes(OpCode::SetUpvalue, index);
+        } else {
+          let index = self.identifier_constant(&assign.name);
+          self.emit_constant_op(OpCode::SetGlobal, index);
+        }
+      }
+      IRInstruction::Array(array) => {
+        for element in &array.elements {
+          self.generate_operand(element);
+        }
+
+        self.release_operands(array.elements.len());
+
+        let count = array.elements.len();
+
+        self.line = array.token.span.line;
+        if count > u16::MAX as usize {
+          self.error(GeneratorErrorKind::TooManyElements);
+        }
+
+        self.emit_op(OpCode::Array);
+        self.emit_byte((count >> 8) as u8);
+        self.emit_byte(count as u8);
+      }
+      IRInstruction::Index(index) => {
+        self.generate_operand(&index.object);
+        self.generate_expression(&index.index);
+        self.release_operands(1);
+
+        self.line = index.token.span.line;
+        self.emit_op(OpCode::Index);
+      }
+      IRInstruction::SetIndex(set) => {
+        self.generate_operand(&set.object);
+        self.generate_operand(&set.index);
+        self.generate_expression(&set.value);
+        self.release_operands(2);
+
+        self.line = set.token.span.line;
+        self.emit_op(OpCode::SetIndex);
+      }
+      IRInstruction::This(_) => match self.resolve_upvalue("this") {
+        Some(index) => self.emit_bytes(OpCode::GetUpvalue, index),
+        None => self.emit_bytes(OpCode::GetLocal, 0),
+      },
+      IRInstruction::Get(get) => {
+        self.generate_expression(&get.object);
+
+        self.line = get.token.span.line;
+        let index = self.identifier_constant(&get.name);
+        self.emit_constant_op(OpCode::GetProperty, index);
+      }
+      IRInstruction::Set(set) => {
+        self.generate_operand(&set.object);
+        self.generate_expression(&set.value);
+        self.release_operands(1);
+
+        self.line = set.token.span.line;
+        let index = self.identifier_constant(&set.name);
+        self.emit_constant_op(OpCode::SetProperty, index);
+      }
+      IRInstruction::New(new) => {
+        self.generate_get_variable(&new.class_name);
+        self.hold_operand();
+
+        for argument in &new.arguments {
+          self.generate_operand(argument);
         }
 
-        bytecode.count += 1;
+        self.release_operands(new.arguments.len() + 1);
+
+        self.line = new.token.span.line;
+        self.emit_bytes(OpCode::Call, new.arguments.len() as u8);
+      }
+      IRInstruction::MethodCall(call) => self.generate_method_call(call),
+      IRInstruction::EnumValue(enum_value) => self.generate_enum_value(enum_value),
+      IRInstruction::Match(ir_match) => self.generate_match(ir_match),
+      IRInstruction::Error(_) => unreachable!("code with errors is not compiled"),
+      _ => {
+        self.generate_statement(instruction);
+        self.emit_op(OpCode::Null);
+      }
+    }
+  }
+
+  fn generate_literal(&mut self, value: &AnalyzerValue) {
+    match value {
+      AnalyzerValue::Int(int) => self.emit_constant(OpCode::ConstantInt, Value::Int(*int)),
+      AnalyzerValue::Float(float) => {
+        self.emit_constant(OpCode::ConstantFloat, Value::Float(*float))
+      }
+      AnalyzerValue::String(string) => {
+        self.emit_constant(OpCode::ConstantString, Value::String(string.clone()))
+      }
+      AnalyzerValue::Boolean(true) => self.emit_op(OpCode::True),
+      AnalyzerValue::Boolean(false) => self.emit_op(OpCode::False),
+      AnalyzerValue::Return(value) => self.generate_literal(value),
+      AnalyzerValue::Function(function) => self.generate_get_variable(&function.name.span.literal),
+      AnalyzerValue::Null | AnalyzerValue::None => self.emit_op(OpCode::Null),
+    }
+  }
+
+  fn generate_variable_declaration(&mut self, variable: &IRVariable) {
+    match &variable.value {
+      Some(value) => self.generate_expression(value),
+      None => self.emit_op(OpCode::Null),
+    };
+
+    self.define_variable(&variable.name);
+  }
+
+  fn generate_function_declaration(&mut self, function: &IRFunction) {
+    if let Some(module) = &function.metadata.extern_module {
+      self.generate_native_declaration(module, function);
+      return;
+    }
+
+    if function.body.is_none() {
+      return;
+    }
+
+    self.generate_function(function, FunctionKind::Function, &[]);
+
+    let is_top_level = self.is_top_level();
+
+    self.define_variable(&function.name);
+
+    if function.name == "main" && is_top_level {
+      self.generate_get_variable(&function.name);
+      self.emit_bytes(OpCode::Call, 0);
+      self.emit_op(OpCode::Pop);
+    }
+  }
+
+  /**
+    Compiles the body of `function` and pushes it on the stack. `captures`
+    are the variables of the enclosing functions used by a lambda, they are
+    added to the closure once it is on the stack.
+  */
+  fn generate_function(&mut self, function: &IRFunction, kind: FunctionKind, captures: &[String]) {
+    let mut context = FunctionContext::new(kind);
+
+    if captures.len() > u8::MAX as usize + 1 {
+      self.error(GeneratorErrorKind::TooManyUpvalues);
+    }
+
+    for name in captures {
+      if let Some((index, is_local)) = self.resolve_capture(name) {
+        context.upvalues.push(Upvalue {
+          name: name.clone(),
+          index,
+          is_local,
+        });
+      }
+    }
+
+    self.functions.push(context);
+    self.begin_scope();
+
+    for parameter in &function.parameters {
+      self.add_local(&parameter.name);
+    }
+
+    if let Some(body) = &function.body {
+      self.generate_block(body);
+    }
+
+    if kind == FunctionKind::Initializer {
+      self.emit_bytes(OpCode::GetLocal, 0);
+    } else {
+      self.emit_op(OpCode::Null);
+    }
+
+    self.emit_op(OpCode::Return);
+
+    self.line = function.token.span.line;
+
+    let context = self.functions.pop().unwrap();
+    let prototype = FunctionPrototype::new(
+      function.name.clone(),
+      function.parameters.len(),
+      context.bytecode,
+    );
+
+    let index = self.make_constant(Value::Function(Rc::new(prototype)));
+    self.emit_constant_op(OpCode::Function, index);
+
+    for upvalue in &context.upvalues {
+      let op = if upvalue.is_local {
+        OpCode::CaptureLocal
+      } else {
+        OpCode::CaptureUpvalue
+      };
+
+      self.emit_bytes(op, upvalue.index);
+    }
+  }
+
+  /**
+    Joins the parts with `Concatenate`, that converts both operands to
+    strings. A template that does not start with text starts with an empty
+    string so the first expression is converted as well.
+  */
+  fn generate_template_literal(&mut self, template: &IRTemplateLiteral) {
+    let mut parts = template.parts.iter().peekable();
+
+    match parts.peek() {
+      Some(IRInstruction::Literal(literal))
+        if matches!(literal.value, AnalyzerValue::String(_)) =>
+      {
+        self.generate_literal(&literal.value);
+        parts.next();
+      }
+      _ => self.generate_literal(&AnalyzerValue::String(String::new())),
+    }
+
+    for part in parts {
+      self.hold_operand();
+      self.generate_expression(part);
+      self.release_operands(1);
+
+      self.line = template.token.span.line;
+      self.emit_op(OpCode::Concatenate);
+    }
+  }
+
+  fn generate_lambda(&mut self, lambda: &IRLambda) {
+    self.generate_function(&lambda.function, FunctionKind::Function, &lambda.captures);
+  }
+
+  /**
+    The class is defined first and its methods are attached to it, the
+    constructor is stored as the method named after the class.
+    Static fields are set on the class once the methods exist.
+  */
+  fn generate_class(&mut self, class: &IRClass) {
+    let index = self.identifier_constant(&class.name);
+    self.emit_constant_op(OpCode::Class, index);
+    self.define_variable(&class.name);
+
+    self.generate_get_variable(&class.name);
+
+    if let Some(constructor) = &class.constructor {
+      self.generate_function(&constructor.function, FunctionKind::Initializer, &[]);
+
+      let index = self.identifier_constant(&class.name);
+      self.emit_constant_op(OpCode::Method, index);
+    }
+
+    for method in &class.methods {
+      self.generate_function(&method.function, FunctionKind::Method, &[]);
+
+      let op = if method.metadata.is_static {
+        OpCode::StaticMethod
+      } else {
+        OpCode::Method
+      };
+
+      let index = self.identifier_constant(&method.function.name);
+      self.emit_constant_op(op, index);
+    }
+
+    self.emit_op(OpCode::Pop);
+
+    for field in &class.fields {
+      if let Some(value) = &field.value {
+        self.generate_get_variable(&class.name);
+        self.generate_expression(value);
+
+        let index = self.identifier_constant(&field.name);
+        self.emit_constant_op(OpCode::SetProperty, index);
+        self.emit_op(OpCode::Pop);
       }
     }
   }
+
+  /**
+    Variants declared with a constant are that constant, the others take
+    their payload from the stack.
+  */
+  fn generate_enum_value(&mut self, enum_value: &IREnumValue) {
+    if let Some(value) = &enum_value.value {
+      self.generate_literal(value);
+      return;
+    }
+
+    for argument in &enum_value.arguments {
+      self.generate_operand(argument);
+    }
+
+    self.release_operands(enum_value.arguments.len());
+
+    let variant = VariantDeclaration::new(
+      enum_value.enum_name.clone(),
+      enum_value.variant.clone(),
+      enum_value.arguments.len(),
+    );
+
+    self.line = enum_value.token.span.line;
+    let index = self.make_constant(Value::Variant(variant));
+    self.emit_constant_op(OpCode::Variant, index);
+  }
+
+  /**
+    An `@extern` function has no body, the VM binds the name to the host
+    function registered for `module`.
+  */
+  fn generate_native_declaration(&mut self, module: &str, function: &IRFunction) {
+    let native = NativeDeclaration::new(
+      module.to_string(),
+      function.name.clone(),
+      function.parameters.len(),
+    );
+
+    let index = self.make_constant(Value::Native(native));
+    self.emit_constant_op(OpCode::Native, index);
+
+    self.define_variable(&function.name);
+  }
+
+  fn generate_block(&mut self, block: &IRBlock) {
+    for instruction in &block.instructions {
+      self.generate_statement(instruction);
+    }
+  }
+
+  fn generate_if(&mut self, ir_if: &IRIf) {
+    self.generate_expression(&ir_if.condition);
+
+    let then_jump = self.emit_jump(OpCode::JumpIfFalse);
+    self.emit_op(OpCode::Pop);
+
+    self.generate_statement(&ir_if.then_branch);
+
+    let else_jump = self.emit_jump(OpCode::Jump);
+
+    self.patch_jump(then_jump);
+    self.emit_op(OpCode::Pop);
+
+    if let Some(else_branch) = &ir_if.else_branch {
+      self.generate_statement(else_branch);
+    }
+
+    self.patch_jump(else_jump);
+  }
+
+  fn generate_while(&mut self, ir_while: &IRWhile) {
+    let loop_start = self.current_bytecode().code.len();
+
+    self.generate_expression(&ir_while.condition);
+
+    let exit_jump = self.emit_jump(OpCode::JumpIfFalse);
+    self.emit_op(OpCode::Pop);
+
+    self.begin_loop();
+    self.generate_statement(&ir_while.body);
+    let context = self.current().loops.pop().unwrap();
+
+    for jump in context.continues {
+      self.patch_jump(jump);
+    }
+
+    self.emit_loop(loop_start);
+
+    self.patch_jump(exit_jump);
+    self.emit_op(OpCode::Pop);
+
+    for jump in context.breaks {
+      self.patch_jump(jump);
+    }
+  }
+
+  /**
+  Lowers `for (let item in iterable) body` to an index based loop.
+  The iterable and the index live in two hidden locals that can not be
+  referenced by the user code.
+  */
+  fn generate_for_in(&mut self, for_in: &IRForIn) {
+    self.line = for_in.token.span.line;
+
+    self.begin_scope();
+
+    self.generate_expression(&for_in.iterable);
+    self.add_local("@iterable");
+
+    self.emit_constant(OpCode::ConstantInt, Value::Int(0));
+    self.add_local("@index");
+
+    let iterable = (self.current().locals.len() - 2) as u8;
+    let index = (self.current().locals.len() - 1) as u8;
+
+    let loop_start = self.current_bytecode().code.len();
+
+    self.emit_bytes(OpCode::GetLocal, index);
+    self.emit_bytes(OpCode::GetLocal, iterable);
+    self.emit_op(OpCode::Length);
+    self.emit_op(OpCode::Less);
+
+    let exit_jump = self.emit_jump(OpCode::JumpIfFalse);
+    self.emit_op(OpCode::Pop);
+
+    self.begin_loop();
+    self.begin_scope();
+
+    self.emit_bytes(OpCode::GetLocal, iterable);
+    self.emit_bytes(OpCode::GetLocal, index);
+    self.emit_op(OpCode::Index);
+    self.add_local(&for_in.variable.name);
+
+    self.generate_statement(&for_in.body);
+
+    self.end_scope();
+    let context = self.current().loops.pop().unwrap();
+
+    for jump in context.continues {
+      self.patch_jump(jump);
+    }
+
+    self.emit_bytes(OpCode::GetLocal, index);
+    self.emit_constant(OpCode::ConstantInt, Value::Int(1));
+    self.emit_op(OpCode::Add);
+    self.emit_bytes(OpCode::SetLocal, index);
+    self.emit_op(OpCode::Pop);
+
+    self.emit_loop(loop_start);
+
+    self.patch_jump(exit_jump);
+    self.emit_op(OpCode::Pop);
+
+    for jump in context.breaks {
+      self.patch_jump(jump);
+    }
+
+    self.end_scope();
+  }
+
+  /**
+  Lowers a `match` to a chain of tests. The matched value lives in a hidden
+  local, the first arm that matches stores its value in that slot and the
+  slot becomes the value of the expression.
+  */
+  fn generate_match(&mut self, ir_match: &IRMatch) {
+    self.begin_scope();
+
+    self.generate_expression(&ir_match.value);
+    self.add_local("@match");
+
+    let slot = (self.current().locals.len() - 1) as u8;
+    let mut end_jumps: Vec<usize> = Vec::new();
+
+    for arm in &ir_match.arms {
+      self.begin_scope();
+
+      let pattern_jump = match &arm.pattern {
+        IRMatchPattern::Wildcard => None,
+        IRMatchPattern::Literal(value)
+        | IRMatchPattern::Variant {
+          value: Some(value), ..
+        } => {
+          self.emit_bytes(OpCode::GetLocal, slot);
+          self.generate_literal(value);
+          self.emit_op(OpCode::Equal);
+
+          Some(self.emit_jump(OpCode::JumpIfFalse))
+        }
+        IRMatchPattern::Variant {
+          enum_name,
+          variant,
+          bindings,
+          ..
+        } => {
+          let declaration =
+            VariantDeclaration::new(enum_name.clone(), variant.clone(), bindings.len());
+
+          self.emit_bytes(OpCode::GetLocal, slot);
+          let index = self.make_constant(Value::Variant(declaration));
+          self.emit_constant_op(OpCode::CheckVariant, index);
+
+          Some(self.emit_jump(OpCode::JumpIfFalse))
+        }
+      };
+
+      if pattern_jump.is_some() {
+        self.emit_op(OpCode::Pop);
+      }
+
+      let mut bindings = 0;
+
+      if let IRMatchPattern::Variant {
+        value: None,
+        bindings: names,
+        ..
+      } = &arm.pattern
+      {
+        for (index, name) in names.iter().enumerate() {
+          if let Some(name) = name {
+            self.emit_bytes(OpCode::GetLocal, slot);
+            self.emit_bytes(OpCode::GetVariantValue, index as u8);
+            self.add_local(name);
+            bindings += 1;
+          }
+        }
+      }
+
+      let guard_jump = arm.guard.as_ref().map(|guard| {
+        self.generate_expression(guard);
+        let jump = self.emit_jump(OpCode::JumpIfFalse);
+        self.emit_op(OpCode::Pop);
+
+        jump
+      });
+
+      match &*arm.body {
+        IRInstruction::Block(block) => {
+          self.generate_block(block);
+          self.emit_op(OpCode::Null);
+        }
+        body => self.generate_expression(body),
+      }
+
+      self.emit_bytes(OpCode::SetLocal, slot);
+      self.emit_op(OpCode::Pop);
+
+      let locals = &self.current().locals;
+      let binding_pops: Vec<OpCode> = locals[locals.len() - bindings..]
+        .iter()
+        .rev()
+        .map(Self::pop_op)
+        .collect();
+
+      self.end_scope();
+      end_jumps.push(self.emit_jump(OpCode::Jump));
+
+      // The guard failed after the bindings were pushed.
+      if let Some(guard_jump) = guard_jump {
+        self.patch_jump(guard_jump);
+        self.emit_op(OpCode::Pop);
+
+        for op in binding_pops {
+          self.emit_op(op);
+        }
+
+        if let Some(pattern_jump) = pattern_jump {
+          let next_jump = self.emit_jump(OpCode::Jump);
+
+          self.patch_jump(pattern_jump);
+          self.emit_op(OpCode::Pop);
+          self.patch_jump(next_jump);
+        }
+      } else if let Some(pattern_jump) = pattern_jump {
+        self.patch_jump(pattern_jump);
+        self.emit_op(OpCode::Pop);
+      }
+    }
+
+    // No arm matched.
+    self.emit_op(OpCode::Null);
+    self.emit_bytes(OpCode::SetLocal, slot);
+    self.emit_op(OpCode::Pop);
+
+    for jump in end_jumps {
+      self.patch_jump(jump);
+    }
+
+    // The slot is left on the stack as the value of the expression.
+    let context = self.current();
+    context.locals.pop();
+    context.scope_depth -= 1;
+  }
+
+  /**
+  Lowers a `switch` to a jump table followed by the bodies of the cases in
+  order, so a case without `break` falls through to the next one:
+
+  ```text
+  value == case 1 ? jump body 1
+  value == case 2 ? jump body 2
+  jump default body or end
+  body 1
+  body 2
+  ```
+  */
+  fn generate_switch(&mut self, switch: &IRSwitch) {
+    self.begin_scope();
+
+    self.generate_expression(&switch.value);
+    self.add_local("@switch");
+
+    let slot = (self.current().locals.len() - 1) as u8;
+
+    let (body_jumps, default_jumps) = match Self::jump_table_range(switch) {
+      Some((min, size)) => self.generate_jump_table(switch, slot, min, size),
+      None => self.generate_switch_comparisons(switch, slot),
+    };
+
+    let default_case = switch.default_case();
+
+    self.begin_breakable(true);
+
+    for (index, case) in switch.cases.iter().enumerate() {
+      match body_jumps[index] {
+        Some(jump) => self.patch_jump(jump),
+        None => default_jumps.iter().for_each(|jump| self.patch_jump(*jump)),
+      }
+
+      self.begin_scope();
+
+      for instruction in &case.body {
+        self.generate_statement(instruction);
+      }
+
+      self.end_scope();
+    }
+
+    if default_case.is_none() {
+      default_jumps.iter().for_each(|jump| self.patch_jump(*jump));
+    }
+
+    let context = self.current().loops.pop().unwrap();
+
+    for jump in context.breaks {
+      self.patch_jump(jump);
+    }
+
+    self.end_scope();
+  }
+
+  /**
+    Compares the value of a `switch` with its cases one by one. Returns the
+    jump to the body of every case, `None` for `default`, and the jumps taken
+    when no case matches.
+  */
+  fn generate_switch_comparisons(
+    &mut self,
+    switch: &IRSwitch,
+    slot: u8,
+  ) -> (Vec<Option<usize>>, Vec<usize>) {
+    let mut body_jumps: Vec<Option<usize>> = Vec::new();
+
+    for case in &switch.cases {
+      let value = match &case.value {
+        Some(value) => value,
+        None => {
+          body_jumps.push(None);
+          continue;
+        }
+      };
+
+      self.emit_bytes(OpCode::GetLocal, slot);
+      self.generate_expression(value);
+      self.emit_op(OpCode::Equal);
+
+      let next_jump = self.emit_jump(OpCode::JumpIfFalse);
+      self.emit_op(OpCode::Pop);
+      body_jumps.push(Some(self.emit_jump(OpCode::Jump)));
+
+      self.patch_jump(next_jump);
+      self.emit_op(OpCode::Pop);
+    }
+
+    (body_jumps, vec![self.emit_jump(OpCode::Jump)])
+  }
+
+  /**
+    Dispatches a `switch` on `int` cases with a `JumpTable`, it is followed by
+    a `Jump` for every value from `min` to `min + size - 1` and a last one
+    taken by the values outside of that range. Returns the jumps like
+    `generate_switch_comparisons`, the values without a case jump to the
+    `default` as well.
+  */
+  fn generate_jump_table(
+    &mut self,
+    switch: &IRSwitch,
+    slot: u8,
+    min: i64,
+    size: usize,
+  ) -> (Vec<Option<usize>>, Vec<usize>) {
+    self.emit_bytes(OpCode::GetLocal, slot);
+    self.emit_constant(OpCode::ConstantInt, Value::Int(min));
+    self.emit_op(OpCode::JumpTable);
+    self.emit_byte((size >> 8) as u8);
+    self.emit_byte(size as u8);
+
+    let mut table: Vec<Option<usize>> = (0..size)
+      .map(|_| Some(self.emit_jump(OpCode::Jump)))
+      .collect();
+    let outside = self.emit_jump(OpCode::Jump);
+
+    let body_jumps = switch
+      .cases
+      .iter()
+      .map(|case| {
+        let value = Self::int_case(case.value.as_deref()?)?;
+        table[(value - min) as usize].take()
+      })
+      .collect();
+
+    let mut default_jumps: Vec<usize> = table.into_iter().flatten().collect();
+    default_jumps.push(outside);
+
+    (body_jumps, default_jumps)
+  }
+
+  /**
+    Smallest value and size of the jump table of a `switch` whose cases are
+    `int` literals close to each other, `None` when the cases are compared
+    one by one.
+  */
+  fn jump_table_range(switch: &IRSwitch) -> Option<(i64, usize)> {
+    let values = switch
+      .cases
+      .iter()
+      .filter_map(|case| case.value.as_deref())
+      .map(Self::int_case)
+      .collect::<Option<Vec<i64>>>()?;
+
+    if values.len() < JUMP_TABLE_MIN_CASES {
+      return None;
+    }
+
+    let min = *values.iter().min()?;
+    let max = *values.iter().max()?;
+    let size = usize::try_from(max.checked_sub(min)?).ok()? + 1;
+
+    (size <= values.len() * 2 && size <= u16::MAX as usize).then_some((min, size))
+  }
+
+  fn int_case(value: &IRInstruction) -> Option<i64> {
+    match value {
+      IRInstruction::Literal(literal) => match literal.value {
+        AnalyzerValue::Int(int) => Some(int),
+        _ => None,
+      },
+      _ => None,
+    }
+  }
+
+  fn generate_logical(&mut self, logical: &IRLogical) {
+    self.generate_expression(&logical.left);
+
+    match logical.instruction_type {
+      IRInstructionType::Or => {
+        let else_jump = self.emit_jump(OpCode::JumpIfFalse);
+        let end_jump = self.emit_jump(OpCode::Jump);
+
+        self.patch_jump(else_jump);
+        self.emit_op(OpCode::Pop);
+
+        self.generate_expression(&logical.right);
+        self.patch_jump(end_jump);
+      }
+      _ => {
+        let end_jump = self.emit_jump(OpCode::JumpIfFalse);
+        self.emit_op(OpCode::Pop);
+
+        self.generate_expression(&logical.right);
+        self.patch_jump(end_jump);
+      }
+    }
+  }
+
+  fn generate_ternary(&mut self, ternary: &IRTernary) {
+    self.generate_expression(&ternary.condition);
+
+    let else_jump = self.emit_jump(OpCode::JumpIfFalse);
+    self.emit_op(OpCode::Pop);
+
+    self.generate_expression(&ternary.then_branch);
+
+    let end_jump = self.emit_jump(OpCode::Jump);
+
+    self.patch_jump(else_jump);
+    self.emit_op(OpCode::Pop);
+
+    self.generate_expression(&ternary.else_branch);
+
+    self.patch_jump(end_jump);
+  }
+
+  fn generate_call(&mut self, call: &IRCall) {
+    self.generate_get_variable(&call.name);
+    self.hold_operand();
+
+    for argument in &call.arguments {
+      self.generate_operand(argument);
+    }
+
+    self.release_operands(call.arguments.len() + 1);
+
+    self.line = call.token.span.line;
+    self.emit_bytes(OpCode::Call, call.arguments.len() as u8);
+  }
+
+  fn generate_method_call(&mut self, call: &IRMethodCall) {
+    self.generate_expression(&call.object);
+
+    self.line = call.token.span.line;
+    let index = self.identifier_constant(&call.name);
+    self.emit_constant_op(OpCode::GetProperty, index);
+    self.hold_operand();
+
+    for argument in &call.arguments {
+      self.generate_operand(argument);
+    }
+
+    self.release_operands(call.arguments.len() + 1);
+
+    self.line = call.token.span.line;
+    self.emit_bytes(OpCode::Call, call.arguments.len() as u8);
+  }
+
+  fn generate_get_variable(&mut self, name: &str) {
+    if let Some(slot) = self.resolve_local(name) {
+      self.emit_bytes(OpCode::GetLocal, slot);
+    } else if let Some(index) = self.resolve_upvalue(name) {
+      self.emit_bytes(OpCode::GetUpvalue, index);
+    } else {
+      let index = self.identifier_constant(name);
+      self.emit_constant_op(OpCode::GetGlobal, index);
+    }
+  }
+
+  fn binary_op_code(instruction_type: &IRInstructionType) -> OpCode {
+    match instruction_type {
+      IRInstructionType::Add => OpCode::Add,
+      IRInstructionType::Sub => OpCode::Subtract,
+      IRInstructionType::Mul => OpCode::Multiply,
+      IRInstructionType::Div => OpCode::Divide,
+      IRInstructionType::Mod => OpCode::Modulo,
+      IRInstructionType::Greater => OpCode::Greater,
+      IRInstructionType::GreaterEqual => OpCode::GreaterEqual,
+      IRInstructionType::Less => OpCode::Less,
+      IRInstructionType::LessEqual => OpCode::LessEqual,
+      IRInstructionType::Equal => OpCode::Equal,
+      IRInstructionType::NotEqual => OpCode::NotEqual,
+      IRInstructionType::Concatenate => OpCode::Concatenate,
+      _ => unreachable!("{:?} is not a binary operator", instruction_type),
+    }
+  }
+
+  fn define_variable(&mut self, name: &str) {
+    if self.is_top_level() {
+      let index = self.identifier_constant(name);
+      self.emit_constant_op(OpCode::DefineGlobal, index);
+    } else {
+      self.add_local(name);
+    }
+  }
+
+  fn is_top_level(&self) -> bool {
+    self.functions.len() == 1 && self.functions.last().unwrap().scope_depth == 0
+  }
+
+  /**
+  Generates a value that stays on the stack while the next operands are
+  generated. It is tracked as an unnamed local so the locals declared by the
+  operands, like the ones of a `match`, get the right slot.
+  */
+  fn generate_operand(&mut self, instruction: &IRInstruction) {
+    self.generate_expression(instruction);
+    self.hold_operand();
+  }
+
+  fn hold_operand(&mut self) {
+    let context = self.current();
+    let depth = context.scope_depth;
+
+    context.locals.push(Local {
+      name: String::new(),
+      depth,
+      is_captured: false,
+    });
+  }
+
+  /**
+  Forgets the last `count` operands, the instruction that uses them already
+  took them from the stack.
+  */
+  fn release_operands(&mut self, count: usize) {
+    let context = self.current();
+    let length = context.locals.len() - count;
+
+    context.locals.truncate(length);
+  }
+
+  /**
+    Declares a local in the current scope. The local past the last slot is
+    reported once, the locals after it would be reported again otherwise.
+  */
+  fn add_local(&mut self, name: &str) {
+    if self.current().locals.len() == u8::MAX as usize + 1 {
+      self.error(GeneratorErrorKind::TooManyLocals);
+    }
+
+    let context = self.current();
+    let depth = context.scope_depth;
+    context.locals.push(Local {
+      name: name.to_string(),
+      depth,
+      is_captured: false,
+    });
+  }
+
+  fn resolve_local(&mut self, name: &str) -> Option<u8> {
+    self
+      .current()
+      .locals
+      .iter()
+      .rposition(|local| local.depth > 0 && local.name == name)
+      .map(|slot| slot as u8)
+  }
+
+  fn resolve_upvalue(&mut self, name: &str) -> Option<u8> {
+    self
+      .current()
+      .upvalues
+      .iter()
+      .position(|upvalue| upvalue.name == name)
+      .map(|index| index as u8)
+  }
+
+  /**
+  Finds a variable captured by a lambda declared in the current function.
+  Inside a lambda `this` is the instance captured from the method.
+  */
+  fn resolve_capture(&mut self, name: &str) -> Option<(u8, bool)> {
+    let slot = match name {
+      "this" if self.resolve_upvalue(name).is_some() => None,
+      "this" => Some(0),
+      _ => self.resolve_local(name),
+    };
+
+    if let Some(slot) = slot {
+      self.current().locals[slot as usize].is_captured = true;
+      return Some((slot, true));
+    }
+
+    self.resolve_upvalue(name).map(|index| (index, false))
+  }
+
+  /**
+  Instruction that removes `local` from the stack, the captured locals are
+  moved to the heap first.
+  */
+  fn pop_op(local: &Local) -> OpCode {
+    if local.is_captured {
+      OpCode::CloseUpvalue
+    } else {
+      OpCode::Pop
+    }
+  }
+
+  fn begin_scope(&mut self) {
+    self.current().scope_depth += 1;
+  }
+
+  fn end_scope(&mut self) {
+    let context = self.current();
+    context.scope_depth -= 1;
+
+    let depth = context.scope_depth;
+    let mut pops = Vec::new();
+
+    while context.locals.last().is_some_and(|local| local.depth > depth) {
+      let local = context.locals.pop().unwrap();
+      pops.push(Self::pop_op(&local));
+    }
+
+    for op in pops {
+      self.emit_op(op);
+    }
+  }
+
+  fn begin_loop(&mut self) {
+    self.begin_breakable(false);
+  }
+
+  fn begin_breakable(&mut self, is_switch: bool) {
+    let scope_depth = self.current().scope_depth;
+
+    self.current().loops.push(LoopContext {
+      scope_depth,
+      breaks: Vec::new(),
+      continues: Vec::new(),
+      is_switch,
+    });
+  }
+
+  /**
+  Pops the locals declared inside the loop `index` before jumping out of it.
+  The locals are kept in the context because the code after the jump still
+  belongs to their scope.
+  */
+  fn pop_loop_locals(&mut self, index: usize) {
+    let context = self.functions.last().unwrap();
+    let depth = context.loops[index].scope_depth;
+
+    let pops: Vec<OpCode> = context
+      .locals
+      .iter()
+      .rev()
+      .take_while(|local| local.depth > depth)
+      .map(Self::pop_op)
+      .collect();
+
+    for op in pops {
+      self.emit_op(op);
+    }
+  }
+
+  fn current(&mut self) -> &mut FunctionContext {
+    self.functions.last_mut().unwrap()
+  }
+
+  fn current_bytecode(&mut self) -> &mut Bytecode {
+    &mut self.current().bytecode
+  }
+
+  fn emit_byte(&mut self, byte: u8) {
+    let line = self.line;
+    self.current_bytecode().write(byte, line);
+  }
+
+  fn emit_op(&mut self, op: OpCode) {
+    self.emit_byte(op as u8);
+  }
+
+  fn emit_bytes(&mut self, op: OpCode, operand: u8) {
+    self.emit_op(op);
+    self.emit_byte(operand);
+  }
+
+  fn error(&mut self, kind: GeneratorErrorKind) {
+    self.errors.push(GeneratorError::new(
+      kind,
+      self.line,
+      self.module_path.clone(),
+    ));
+  }
+
+  fn make_constant(&mut self, value: Value) -> usize {
+    let index = self.current_bytecode().add_constant(value);
+
+    if index == MAX_CONSTANTS {
+      self.error(GeneratorErrorKind::TooManyConstants);
+    }
+
+    index
+  }
+
+  fn emit_constant(&mut self, op: OpCode, value: Value) {
+    let index = self.make_constant(value);
+    self.emit_constant_op(op, index);
+  }
+
+  /**
+    Emits `op` with a 1-byte constant index, or its long form with a 3-byte
+    index when the constant does not fit in a byte.
+  */
+  fn emit_constant_op(&mut self, op: OpCode, index: usize) {
+    if index <= u8::MAX as usize {
+      self.emit_bytes(op, index as u8);
+      return;
+    }
+
+    self.emit_op(op.long_form().unwrap());
+    self.emit_byte((index >> 16) as u8);
+    self.emit_byte((index >> 8) as u8);
+    self.emit_byte(index as u8);
+  }
+
+  fn identifier_constant(&mut self, name: &str) -> usize {
+    self.make_constant(Value::String(name.to_string()))
+  }
+
+  /**
+    Updates the current line from the token of `instruction`, instructions
+    without a token keep the line of the previous one.
+  */
+  fn set_line(&mut self, instruction: &IRInstruction) {
+    let token = match instruction {
+      IRInstruction::Variable(variable) => match &variable.token {
+        Some(token) => token,
+        None => return,
+      },
+      IRInstruction::Function(function) => &function.token,
+      IRInstruction::If(ir_if) => &ir_if.token,
+      IRInstruction::While(ir_while) => &ir_while.token,
+      IRInstruction::Literal(literal) => &literal.token,
+      IRInstruction::Binary(binary) => &binary.token,
+      IRInstruction::Unary(unary) => &unary.token,
+      IRInstruction::Logical(logical) => &logical.token,
+      IRInstruction::Call(call) => &call.token,
+      IRInstruction::Assign(assign) => &assign.token,
+      IRInstruction::Return(ir_return) => &ir_return.token,
+      IRInstruction::Array(array) => &array.token,
+      IRInstruction::ForIn(for_in) => &for_in.token,
+      IRInstruction::Break(ir_break) => &ir_break.token,
+      IRInstruction::Continue(ir_continue) => &ir_continue.token,
+      IRInstruction::This(this) => &this.token,
+      IRInstruction::Get(get) => &get.token,
+      IRInstruction::Set(set) => &set.token,
+      IRInstruction::New(new) => &new.token,
+      IRInstruction::MethodCall(call) => &call.token,
+      IRInstruction::EnumValue(enum_value) => &enum_value.token,
+      IRInstruction::Match(ir_match) => &ir_match.token,
+      IRInstruction::Switch(switch) => &switch.token,
+      IRInstruction::Lambda(lambda) => &lambda.token,
+      IRInstruction::TemplateLiteral(template) => &template.token,
+      IRInstruction::CallValue(call) => &call.token,
+      IRInstruction::Index(index) => &index.token,
+      IRInstruction::SetIndex(set) => &set.token,
+      _ => return,
+    };
+
+    self.line = token.span.line;
+  }
+
+  /**
+  Writes a jump with a placeholder offset and returns the position of the
+  offset so it can be patched once the target is known.
+  */
+  fn emit_jump(&mut self, op: OpCode) -> usize {
+    self.emit_op(op);
+    self.emit_byte(0xff);
+    self.emit_byte(0xff);
+
+    self.current_bytecode().code.len() - 2
+  }
+
+  fn patch_jump(&mut self, offset: usize) {
+    let jump = self.current_bytecode().code.len() - offset - 2;
+
+    if jump > u16::MAX as usize {
+      self.error(GeneratorErrorKind::JumpTooLarge);
+    }
+
+    let bytecode = self.current_bytecode();
+    bytecode.code[offset] = (jump >> 8) as u8;
+    bytecode.code[offset + 1] = jump as u8;
+  }
+
+  fn emit_loop(&mut self, loop_start: usize) {
+    self.emit_op(OpCode::Loop);
+
+    let offset = self.current_bytecode().code.len() - loop_start + 2;
+
+    if offset > u16::MAX as usize {
+      self.error(GeneratorErrorKind::LoopTooLarge);
+    }
+
+    self.emit_byte((offset >> 8) as u8);
+    self.emit_byte(offset as u8);
+  }
+}
+
+/**
+  Every module is written to its own `.ignc` file with a single section.
+*/
+impl CodeGenerator for BytecodeGenerator {
+  fn generate_code(
+    &mut self,
+    irs: &HashMap<String, Vec<IRInstruction>>,
+  ) -> Result<Vec<CodeResult>, Vec<Diagnostic>> {
+    self.generate(irs);
+
+    if !self.errors.is_empty() {
+      return Err(
+        self
+          .errors
+          .iter()
+          .map(|error| error.to_diagnostic())
+          .collect(),
+      );
+    }
+
+    Ok(
+      irs
+        .keys()
+        .map(|module_path| {
+          let mut file = BytecodeFile::new();
+          file.add_section(module_path.clone(), self.bytecodes[module_path].clone());
+
+          CodeResult::new(file.to_bytes(), module_path.clone())
+        })
+        .collect(),
+    )
+  }
+
+  fn file_extension(&self) -> &str {
+    "ignc"
+  }
+}
+
+impl Default for BytecodeGenerator {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_constants_are_interned() {
+    let mut bytecode = Bytecode::new();
+
+    let int = bytecode.add_constant(Value::Int(1));
+    let float = bytecode.add_constant(Value::Float(1.0));
+    let string = bytecode.add_constant(Value::String("1".to_string()));
+
+    assert_eq!(bytecode.add_constant(Value::Int(1)), int);
+    assert_eq!(bytecode.add_constant(Value::Float(1.0)), float);
+    assert_eq!(bytecode.add_constant(Value::String("1".to_string())), string);
+    assert_ne!(bytecode.add_constant(Value::Float(-0.0)), bytecode.add_constant(Value::Float(0.0)));
+    assert_eq!(bytecode.constants.len(), 5);
+  }
+
+  #[test]
+  fn test_long_constant_operands() {
+    let mut generator = BytecodeGenerator::new();
+    generator
+      .functions
+      .push(FunctionContext::new(FunctionKind::Function));
+
+    for i in 0..300 {
+      generator.emit_constant(OpCode::ConstantInt, Value::Int(i));
+    }
+
+    let bytecode = generator.functions.pop().unwrap().bytecode;
+
+    assert_eq!(bytecode.code[0], OpCode::ConstantInt as u8);
+    assert_eq!(bytecode.code[255 * 2], OpCode::ConstantInt as u8);
+    assert_eq!(bytecode.code[256 * 2], OpCode::ConstantIntLong as u8);
+    assert_eq!(bytecode.read_operand(256 * 2 + 1, 3), 256);
+    assert_eq!(bytecode.code.len(), 256 * 2 + 44 * 4);
+  }
+
+  /**
+    Generator that lowered the script `source`.
+  */
+  fn generator(source: &str) -> BytecodeGenerator {
+    let mut lexer = lexer::Lexer::new(source, "main.ign".to_string());
+    lexer.scan_tokens();
+
+    let mut parser = parser::Parser::new(lexer.tokens);
+    let statements = parser.parse();
+    assert!(parser.diagnostics.is_empty());
+    let mut analyzer = analyzer::Analyzer::new("main.ign".to_string());
+    analyzer.analyze(&statements);
+    assert!(analyzer.diagnostics.is_empty());
+
+    let mut generator = BytecodeGenerator::new();
+    generator.generate(&analyzer.irs);
+
+    generator
+  }
+
+  /**
+    Bytecode of the script `source`.
+  */
+  fn script(source: &str) -> Bytecode {
+    generator(source).bytecodes.remove("main.ign").unwrap()
+  }
+
+  /**
+    Ops of `bytecode` with their lines.
+  */
+  fn ops(bytecode: &Bytecode) -> Vec<(OpCode, usize)> {
+    let mut ops = vec![];
+    let mut offset = 0;
+
+    while offset < bytecode.code.len() {
+      let op = OpCode::from_byte(bytecode.code[offset]).unwrap();
+      ops.push((op, bytecode.lines[offset]));
+      offset += 1 + op.operand_width();
+    }
+
+    ops
+  }
+
+  /**
+    Ops of the code of the function `name` declared by `source`.
+  */
+  fn function_ops(source: &str, name: &str) -> Vec<OpCode> {
+    let function = script(source)
+      .constants
+      .iter()
+      .find_map(|constant| match constant {
+        Value::Function(function) if function.name == name => Some(function.clone()),
+        _ => None,
+      })
+      .unwrap();
+
+    ops(&function.bytecode)
+      .into_iter()
+      .map(|(op, _)| op)
+      .collect()
+  }
+
+  #[test]
+  fn test_declaration_lines() {
+    let bytecode = script(
+      "import { println } from \"std:io\";\n\nfunction f(n: int): int {\n  if (n > 2) {\n    return n;\n  }\n  return 0;\n}\n\nlet x: int = f(3);",
+    );
+    let ops = ops(&bytecode);
+    let line = |op: OpCode| ops.iter().find(|(o, _)| *o == op).unwrap().1;
+
+    assert_eq!(line(OpCode::Native), 1);
+    assert_eq!(line(OpCode::Function), 3);
+    assert_eq!(line(OpCode::Call), 10);
+  }
+
+  #[test]
+  fn test_switch_jump_table() {
+    let ops = function_ops(
+      "function f(n: int): int {\n  switch n {\n    case 3: return 1;\n    case 1: return 2;\n    case 2: return 3;\n    default: return 4;\n  }\n}",
+      "f",
+    );
+
+    assert!(ops.contains(&OpCode::JumpTable));
+    assert!(!ops.contains(&OpCode::Equal));
+
+    let ops = function_ops(
+      "function f(s: string): int {\n  switch s {\n    case \"a\": return 1;\n    case \"b\": return 2;\n    case \"c\": return 3;\n  }\n  return 0;\n}",
+      "f",
+    );
+
+    assert!(!ops.contains(&OpCode::JumpTable));
+
+    let ops = function_ops(
+      "function f(n: int): int {\n  switch n {\n    case 1: return 1;\n    case 50: return 2;\n    case 100: return 3;\n  }\n  return 0;\n}",
+      "f",
+    );
+
+    assert!(!ops.contains(&OpCode::JumpTable));
+  }
+
+  #[test]
+  fn test_format_limits_are_errors() {
+    let locals: String = (0..300)
+      .map(|i| format!("  let x{}: int = {};\n", i, i))
+      .collect();
+    let source = format!("function f(): void {{\n{}}}", locals);
+
+    let errors = generator(&source).errors;
+
+    assert_eq!(
+      errors,
+      vec![GeneratorError::new(
+        GeneratorErrorKind::TooManyLocals,
+        257,
+        "main.ign".to_string()
+      )]
+    );
+  }
 }
@@ -0,0 +1,114 @@
+use std::{collections::HashMap, fs, io, path::Path};
+
+use analyzer::ir::instruction::IRInstruction;
+use diagnostic::Diagnostic;
+
+/**
+  Output of a code generator for a single module.
+
+  - code: contents of the output file, text for the transpilers and raw bytes
+    for the binary targets.
+  - module_path: path of the source module the code was generated from.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct CodeResult {
+  pub code: Vec<u8>,
+  pub module_path: String,
+}
+
+impl CodeResult {
+  pub fn new(code: Vec<u8>, module_path: String) -> Self {
+    Self { code, module_path }
+  }
+}
+
+/**
+  Common interface of every backend of `ignisc build`.
+
+  A backend lowers the IR of each analyzed module and decides how the output
+  files are named, so new targets can be plugged into the build without
+  touching the driver.
+*/
+pub trait CodeGenerator {
+  /**
+    Lowers the IR of every module and returns one result per emitted file,
+    or the errors of the modules that can not be lowered.
+  */
+  fn generate_code(
+    &mut self,
+    irs: &HashMap<String, Vec<IRInstruction>>,
+  ) -> Result<Vec<CodeResult>, Vec<Diagnostic>>;
+
+  /**
+    Extension of the emitted files, without the leading dot.
+  */
+  fn file_extension(&self) -> &str;
+
+  /**
+    Path where the code generated for `module_path` is written.
+    By default the module keeps its relative location inside `build/` and the
+    `.ign` extension is replaced by `file_extension`.
+  */
+  fn output_path(&self, module_path: &str) -> String {
+    let module = module_path.strip_suffix(".ign").unwrap_or(module_path);
+
+    format!("build/{}.{}", module, self.file_extension())
+  }
+
+  /**
+    Writes every result to its `output_path`, creating the directories that
+    do not exist yet.
+  */
+  fn write_code(&self, code_results: &[CodeResult]) -> io::Result<()> {
+    for code_result in code_results {
+      let build_path = self.output_path(&code_result.module_path);
+
+      if let Some(parent) = Path::new(&build_path).parent() {
+        fs::create_dir_all(parent)?;
+      }
+
+      fs::write(build_path, &code_result.code)?;
+    }
+
+    Ok(())
+  }
+
+  /**
+    Runs the code written for `module_path` after the build, the error is the
+    message to show. Backends whose output can not be run do nothing.
+  */
+  fn run(&self, _module_path: &str) -> Result<(), String> {
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  struct TestGenerator;
+
+  impl CodeGenerator for TestGenerator {
+    fn generate_code(
+      &mut self,
+      _irs: &HashMap<String, Vec<IRInstruction>>,
+    ) -> Result<Vec<CodeResult>, Vec<Diagnostic>> {
+      Ok(vec![])
+    }
+
+    fn file_extension(&self) -> &str {
+      "out"
+    }
+  }
+
+  #[test]
+  fn test_output_path() {
+    let generator = TestGenerator;
+
+    assert_eq!(
+      generator.output_path("example/math/src/main.ign"),
+      "build/example/math/src/main.out"
+    );
+    assert_eq!(generator.output_path("main"), "build/main.out");
+  }
+}
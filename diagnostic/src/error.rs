@@ -54,6 +54,29 @@ pub enum DiagnosticError {
   ArrayElementTypeMismatch(Token),
   ModuleNotFound(Token),
   ImportedFunctionIsNotExported(Token),
+  ThisOutsideMethod(Token),
+  UndefinedClass(Token),
+  UndefinedProperty(String, Token),
+  PrivateMemberAccess(String, Token),
+  ReadonlyPropertyAssignment(String, Token),
+  InvalidMemberAccess(DataType, Token),
+  EnumAlreadyDefined(String, Token),
+  UndefinedEnumVariant(String, Token),
+  InvalidEnumValue(String, Token),
+  NonExhaustiveMatch(String, Vec<String>, Token),
+  UnreachableMatchArm(Token),
+  DuplicateSwitchCase(String, Token),
+  InvalidTemplateExpression(DataType, Token),
+  NotIndexable(DataType, Token),
+  InvalidIndexType(DataType, Token),
+  TypeAlreadyDefined(String, Token),
+  UndefinedInterface(Token),
+  MissingInterfaceMember(String, String, String, Token),
+  InterfaceMemberMismatch(String, String, DataType, DataType, Token),
+  CannotInferType(Token),
+  ReturnTypeMismatch(DataType, DataType, Token),
+  MissingReturn(Token),
+  UnassignedVariable(Token),
 }
 
 impl DiagnosticError {
@@ -146,6 +169,73 @@ impl DiagnosticError {
       AnalyzerDiagnosticError::ContinueOutsideLoop(token) => {
         DiagnosticError::ContinueOutsideLoop(token)
       }
+      AnalyzerDiagnosticError::ThisOutsideMethod(token) => {
+        DiagnosticError::ThisOutsideMethod(token)
+      }
+      AnalyzerDiagnosticError::UndefinedClass(token) => DiagnosticError::UndefinedClass(token),
+      AnalyzerDiagnosticError::UndefinedProperty(class_name, token) => {
+        DiagnosticError::UndefinedProperty(class_name, token)
+      }
+      AnalyzerDiagnosticError::PrivateMemberAccess(class_name, token) => {
+        DiagnosticError::PrivateMemberAccess(class_name, token)
+      }
+      AnalyzerDiagnosticError::ReadonlyPropertyAssignment(class_name, token) => {
+        DiagnosticError::ReadonlyPropertyAssignment(class_name, token)
+      }
+      AnalyzerDiagnosticError::InvalidMemberAccess(data_type, token) => {
+        DiagnosticError::InvalidMemberAccess(data_type, token)
+      }
+      AnalyzerDiagnosticError::EnumAlreadyDefined(name, token) => {
+        DiagnosticError::EnumAlreadyDefined(name, token)
+      }
+      AnalyzerDiagnosticError::UndefinedEnumVariant(enum_name, token) => {
+        DiagnosticError::UndefinedEnumVariant(enum_name, token)
+      }
+      AnalyzerDiagnosticError::InvalidEnumValue(enum_name, token) => {
+        DiagnosticError::InvalidEnumValue(enum_name, token)
+      }
+      AnalyzerDiagnosticError::NonExhaustiveMatch(enum_name, missing, token) => {
+        DiagnosticError::NonExhaustiveMatch(enum_name, missing, token)
+      }
+      AnalyzerDiagnosticError::UnreachableMatchArm(token) => {
+        DiagnosticError::UnreachableMatchArm(token)
+      }
+      AnalyzerDiagnosticError::DuplicateSwitchCase(case, token) => {
+        DiagnosticError::DuplicateSwitchCase(case, token)
+      }
+      AnalyzerDiagnosticError::InvalidTemplateExpression(data_type, token) => {
+        DiagnosticError::InvalidTemplateExpression(data_type, token)
+      }
+      AnalyzerDiagnosticError::NotIndexable(data_type, token) => {
+        DiagnosticError::NotIndexable(data_type, token)
+      }
+      AnalyzerDiagnosticError::InvalidIndexType(data_type, token) => {
+        DiagnosticError::InvalidIndexType(data_type, token)
+      }
+      AnalyzerDiagnosticError::TypeAlreadyDefined(name, token) => {
+        DiagnosticError::TypeAlreadyDefined(name, token)
+      }
+      AnalyzerDiagnosticError::UndefinedInterface(token) => {
+        DiagnosticError::UndefinedInterface(token)
+      }
+      AnalyzerDiagnosticError::MissingInterfaceMember(class, interface, member, token) => {
+        DiagnosticError::MissingInterfaceMember(class, interface, member, token)
+      }
+      AnalyzerDiagnosticError::InterfaceMemberMismatch(
+        interface,
+        member,
+        expected,
+        found,
+        token,
+      ) => DiagnosticError::InterfaceMemberMismatch(interface, member, expected, found, token),
+      AnalyzerDiagnosticError::CannotInferType(token) => DiagnosticError::CannotInferType(token),
+      AnalyzerDiagnosticError::ReturnTypeMismatch(expected, found, token) => {
+        DiagnosticError::ReturnTypeMismatch(expected, found, token)
+      }
+      AnalyzerDiagnosticError::MissingReturn(token) => DiagnosticError::MissingReturn(token),
+      AnalyzerDiagnosticError::UnassignedVariable(token) => {
+        DiagnosticError::UnassignedVariable(token)
+      }
     }
   }
 
@@ -321,6 +411,75 @@ impl DiagnosticError {
       DiagnosticError::ContinueOutsideLoop(token) => {
         diagnostics.report_continue_outside_loop(token);
       }
+      DiagnosticError::ThisOutsideMethod(token) => {
+        diagnostics.report_this_outside_method(token);
+      }
+      DiagnosticError::UndefinedClass(token) => {
+        diagnostics.report_undefined_class(token);
+      }
+      DiagnosticError::UndefinedProperty(class_name, token) => {
+        diagnostics.report_undefined_property(class_name, token);
+      }
+      DiagnosticError::PrivateMemberAccess(class_name, token) => {
+        diagnostics.report_private_member_access(class_name, token);
+      }
+      DiagnosticError::ReadonlyPropertyAssignment(class_name, token) => {
+        diagnostics.report_readonly_property_assignment(class_name, token);
+      }
+      DiagnosticError::InvalidMemberAccess(data_type, token) => {
+        diagnostics.report_invalid_member_access(data_type, token);
+      }
+      DiagnosticError::EnumAlreadyDefined(name, token) => {
+        diagnostics.report_enum_already_defined(name, token);
+      }
+      DiagnosticError::UndefinedEnumVariant(enum_name, token) => {
+        diagnostics.report_undefined_enum_variant(enum_name, token);
+      }
+      DiagnosticError::InvalidEnumValue(enum_name, token) => {
+        diagnostics.report_invalid_enum_value(enum_name, token);
+      }
+      DiagnosticError::NonExhaustiveMatch(enum_name, missing, token) => {
+        diagnostics.report_non_exhaustive_match(enum_name, missing, token);
+      }
+      DiagnosticError::UnreachableMatchArm(token) => {
+        diagnostics.report_unreachable_match_arm(token);
+      }
+      DiagnosticError::DuplicateSwitchCase(case, token) => {
+        diagnostics.report_duplicate_switch_case(case, token);
+      }
+      DiagnosticError::InvalidTemplateExpression(data_type, token) => {
+        diagnostics.report_invalid_template_expression(data_type, token);
+      }
+      DiagnosticError::NotIndexable(data_type, token) => {
+        diagnostics.report_not_indexable(data_type, token);
+      }
+      DiagnosticError::InvalidIndexType(data_type, token) => {
+        diagnostics.report_invalid_index_type(data_type, token);
+      }
+      DiagnosticError::TypeAlreadyDefined(name, token) => {
+        diagnostics.report_type_already_defined(name, token);
+      }
+      DiagnosticError::UndefinedInterface(token) => {
+        diagnostics.report_undefined_interface(token);
+      }
+      DiagnosticError::MissingInterfaceMember(class, interface, member, token) => {
+        diagnostics.report_missing_interface_member(class, interface, member, token);
+      }
+      DiagnosticError::InterfaceMemberMismatch(interface, member, expected, found, token) => {
+        diagnostics.report_interface_member_mismatch(interface, member, expected, found, token);
+      }
+      DiagnosticError::CannotInferType(token) => {
+        diagnostics.report_cannot_infer_type(token);
+      }
+      DiagnosticError::ReturnTypeMismatch(expected, found, token) => {
+        diagnostics.report_return_type_mismatch(expected, found, token);
+      }
+      DiagnosticError::MissingReturn(token) => {
+        diagnostics.report_missing_return(token);
+      }
+      DiagnosticError::UnassignedVariable(token) => {
+        diagnostics.report_unassigned_variable(token);
+      }
     }
   }
 }
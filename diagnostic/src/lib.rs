@@ -10,7 +10,7 @@ use {
   analyzer::analyzer_value::AnalyzerValue,
 };
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum DiagnosticLevel {
   Warning,
   Error,
@@ -25,7 +25,7 @@ impl Display for DiagnosticLevel {
   }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Diagnostic {
   pub code: DiagnosticLevel,
   pub span: Box<TextSpan>,
@@ -332,7 +332,11 @@ impl DiagnosticList {
 
   fn report_type_mismatch_unary(&mut self, right: &DataType, token: &Token) {
     self.report_error(
-      format!("Type mismatch, expected '{}'", right.to_string()),
+      format!(
+        "Operator '{}' can not be applied to a value of type '{}'",
+        token.span.literal,
+        right.to_string()
+      ),
       token.span.clone(),
     );
   }
@@ -459,4 +463,236 @@ impl DiagnosticList {
   fn report_continue_outside_loop(&mut self, token: &Token) {
     self.report_error(format!("Continue outside loop"), token.span.clone());
   }
+
+  fn report_this_outside_method(&mut self, token: &Token) {
+    self.report_error(
+      "'this' can only be used inside a method".to_string(),
+      token.span.clone(),
+    );
+  }
+
+  fn report_undefined_class(&mut self, token: &Token) {
+    self.report_error(
+      format!("Undefined class '{}'", token.span.literal),
+      token.span.clone(),
+    );
+  }
+
+  fn report_undefined_property(&mut self, class_name: &str, token: &Token) {
+    self.report_error(
+      format!(
+        "Class '{}' has no member '{}'",
+        class_name, token.span.literal
+      ),
+      token.span.clone(),
+    );
+  }
+
+  fn report_private_member_access(&mut self, class_name: &str, token: &Token) {
+    self.report_error(
+      format!(
+        "'{}' is private and only accessible inside class '{}'",
+        token.span.literal, class_name
+      ),
+      token.span.clone(),
+    );
+  }
+
+  fn report_readonly_property_assignment(&mut self, class_name: &str, token: &Token) {
+    self.report_error(
+      format!(
+        "'{}' is readonly and can only be assigned in the constructor of '{}'",
+        token.span.literal, class_name
+      ),
+      token.span.clone(),
+    );
+  }
+
+  fn report_invalid_member_access(&mut self, data_type: &DataType, token: &Token) {
+    self.report_error(
+      format!(
+        "Type '{}' has no member '{}'",
+        data_type.to_string(),
+        token.span.literal
+      ),
+      token.span.clone(),
+    );
+  }
+
+  fn report_enum_already_defined(&mut self, name: &str, token: &Token) {
+    self.report_error(
+      format!("Enum '{}' was already defined", name),
+      token.span.clone(),
+    );
+  }
+
+  fn report_undefined_enum_variant(&mut self, enum_name: &str, token: &Token) {
+    self.report_error(
+      format!(
+        "Enum '{}' has no variant '{}'",
+        enum_name, token.span.literal
+      ),
+      token.span.clone(),
+    );
+  }
+
+  fn report_invalid_enum_value(&mut self, enum_name: &str, token: &Token) {
+    self.report_error(
+      format!(
+        "The values of enum '{}' must be string or int literals of the same type",
+        enum_name
+      ),
+      token.span.clone(),
+    );
+  }
+
+  fn report_non_exhaustive_match(&mut self, enum_name: &str, missing: &[String], token: &Token) {
+    self.report_error(
+      format!(
+        "Match on '{}' is not exhaustive, missing: {}",
+        enum_name,
+        missing.join(", ")
+      ),
+      token.span.clone(),
+    );
+  }
+
+  fn report_unreachable_match_arm(&mut self, token: &Token) {
+    self.report_error(
+      format!(
+        "Unreachable match arm '{}', a previous arm matches every value it matches",
+        token.span.literal
+      ),
+      token.span.clone(),
+    );
+  }
+
+  fn report_duplicate_switch_case(&mut self, case: &str, token: &Token) {
+    self.report_error(
+      format!("Duplicate case '{}' in switch", case),
+      token.span.clone(),
+    );
+  }
+
+  fn report_invalid_template_expression(&mut self, data_type: &DataType, token: &Token) {
+    self.report_error(
+      format!(
+        "A value of type '{}' can not be used in a template string",
+        data_type.to_string()
+      ),
+      token.span.clone(),
+    );
+  }
+
+  fn report_not_indexable(&mut self, data_type: &DataType, token: &Token) {
+    self.report_error(
+      format!(
+        "A value of type '{}' can not be indexed",
+        data_type.to_string()
+      ),
+      token.span.clone(),
+    );
+  }
+
+  fn report_invalid_index_type(&mut self, data_type: &DataType, token: &Token) {
+    self.report_error(
+      format!(
+        "An index must be of type 'int', found '{}'",
+        data_type.to_string()
+      ),
+      token.span.clone(),
+    );
+  }
+
+  fn report_type_already_defined(&mut self, name: &str, token: &Token) {
+    self.report_error(
+      format!("Type '{}' was already defined", name),
+      token.span.clone(),
+    );
+  }
+
+  fn report_undefined_interface(&mut self, token: &Token) {
+    self.report_error(
+      format!("Interface '{}' is not defined", token.span.literal),
+      token.span.clone(),
+    );
+  }
+
+  fn report_missing_interface_member(
+    &mut self,
+    class: &str,
+    interface: &str,
+    member: &str,
+    token: &Token,
+  ) {
+    self.report_error(
+      format!(
+        "Class '{}' does not implement '{}' of interface '{}'",
+        class, member, interface
+      ),
+      token.span.clone(),
+    );
+  }
+
+  fn report_interface_member_mismatch(
+    &mut self,
+    interface: &str,
+    member: &str,
+    expected: &DataType,
+    found: &DataType,
+    token: &Token,
+  ) {
+    self.report_error(
+      format!(
+        "'{}' has type '{}' but interface '{}' expects '{}'",
+        member,
+        found.to_string(),
+        interface,
+        expected.to_string()
+      ),
+      token.span.clone(),
+    );
+  }
+
+  fn report_cannot_infer_type(&mut self, token: &Token) {
+    self.report_error(
+      format!(
+        "The type of '{}' can not be inferred, it needs a type annotation",
+        token.span.literal
+      ),
+      token.span.clone(),
+    );
+  }
+
+  fn report_return_type_mismatch(&mut self, expected: &DataType, found: &DataType, token: &Token) {
+    self.report_error(
+      format!(
+        "The function returns '{}', found a return of type '{}'",
+        expected.to_string(),
+        found.to_string()
+      ),
+      token.span.clone(),
+    );
+  }
+
+  fn report_missing_return(&mut self, token: &Token) {
+    self.report_error(
+      format!("Not all paths of '{}' return a value", token.span.literal),
+      token.span.clone(),
+    );
+  }
+
+  fn report_unassigned_variable(&mut self, token: &Token) {
+    self.report_error(
+      format!(
+        "Variable '{}' is used before a value is assigned to it",
+        token.span.literal
+      ),
+      token.span.clone(),
+    );
+  }
+
+  fn report_unreachable_code(&mut self, token: &Token) {
+    self.report_warning("Unreachable code".to_string(), token.span.clone());
+  }
 }
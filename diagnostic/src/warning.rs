@@ -1 +1,26 @@
+use {lexer::token::Token, analyzer::analyzer_warning::AnalyzerDiagnosticWarning};
 
+use super::DiagnosticList;
+
+#[derive(Debug)]
+pub enum DiagnosticWarning {
+  UnreachableCode(Token),
+}
+
+impl DiagnosticWarning {
+  pub fn from_analyzer_warning(warning: AnalyzerDiagnosticWarning) -> Self {
+    match warning {
+      AnalyzerDiagnosticWarning::UnreachableCode(token) => {
+        DiagnosticWarning::UnreachableCode(token)
+      }
+    }
+  }
+
+  pub fn report(&self, diagnostics: &mut DiagnosticList) {
+    match self {
+      DiagnosticWarning::UnreachableCode(token) => {
+        diagnostics.report_unreachable_code(token);
+      }
+    }
+  }
+}
@@ -9,9 +9,7 @@ pub enum DataType {
   Char,
   Null,
   None,
-  Pending,
   Void,
-  Variable(String),
   Array(Box<DataType>),
   Callable(Vec<DataType>, Box<DataType>),
   // TODO: Type non-primitive
@@ -48,8 +46,6 @@ impl DataType {
       DataType::Boolean => "Boolean".to_string(),
       DataType::Char => "Char".to_string(),
       DataType::None => "Null".to_string(),
-      DataType::Pending => "Pending".to_string(),
-      DataType::Variable(name) => name.to_string(),
       DataType::ClassType(name) => name.clone(),
       DataType::GenericType { base, parameters } => {
         let params: Vec<String> = parameters.iter().map(|p| p.to_string()).collect();
@@ -80,6 +80,7 @@ pub enum TokenType {
   Null,
   Return,
   This,
+  New,
   Let,
   Const,
   Mut,
@@ -93,9 +94,14 @@ pub enum TokenType {
   Extends,
   Implements,
   Interface,
+  Type,
   Void,
   Extern,
   Continue,
+  Match,
+  Switch,
+  Case,
+  Default,
 
   Bad,
   Identifier,
@@ -163,6 +169,7 @@ impl TokenType {
       TokenType::Null => "null".to_string(),
       TokenType::Return => "return".to_string(),
       TokenType::This => "this".to_string(),
+      TokenType::New => "new".to_string(),
       TokenType::Let => "let".to_string(),
       TokenType::Const => "const".to_string(),
       TokenType::Mut => "mut".to_string(),
@@ -176,6 +183,7 @@ impl TokenType {
       TokenType::Extends => "extends".to_string(),
       TokenType::Implements => "implements".to_string(),
       TokenType::Interface => "interface".to_string(),
+      TokenType::Type => "type".to_string(),
       TokenType::Void => "void".to_string(),
       TokenType::Bad => "bad".to_string(),
       TokenType::Identifier => "identifier".to_string(),
@@ -183,6 +191,10 @@ impl TokenType {
       TokenType::Extern => "extern".to_string(),
       TokenType::At => "comment".to_string(),
       TokenType::Continue => "continue".to_string(),
+      TokenType::Match => "match".to_string(),
+      TokenType::Switch => "switch".to_string(),
+      TokenType::Case => "case".to_string(),
+      TokenType::Default => "default".to_string(),
       _ => String::new(),
     }
   }
@@ -247,6 +259,7 @@ impl Display for TokenType {
       TokenType::Null => write!(f, "null"),
       TokenType::Return => write!(f, "return"),
       TokenType::This => write!(f, "this"),
+      TokenType::New => write!(f, "new"),
       TokenType::Let => write!(f, "let"),
       TokenType::Const => write!(f, "const"),
       TokenType::Mut => write!(f, "mut"),
@@ -260,6 +273,7 @@ impl Display for TokenType {
       TokenType::Extends => write!(f, "extends"),
       TokenType::Implements => write!(f, "implements"),
       TokenType::Interface => write!(f, "interface"),
+      TokenType::Type => write!(f, "type"),
       TokenType::Bad => write!(f, "bad"),
       TokenType::Identifier => write!(f, "identifier"),
       TokenType::Eof => write!(f, "eof"),
@@ -273,6 +287,10 @@ impl Display for TokenType {
       TokenType::ExpressionStart => write!(f, "${{"),
       TokenType::ExpressionEnd => write!(f, "}}"),
       TokenType::Continue => write!(f, "continue"),
+      TokenType::Match => write!(f, "match"),
+      TokenType::Switch => write!(f, "switch"),
+      TokenType::Case => write!(f, "case"),
+      TokenType::Default => write!(f, "default"),
     }
   }
 }
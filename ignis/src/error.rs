@@ -0,0 +1,53 @@
+use std::fmt::Display;
+
+use bytecode_generator::bytecode_file::BytecodeFileError;
+use diagnostic::Diagnostic;
+use vm::vm_error::VMError;
+
+#[derive(Debug)]
+pub enum IgnisError {
+  /// The file could not be read
+  Io(String, std::io::Error),
+  /// The source has syntax or semantic errors
+  Compile(Vec<Diagnostic>),
+  /// The bytecode file is not valid
+  InvalidBytecode(BytecodeFileError),
+  /// The script failed while running
+  Runtime(VMError),
+  /// The global does not exist
+  UndefinedGlobal(String),
+  /// A host value can not be passed to the script
+  InvalidValue(String),
+}
+
+impl Display for IgnisError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      IgnisError::Io(path, error) => write!(f, "{}: {}", path, error),
+      IgnisError::Compile(diagnostics) => {
+        for (i, diagnostic) in diagnostics.iter().enumerate() {
+          if i > 0 {
+            writeln!(f)?;
+          }
+
+          write!(
+            f,
+            "{}: {}\n[line {}] in {}",
+            diagnostic.code,
+            diagnostic.hint.clone().unwrap_or_default(),
+            diagnostic.span.line,
+            diagnostic.module_path.clone().unwrap_or_default()
+          )?;
+        }
+
+        Ok(())
+      }
+      IgnisError::InvalidBytecode(error) => write!(f, "{}", error),
+      IgnisError::Runtime(error) => write!(f, "{}", error),
+      IgnisError::UndefinedGlobal(name) => write!(f, "Undefined global '{}'", name),
+      IgnisError::InvalidValue(message) => write!(f, "{}", message),
+    }
+  }
+}
+
+impl std::error::Error for IgnisError {}
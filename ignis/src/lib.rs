@@ -0,0 +1,636 @@
+pub mod error;
+pub mod value;
+
+use std::fs;
+
+use analyzer::{
+  ir::instruction::{
+    class::IRClass, function::IRFunction, ir_enum::IREnum, ir_interface::IRInterface,
+    ir_type_alias::IRTypeAlias, IRInstruction,
+  },
+  Analyzer,
+};
+use bytecode_generator::{bytecode_file::BytecodeFile, Bytecode, BytecodeGenerator};
+use diagnostic::{error::DiagnosticError, warning::DiagnosticWarning, DiagnosticList};
+use enums::data_type::DataType;
+use lexer::Lexer;
+use parser::Parser;
+use vm::{heap::GCStats, native::NativeRegistry, VM};
+
+pub use diagnostic::{Diagnostic, DiagnosticLevel};
+pub use error::IgnisError;
+pub use value::Value;
+pub use vm::native::NativeType;
+
+pub type IgnisResult<T> = Result<T, IgnisError>;
+
+/**
+  A compiled module, ready to be run by an `Engine`.
+
+  - declarations: top level variables, functions, classes, enums and types, the engine
+    makes them visible to the programs compiled after this one runs.
+  - warnings: problems found by the compiler that do not stop the program, like
+    code that can not run.
+*/
+#[derive(Debug, Clone)]
+pub struct Program {
+  pub module_path: String,
+  pub bytecode: Bytecode,
+  pub warnings: Vec<Diagnostic>,
+  declarations: Vec<IRInstruction>,
+}
+
+impl Program {
+  pub fn new(module_path: String, bytecode: Bytecode) -> Self {
+    Self {
+      module_path,
+      bytecode,
+      warnings: Vec::new(),
+      declarations: Vec::new(),
+    }
+  }
+
+  /**
+    Loads the first module of an `.ignc` file.
+  */
+  pub fn from_bytes(bytes: &[u8]) -> IgnisResult<Self> {
+    let file = BytecodeFile::from_bytes(bytes).map_err(IgnisError::InvalidBytecode)?;
+
+    match file.sections.into_iter().next() {
+      Some(section) => Ok(Self::new(section.module_path, section.bytecode)),
+      None => Err(IgnisError::InvalidBytecode(
+        bytecode_generator::bytecode_file::BytecodeFileError::UnexpectedEnd,
+      )),
+    }
+  }
+
+  /**
+    Serializes the program in the `.ignc` format read by `ignisc`.
+  */
+  pub fn to_bytes(&self) -> Vec<u8> {
+    let mut file = BytecodeFile::new();
+    file.add_section(self.module_path.clone(), self.bytecode.clone());
+
+    file.to_bytes()
+  }
+}
+
+/**
+  Entry point to compile and run Ignis code from a Rust application.
+
+  The globals defined by a program are kept after it runs, so the host can
+  read them, call the functions the program declared or run more programs
+  that use them.
+
+  - variables: globals defined by the host or by the programs that ran, with
+    their type and mutability.
+  - functions: functions declared by the programs that ran.
+  - classes: classes declared by the programs that ran.
+  - enums: enums declared by the programs that ran.
+  - interfaces, type_aliases: types declared by the programs that ran.
+
+  ```
+  use ignis::{Engine, NativeType, Value};
+
+  let mut engine = Engine::new();
+
+  engine.register_native("host", "double", vec![NativeType::Int], |arguments| {
+    match arguments[0] {
+      Value::Int(int) => Ok(Value::Int(int * 2)),
+      _ => unreachable!(),
+    }
+  });
+
+  engine
+    .eval(
+      "@extern(\"host\")\nfunction double(x: int): int;\nlet result: int = double(21);",
+      "main.ign",
+    )
+    .unwrap();
+
+  assert_eq!(engine.get_global("result"), Some(Value::Int(42)));
+  ```
+*/
+pub struct Engine {
+  vm: VM,
+  variables: Vec<(String, DataType, bool)>,
+  functions: Vec<IRFunction>,
+  classes: Vec<IRClass>,
+  enums: Vec<IREnum>,
+  interfaces: Vec<IRInterface>,
+  type_aliases: Vec<IRTypeAlias>,
+}
+
+impl Engine {
+  /**
+    Engine with the functions of the standard library registered.
+  */
+  pub fn new() -> Self {
+    Self {
+      vm: VM::with_natives(NativeRegistry::with_std()),
+      variables: Vec::new(),
+      functions: Vec::new(),
+      classes: Vec::new(),
+      enums: Vec::new(),
+      interfaces: Vec::new(),
+      type_aliases: Vec::new(),
+    }
+  }
+
+  /**
+    Engine without any native function, only the functions registered by
+    the host are available to the scripts.
+  */
+  pub fn empty() -> Self {
+    Self {
+      vm: VM::with_natives(NativeRegistry::new()),
+      variables: Vec::new(),
+      functions: Vec::new(),
+      classes: Vec::new(),
+      enums: Vec::new(),
+      interfaces: Vec::new(),
+      type_aliases: Vec::new(),
+    }
+  }
+
+  /**
+    Makes `function` available to the scripts that declare
+    `@extern("module") function name(...)`.
+    The arguments are checked against `parameters` before `function` runs and
+    an `Err` becomes a runtime error of the script.
+  */
+  pub fn register_native<F>(
+    &mut self,
+    module: &str,
+    name: &str,
+    parameters: Vec<NativeType>,
+    function: F,
+  ) where
+    F: Fn(&[Value]) -> Result<Value, String> + 'static,
+  {
+    self
+      .vm
+      .natives_mut()
+      .register(module, name, parameters, move |heap, arguments| {
+        let arguments = arguments
+          .iter()
+          .map(|x| Value::from_vm(heap, x))
+          .collect::<Vec<Value>>();
+
+        function(&arguments)?.to_vm(heap)
+      });
+  }
+
+  /**
+    Compiles `source` as the module `module_path`.
+    Imports of other modules are resolved from the file system, relative to
+    the current directory.
+  */
+  pub fn compile(&self, source: &str, module_path: &str) -> IgnisResult<Program> {
+    self.compile_source(source, module_path, false)
+  }
+
+  fn compile_source(
+    &self,
+    source: &str,
+    module_path: &str,
+    return_last_expression: bool,
+  ) -> IgnisResult<Program> {
+    let mut lexer = Lexer::new(source, module_path.to_string());
+    lexer.scan_tokens();
+
+    let mut parser = Parser::new(lexer.tokens);
+    let mut diagnostics = DiagnosticList::new();
+
+    let statements = parser.parse();
+
+    // The statements with syntax errors are skipped, the rest of the module
+    // is still analyzed.
+    for error in DiagnosticError::from_parser_diagnostic(parser.diagnostics) {
+      error.report(&mut diagnostics);
+    }
+
+    let mut analyzer = Analyzer::new(module_path.to_string());
+
+    for (name, data_type, is_mutable) in &self.variables {
+      analyzer.declare_global(name, data_type.clone(), *is_mutable);
+    }
+
+    for function in &self.functions {
+      analyzer.declare_function(function.clone());
+    }
+
+    for class in &self.classes {
+      analyzer.declare_class(class.clone());
+    }
+
+    for ir_enum in &self.enums {
+      analyzer.declare_enum(ir_enum.clone());
+    }
+
+    for interface in &self.interfaces {
+      analyzer.declare_interface(interface.clone());
+    }
+
+    for alias in &self.type_aliases {
+      analyzer.declare_type_alias(alias.clone());
+    }
+
+    analyzer.analyze(&statements);
+
+    for error in analyzer.diagnostics {
+      DiagnosticError::from_evaluator_error(error).report(&mut diagnostics);
+    }
+
+    if !diagnostics.diagnostics.is_empty() {
+      return Err(IgnisError::Compile(diagnostics.diagnostics));
+    }
+
+    for warning in analyzer.warnings {
+      DiagnosticWarning::from_analyzer_warning(warning).report(&mut diagnostics);
+    }
+
+    let mut generator = BytecodeGenerator::new();
+    generator.return_last_expression = return_last_expression;
+    generator.generate(&analyzer.irs);
+
+    if !generator.errors.is_empty() {
+      let errors = generator.errors.iter().map(|error| error.to_diagnostic());
+
+      return Err(IgnisError::Compile(errors.collect()));
+    }
+
+    let bytecode = generator.bytecodes.remove(module_path).unwrap_or_default();
+
+    let mut program = Program::new(module_path.to_string(), bytecode);
+    program.warnings = diagnostics.diagnostics;
+    program.declarations = analyzer
+      .irs
+      .remove(module_path)
+      .unwrap_or_default()
+      .into_iter()
+      .filter(|instruction| match instruction {
+        IRInstruction::Variable(variable) => variable.metadata.is_declaration,
+        IRInstruction::Function(_)
+        | IRInstruction::Class(_)
+        | IRInstruction::Enum(_)
+        | IRInstruction::Interface(_)
+        | IRInstruction::TypeAlias(_) => true,
+        _ => false,
+      })
+      .collect();
+
+    Ok(program)
+  }
+
+  /**
+    Compiles an `.ign` file, or loads it when it is an `.ignc` file.
+  */
+  pub fn compile_file(&self, path: &str) -> IgnisResult<Program> {
+    if path.ends_with(".ignc") {
+      let bytes = fs::read(path).map_err(|e| IgnisError::Io(path.to_string(), e))?;
+      return Program::from_bytes(&bytes);
+    }
+
+    let source = fs::read_to_string(path).map_err(|e| IgnisError::Io(path.to_string(), e))?;
+
+    self.compile(&source, path)
+  }
+
+  /**
+    Runs the top level code of `program`.
+  */
+  pub fn run(&mut self, program: &Program) -> IgnisResult<Value> {
+    let result = self
+      .vm
+      .interpret(program.bytecode.clone(), program.module_path.clone())
+      .map_err(IgnisError::Runtime)?;
+
+    for declaration in &program.declarations {
+      match declaration {
+        IRInstruction::Variable(variable) => self.declare_variable(
+          &variable.name,
+          variable.data_type.clone(),
+          variable.metadata.is_mutable,
+        ),
+        IRInstruction::Function(function) => {
+          self.functions.retain(|f| f.name != function.name);
+          self.functions.push(function.clone());
+        }
+        IRInstruction::Class(class) => {
+          self.classes.retain(|c| c.name != class.name);
+          self.classes.push(class.clone());
+        }
+        IRInstruction::Enum(ir_enum) => {
+          self.enums.retain(|e| e.name != ir_enum.name);
+          self.enums.push(ir_enum.clone());
+        }
+        IRInstruction::Interface(interface) => {
+          self.interfaces.retain(|i| i.name != interface.name);
+          self.interfaces.push(interface.clone());
+        }
+        IRInstruction::TypeAlias(alias) => {
+          self.type_aliases.retain(|a| a.name != alias.name);
+          self.type_aliases.push(alias.clone());
+        }
+        _ => (),
+      }
+    }
+
+    Ok(Value::from_vm(self.vm.heap(), &result))
+  }
+
+  /**
+    Compiles and runs `source` as the module `module_path`, returns the value
+    of the last statement when it is an expression.
+  */
+  pub fn eval(&mut self, source: &str, module_path: &str) -> IgnisResult<Value> {
+    let program = self.compile_source(source, module_path, true)?;
+
+    self.run(&program)
+  }
+
+  /**
+    Calls the global function `name`, declared by a program that already ran.
+  */
+  pub fn call(&mut self, name: &str, arguments: &[Value]) -> IgnisResult<Value> {
+    let callee = match self.vm.get_global(name) {
+      Some(callee) => callee,
+      None => return Err(IgnisError::UndefinedGlobal(name.to_string())),
+    };
+
+    let mut values = vec![];
+
+    for argument in arguments {
+      let value = argument
+        .to_vm(self.vm.heap_mut())
+        .map_err(IgnisError::InvalidValue)?;
+
+      values.push(value);
+    }
+
+    let result = self
+      .vm
+      .call_value(callee, &values)
+      .map_err(IgnisError::Runtime)?;
+
+    Ok(Value::from_vm(self.vm.heap(), &result))
+  }
+
+  pub fn get_global(&self, name: &str) -> Option<Value> {
+    self
+      .vm
+      .get_global(name)
+      .map(|value| Value::from_vm(self.vm.heap(), &value))
+  }
+
+  /**
+    Defines a global that the programs compiled afterwards can read and
+    assign.
+  */
+  pub fn set_global(&mut self, name: &str, value: Value) -> IgnisResult<()> {
+    let data_type = value.data_type();
+    let value = value
+      .to_vm(self.vm.heap_mut())
+      .map_err(IgnisError::InvalidValue)?;
+
+    self.vm.set_global(name, value);
+    self.declare_variable(name, data_type, true);
+
+    Ok(())
+  }
+
+  pub fn gc_stats(&self) -> &GCStats {
+    self.vm.gc_stats()
+  }
+
+  fn declare_variable(&mut self, name: &str, data_type: DataType, is_mutable: bool) {
+    self.variables.retain(|(variable, _, _)| variable != name);
+    self
+      .variables
+      .push((name.to_string(), data_type, is_mutable));
+  }
+}
+
+impl Default for Engine {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn assert_compile_error(result: IgnisResult<Value>) -> Vec<Diagnostic> {
+    match result {
+      Err(IgnisError::Compile(diagnostics)) => diagnostics,
+      other => panic!("Expected a compile error, found {:?}", other),
+    }
+  }
+
+  #[test]
+  fn test_call_script_function() {
+    let mut engine = Engine::new();
+
+    engine
+      .eval(
+        "function add(a: int, b: int): int {\n  return a + b;\n}",
+        "main.ign",
+      )
+      .unwrap();
+
+    let result = engine.call("add", &[Value::Int(40), Value::Int(2)]);
+
+    assert_eq!(result.unwrap(), Value::Int(42));
+  }
+
+  #[test]
+  fn test_declarations_persist_between_evals() {
+    let mut engine = Engine::new();
+
+    engine.eval("let mut count: int = 1;", "repl").unwrap();
+    engine
+      .eval("function twice(x: int): int {\n  return x * 2;\n}", "repl")
+      .unwrap();
+    engine.eval("count = twice(count) + 1;", "repl").unwrap();
+
+    assert_eq!(engine.eval("count;", "repl").unwrap(), Value::Int(3));
+    assert_eq!(engine.eval("twice(count);", "repl").unwrap(), Value::Int(6));
+  }
+
+  #[test]
+  fn test_declared_types_persist_between_evals() {
+    let mut engine = Engine::new();
+
+    engine
+      .eval(
+        "class Counter {\n  private count: int = 0;\n\n  public increment(): int {\n    this.count = this.count + 1;\n    return this.count;\n  }\n}\nenum Option<T> {\n  SOME(T),\n  NONE\n}\ntype Size = int;\ninterface Sized {\n  size(): Size;\n}",
+        "repl",
+      )
+      .unwrap();
+    engine
+      .eval("let counter: Counter = new Counter();", "repl")
+      .unwrap();
+    engine
+      .eval("let value: Option<int> = Option.SOME(42);", "repl")
+      .unwrap();
+
+    assert_eq!(
+      engine.eval("counter.increment();", "repl").unwrap(),
+      Value::Int(1)
+    );
+    assert_eq!(
+      engine.get_global("counter"),
+      Some(Value::Instance("Counter".to_string()))
+    );
+    assert_eq!(
+      engine.get_global("value"),
+      Some(Value::Variant(
+        "Option".to_string(),
+        "SOME".to_string(),
+        vec![Value::Int(42)]
+      ))
+    );
+    assert_eq!(
+      engine
+        .eval(
+          "function measure(value: Sized): Size {\n  return value.size();\n}\nlet size: Size = 1;\nsize;",
+          "repl"
+        )
+        .unwrap(),
+      Value::Int(1)
+    );
+
+    assert_eq!(
+      assert_compile_error(engine.eval("counter.count;", "repl")).len(),
+      1
+    );
+  }
+
+  #[test]
+  fn test_call_closure_of_top_level_block() {
+    let mut engine = Engine::new();
+
+    engine
+      .eval(
+        "let mut f: () -> int = (): int -> 0;\nlet mut g: () -> int = (): int -> 0;\nif (true) {\n  let j: int = 7;\n  f = (): int -> j;\n}\nlet mut i: int = 0;\nwhile (i < 1) {\n  let k: int = 35;\n  g = (): int -> k;\n  i = i + 1;\n}",
+        "main.ign",
+      )
+      .unwrap();
+
+    assert_eq!(engine.call("f", &[]).unwrap(), Value::Int(7));
+    assert_eq!(
+      engine.eval("f() + g();", "main.ign").unwrap(),
+      Value::Int(42)
+    );
+  }
+
+  #[test]
+  fn test_native_values_in_and_out() {
+    let mut engine = Engine::empty();
+
+    engine.register_native("host", "greet", vec![NativeType::String], |arguments| {
+      Ok(Value::String(format!("Hello, {}!", arguments[0])))
+    });
+
+    engine.set_global("name", Value::from("Ignis")).unwrap();
+    engine
+      .eval(
+        "@extern(\"host\")\nfunction greet(name: string): string;\nlet message: string = greet(name);",
+        "main.ign",
+      )
+      .unwrap();
+
+    assert_eq!(
+      engine.get_global("message"),
+      Some(Value::String("Hello, Ignis!".to_string()))
+    );
+  }
+
+  #[test]
+  fn test_errors_are_returned() {
+    let mut engine = Engine::new();
+
+    let diagnostics = assert_compile_error(engine.eval("let x: int = ;", "main.ign"));
+    assert!(!diagnostics.is_empty());
+    assert_eq!(diagnostics[0].span.line, 1);
+
+    engine
+      .eval(
+        "function divide(a: int, b: int): int {\n  return a / b;\n}",
+        "main.ign",
+      )
+      .unwrap();
+
+    match engine.call("divide", &[Value::Int(1), Value::Int(0)]) {
+      Err(IgnisError::Runtime(error)) => assert_eq!(error.line, 2),
+      other => panic!("Expected a runtime error, found {:?}", other),
+    }
+  }
+
+  #[test]
+  fn test_all_errors_are_reported() {
+    let mut engine = Engine::new();
+
+    let diagnostics = assert_compile_error(engine.eval(
+      "function f(): void {\n  let a = ;\n  let b = 1 +;\n}\nlet c = 2;\nlet d = );",
+      "main.ign",
+    ));
+    let lines: Vec<usize> = diagnostics.iter().map(|d| d.span.line).collect();
+    assert_eq!(lines, vec![2, 3, 6]);
+
+    let diagnostics = assert_compile_error(engine.eval(
+      "function add(a: int, b: int): int {\n  let x = missing + 1;\n  let y = x * 2;\n  return add(true, \"b\");\n}\nlet z: string = 1;",
+      "main.ign",
+    ));
+    let lines: Vec<usize> = diagnostics.iter().map(|d| d.span.line).collect();
+    assert_eq!(lines, vec![2, 4, 4, 6]);
+
+    // A syntax error does not hide the errors of the rest of the file.
+    let diagnostics = assert_compile_error(engine.eval(
+      "let x: int = ;\nlet y: int = x + 1;\nlet s: string = 1;\nfunction f(): int {\n  let g: int = );\n}\nlet z: int = 1;\nz = 2;",
+      "main.ign",
+    ));
+    let lines: Vec<usize> = diagnostics.iter().map(|d| d.span.line).collect();
+    assert_eq!(lines, vec![1, 5, 3, 4, 8]);
+  }
+
+  #[test]
+  fn test_bytecode_limits_are_compile_errors() {
+    let engine = Engine::new();
+
+    let locals: String = (0..300)
+      .map(|i| format!("  let x{}: int = {};\n", i, i))
+      .collect();
+    let source = format!("function f(): void {{\n{}}}", locals);
+
+    match engine.compile(&source, "main.ign") {
+      Err(IgnisError::Compile(diagnostics)) => {
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].span.line, 257);
+        assert_eq!(
+          diagnostics[0].hint.as_deref(),
+          Some("Too many local variables in function")
+        );
+      }
+      other => panic!("Expected a compile error, found {:?}", other),
+    }
+  }
+
+  #[test]
+  fn test_warnings_are_returned() {
+    let engine = Engine::new();
+
+    let program = engine
+      .compile(
+        "function one(): int {\n  return 1;\n  one();\n}",
+        "main.ign",
+      )
+      .unwrap();
+
+    assert_eq!(program.warnings.len(), 1);
+    assert_eq!(program.warnings[0].code, DiagnosticLevel::Warning);
+    assert_eq!(program.warnings[0].span.line, 3);
+  }
+}
@@ -0,0 +1,197 @@
+use std::fmt::Display;
+
+use enums::data_type::DataType;
+use vm::{
+  heap::Heap,
+  object::{ObjUpvalue, ObjVariant, Object},
+  vm_value::VMValue,
+};
+
+/**
+  A value exchanged between the host and the scripts.
+  Functions, classes and instances can only be read, they are identified by
+  the name of the function or the class.
+  Variants of enums hold the name of the enum, the name of the variant and
+  the payload.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+  Null,
+  Int(i64),
+  Float(f64),
+  Boolean(bool),
+  String(String),
+  Array(Vec<Value>),
+  Function(String),
+  Class(String),
+  Instance(String),
+  Variant(String, String, Vec<Value>),
+}
+
+impl Value {
+  /**
+    Type the analyzer gives to a global defined with this value.
+  */
+  pub fn data_type(&self) -> DataType {
+    match self {
+      Value::Null => DataType::Null,
+      Value::Int(_) => DataType::Int,
+      Value::Float(_) => DataType::Float,
+      Value::Boolean(_) => DataType::Boolean,
+      Value::String(_) => DataType::String,
+      Value::Array(array) => DataType::Array(Box::new(
+        array.first().map_or(DataType::None, |x| x.data_type()),
+      )),
+      Value::Function(_) | Value::Class(_) => DataType::None,
+      Value::Instance(name) | Value::Variant(name, _, _) => DataType::ClassType(name.clone()),
+    }
+  }
+
+  /**
+    Copies a value out of the VM, strings and arrays are copied as well.
+  */
+  pub fn from_vm(heap: &Heap, value: &VMValue) -> Self {
+    let reference = match value {
+      VMValue::Null => return Value::Null,
+      VMValue::Int(int) => return Value::Int(*int),
+      VMValue::Float(float) => return Value::Float(*float),
+      VMValue::Boolean(boolean) => return Value::Boolean(*boolean),
+      VMValue::Object(reference) => *reference,
+    };
+
+    match heap.get(reference) {
+      Object::String(string) => Value::String(string.to_string()),
+      Object::Array(array) => Value::Array(array.iter().map(|x| Value::from_vm(heap, x)).collect()),
+      Object::Function(function) => Value::Function(function.name.clone()),
+      Object::Native(native) => Value::Function(native.name.clone()),
+      Object::Closure(closure) => Value::from_vm(heap, &VMValue::Object(closure.function)),
+      Object::Upvalue(ObjUpvalue::Closed(value)) => Value::from_vm(heap, value),
+      Object::Upvalue(ObjUpvalue::Open(_)) => Value::Null,
+      Object::Instance(instance) => Value::Instance(instance.class_name.clone()),
+      Object::Class(class) => Value::Class(class.name.clone()),
+      Object::BoundMethod(bound) => Value::from_vm(heap, &VMValue::Object(bound.method)),
+      Object::Variant(variant) => Value::Variant(
+        variant.enum_name.clone(),
+        variant.name.clone(),
+        variant
+          .values
+          .iter()
+          .map(|x| Value::from_vm(heap, x))
+          .collect(),
+      ),
+    }
+  }
+
+  /**
+    Allocates the value in `heap`, fails for functions, classes and instances
+    because the host can not create them.
+  */
+  pub fn to_vm(&self, heap: &mut Heap) -> Result<VMValue, String> {
+    let value = match self {
+      Value::Null => VMValue::Null,
+      Value::Int(int) => VMValue::Int(*int),
+      Value::Float(float) => VMValue::Float(*float),
+      Value::Boolean(boolean) => VMValue::Boolean(*boolean),
+      Value::String(string) => VMValue::Object(heap.intern(string)),
+      Value::Array(array) => {
+        let elements = array
+          .iter()
+          .map(|x| x.to_vm(heap))
+          .collect::<Result<Vec<VMValue>, String>>()?;
+
+        VMValue::Object(heap.alloc(Object::Array(elements)))
+      }
+      Value::Variant(enum_name, name, values) => {
+        let values = values
+          .iter()
+          .map(|x| x.to_vm(heap))
+          .collect::<Result<Vec<VMValue>, String>>()?;
+
+        VMValue::Object(heap.alloc(Object::Variant(ObjVariant::new(
+          enum_name.clone(),
+          name.clone(),
+          values,
+        ))))
+      }
+      Value::Function(name) => {
+        return Err(format!("Can not pass the function '{}' to a script", name))
+      }
+      Value::Class(name) => return Err(format!("Can not pass the class '{}' to a script", name)),
+      Value::Instance(name) => {
+        return Err(format!(
+          "Can not pass an instance of '{}' to a script",
+          name
+        ))
+      }
+    };
+
+    Ok(value)
+  }
+}
+
+impl Display for Value {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Value::Null => write!(f, "null"),
+      Value::Int(int) => write!(f, "{}", int),
+      Value::Float(float) => write!(f, "{}", float),
+      Value::Boolean(boolean) => write!(f, "{}", boolean),
+      Value::String(string) => write!(f, "{}", string),
+      Value::Array(array) => {
+        let elements = array.iter().map(|x| x.to_string()).collect::<Vec<String>>();
+
+        write!(f, "[{}]", elements.join(", "))
+      }
+      Value::Function(name) => write!(f, "<fn {}>", name),
+      Value::Class(name) => write!(f, "<class {}>", name),
+      Value::Instance(name) => write!(f, "<{} instance>", name),
+      Value::Variant(enum_name, name, values) if values.is_empty() => {
+        write!(f, "{}.{}", enum_name, name)
+      }
+      Value::Variant(enum_name, name, values) => {
+        let values = values
+          .iter()
+          .map(|x| x.to_string())
+          .collect::<Vec<String>>();
+
+        write!(f, "{}.{}({})", enum_name, name, values.join(", "))
+      }
+    }
+  }
+}
+
+impl From<i64> for Value {
+  fn from(value: i64) -> Self {
+    Value::Int(value)
+  }
+}
+
+impl From<f64> for Value {
+  fn from(value: f64) -> Self {
+    Value::Float(value)
+  }
+}
+
+impl From<bool> for Value {
+  fn from(value: bool) -> Self {
+    Value::Boolean(value)
+  }
+}
+
+impl From<&str> for Value {
+  fn from(value: &str) -> Self {
+    Value::String(value.to_string())
+  }
+}
+
+impl From<String> for Value {
+  fn from(value: String) -> Self {
+    Value::String(value)
+  }
+}
+
+impl<T: Into<Value>> From<Vec<T>> for Value {
+  fn from(value: Vec<T>) -> Self {
+    Value::Array(value.into_iter().map(|x| x.into()).collect())
+  }
+}
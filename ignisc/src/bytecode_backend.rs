@@ -0,0 +1,79 @@
+use std::{collections::HashMap, fs};
+
+use analyzer::ir::instruction::IRInstruction;
+use bytecode_generator::{BytecodeGenerator, bytecode_file::BytecodeFile};
+use code_generator::{CodeGenerator, CodeResult};
+use diagnostic::Diagnostic;
+use vm::VM;
+
+/**
+  Bytecode backend of `ignisc build`, the modules are written to `.ignc`
+  files and the built module is run on the VM.
+*/
+pub struct BytecodeBackend {
+  generator: BytecodeGenerator,
+  print_gc_stats: bool,
+}
+
+impl BytecodeBackend {
+  pub fn new(print_gc_stats: bool) -> Self {
+    Self {
+      generator: BytecodeGenerator::new(),
+      print_gc_stats,
+    }
+  }
+}
+
+impl CodeGenerator for BytecodeBackend {
+  fn generate_code(
+    &mut self,
+    irs: &HashMap<String, Vec<IRInstruction>>,
+  ) -> Result<Vec<CodeResult>, Vec<Diagnostic>> {
+    self.generator.generate_code(irs)
+  }
+
+  fn file_extension(&self) -> &str {
+    self.generator.file_extension()
+  }
+
+  fn run(&self, module_path: &str) -> Result<(), String> {
+    run_bytecode(
+      &self.output_path(module_path),
+      Some(module_path),
+      self.print_gc_stats,
+    )
+  }
+}
+
+/**
+  Loads an `.ignc` file and executes the section of `module_path`, or the
+  first section when no module is given.
+*/
+pub fn run_bytecode(
+  file_path: &str,
+  module_path: Option<&str>,
+  print_gc_stats: bool,
+) -> Result<(), String> {
+  let bytes = fs::read(file_path).map_err(|error| format!("{}: {}", file_path, error))?;
+  let file =
+    BytecodeFile::from_bytes(&bytes).map_err(|error| format!("{}: {}", file_path, error))?;
+
+  let section = match module_path {
+    Some(module_path) => file.section(module_path),
+    None => file.sections.first(),
+  };
+
+  let section = match section {
+    Some(section) => section.clone(),
+    None => return Err(format!("{}: no bytecode for the module", file_path)),
+  };
+
+  let mut vm = VM::new();
+  let result = vm.interpret(section.bytecode, section.module_path);
+
+  if print_gc_stats {
+    println!("{}", vm.gc_stats());
+  }
+
+  result.map(|_| ()).map_err(|error| error.to_string())
+}
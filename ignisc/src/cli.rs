@@ -1,4 +1,8 @@
 use clap::{Parser, ValueEnum, Subcommand};
+use code_generator::CodeGenerator;
+use to_lua::TranspilerToLua;
+
+use crate::bytecode_backend::BytecodeBackend;
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
 pub enum DebugPrint {
@@ -14,6 +18,10 @@ pub enum DebugPrint {
   Analyzer,
   /// Print the IR struct
   Ir,
+  /// Print the disassembled bytecode
+  Bytecode,
+  /// Print the garbage collector statistics after running the bytecode
+  Gc,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
@@ -26,6 +34,23 @@ pub enum Backend {
   LLVM,
 }
 
+impl Backend {
+  /**
+    Code generator used by `ignisc build` for this backend, `None` when the
+    backend is not implemented yet. `debug` are the `--debug` options of the
+    build.
+  */
+  pub fn code_generator(&self, debug: &[DebugPrint]) -> Option<Box<dyn CodeGenerator>> {
+    match self {
+      Backend::Lua => Some(Box::new(TranspilerToLua::new())),
+      Backend::Bytecode => Some(Box::new(BytecodeBackend::new(
+        debug.contains(&DebugPrint::Gc),
+      ))),
+      Backend::LLVM => None,
+    }
+  }
+}
+
 #[derive(Parser, Debug, Clone, PartialEq)]
 pub struct BuildCommand {
 	pub file_path: String,
@@ -34,6 +59,8 @@ pub struct BuildCommand {
 #[derive(Subcommand, Clone, PartialEq)]
 pub enum SubCommand {
   Build(BuildCommand),
+  /// Start an interactive session running on the bytecode VM
+  Repl,
 }
 
 #[derive(Parser)]
@@ -1,41 +1,34 @@
 use std::{
-  io::{self, Write, BufRead},
   env,
   process::exit,
   fs,
   backtrace::Backtrace,
+  collections::HashMap,
 };
 
+mod bytecode_backend;
 mod cli;
+mod repl;
 
 use analyzer::{
   Analyzer,
   debug::{display_block, display_ir},
+  ir::instruction::IRInstruction,
 };
+use bytecode_backend::run_bytecode;
+use bytecode_generator::{BytecodeGenerator, debug::BytecodeDebug};
 use clap::Parser as ClapParser;
-use cli::{Cli, DebugPrint, Backend, SubCommand};
+use cli::{Cli, DebugPrint, SubCommand};
 use parser::Parser;
 use lexer::Lexer;
 use ast::Ast;
-use to_lua::TranspilerToLua;
-use diagnostic::{DiagnosticList, error::DiagnosticError};
-
-struct CodeResult {
-  pub code: String,
-  pub file_name: String,
-}
-
-impl CodeResult {
-  pub fn new(code: String, file_name: String) -> Self {
-    Self { code, file_name }
-  }
-}
+use diagnostic::{DiagnosticLevel, DiagnosticList, error::DiagnosticError, warning::DiagnosticWarning};
+use repl::Repl;
 
 struct App {
   pub args: Cli,
   pub file_path: String,
   pub build: bool,
-  pub relp: bool,
   pub source: String,
 }
 
@@ -49,13 +42,16 @@ impl App {
         file_path = b.file_path.clone();
         build = true;
       }
+      SubCommand::Repl => {
+        file_path = String::new();
+        build = false;
+      }
     };
 
     Self {
       args,
       file_path,
       build,
-      relp: false,
       source: String::new(),
     }
   }
@@ -64,50 +60,58 @@ impl App {
     for diagnostic in diagnostics.diagnostics.iter() {
       println!("- {}", diagnostic.module_path.as_ref().unwrap());
       println!("{}: {}", diagnostic.code, diagnostic.hint.as_ref().unwrap());
-
-      if !self.relp {
-        println!("{} | {}", diagnostic.span.line, diagnostic.span.literal);
-        println!("Column: {}", diagnostic.span.end - diagnostic.span.start);
-      }
+      println!("{} | {}", diagnostic.span.line, diagnostic.span.literal);
+      println!("Column: {}", diagnostic.span.end - diagnostic.span.start);
     }
   }
 
-  pub fn create_lua_files(&self, code_results: Vec<CodeResult>) {
-    for code_result in code_results {
-      let mut path = code_result.file_name.split("/").collect::<Vec<&str>>();
-      let code = code_result.code.clone();
-
-      let mut name = path.last().unwrap().replace(r".ign", "");
-
-      name.push_str(".lua");
-      path.pop();
-
-      let mut build_path = "build/".to_string() + path.join("/").as_str();
-
-      fs::create_dir_all(build_path.clone()).unwrap();
+  pub fn run_file(&mut self) -> Result<(), ()> {
+    if self.file_path.ends_with(".ignc") {
+      let print_gc_stats = self.args.debug.contains(&DebugPrint::Gc);
 
-      build_path.push_str(format!("/{}", &name).as_str());
+      if let Err(error) = run_bytecode(&self.file_path, None, print_gc_stats) {
+        println!("{}", error);
+        exit(1);
+      }
 
-      fs::write(build_path, code).unwrap();
+      return Ok(());
     }
-  }
 
-  pub fn run_file(&mut self) -> Result<(), ()> {
     match fs::read_to_string(self.file_path.clone()) {
       Ok(content) => {
         self.source = content;
 
-        let result = self.run()?;
+        let irs = self.run()?;
 
-        match self.args.backend {
-          Backend::Lua => {
-            self.create_lua_files(result);
-          }
-          _ => {
+        let mut generator = match self.args.backend.code_generator(&self.args.debug) {
+          Some(generator) => generator,
+          None => {
             println!("Backend not implemented");
+            return Ok(());
+          }
+        };
+
+        let code_results = match generator.generate_code(&irs) {
+          Ok(code_results) => code_results,
+          Err(errors) => {
+            let mut diagnostics = DiagnosticList::new();
+            diagnostics.diagnostics = errors;
+
+            self.display_diagnostic(&diagnostics);
+            exit(1);
           }
         };
 
+        if let Err(e) = generator.write_code(&code_results) {
+          println!("{:?}", e);
+          return Err(());
+        }
+
+        if let Err(error) = generator.run(&self.file_path) {
+          println!("{}", error);
+          exit(1);
+        }
+
         Ok(())
       }
       Err(e) => {
@@ -117,7 +121,7 @@ impl App {
     }
   }
 
-  fn run(&mut self) -> Result<Vec<CodeResult>, ()> {
+  fn run(&mut self) -> Result<HashMap<String, Vec<IRInstruction>>, ()> {
     let mut lexer: Lexer<'_> = Lexer::new(&self.source, self.file_path.clone());
     lexer.scan_tokens();
 
@@ -126,24 +130,17 @@ impl App {
     }
 
     let mut parser = Parser::new(lexer.tokens);
-    let parser_result = parser.parse();
+    let mut ast: Ast = Ast::new(parser.parse());
 
     let mut diagnostics = DiagnosticList::new();
 
-    let mut ast: Ast = match parser_result {
-      Ok(statements) => Ast::new(statements),
-      Err(_) => {
-        DiagnosticError::from_parser_diagnostic(parser.diagnostics)
-          .iter()
-          .for_each(|error| {
-            error.report(&mut diagnostics);
-          });
-
-        self.display_diagnostic(&diagnostics);
-
-        return Err(());
-      }
-    };
+    // The statements with syntax errors are skipped, the rest of the file is
+    // still analyzed.
+    DiagnosticError::from_parser_diagnostic(parser.diagnostics)
+      .iter()
+      .for_each(|error| {
+        error.report(&mut diagnostics);
+      });
 
     if self.args.debug.contains(&DebugPrint::Ast) {
       let pretty_string = serde_json::to_string_pretty(&ast.to_json()).unwrap();
@@ -161,6 +158,10 @@ impl App {
       );
     }
 
+    for warning in &analyzer.warnings {
+      DiagnosticWarning::from_analyzer_warning(warning.clone()).report(&mut diagnostics);
+    }
+
     if self.args.debug.contains(&DebugPrint::Ir) {
       for result in &analyzer.irs {
         println!("IR for {}", result.0);
@@ -170,86 +171,45 @@ impl App {
       }
     }
 
-    let mut transpiler = TranspilerToLua::new();
-    let mut code_results: Vec<CodeResult> = vec![];
-
-    for result in analyzer.irs.iter() {
-      transpiler.transpile(result.1);
-
-      code_results.push(CodeResult::new(transpiler.code.clone(), result.0.clone()));
-    }
-
     // visit(ast.statements, &mut diagnostics, evaluator);
 
     if diagnostics.diagnostics.len() > 0 {
       self.display_diagnostic(&diagnostics);
+    }
 
-      if !self.relp {
-        exit(1);
-      }
+    // Warnings do not stop the compilation.
+    if diagnostics
+      .diagnostics
+      .iter()
+      .any(|diagnostic| diagnostic.code == DiagnosticLevel::Error)
+    {
+      exit(1);
     }
 
     diagnostics.clean_diagnostic();
 
-    return Ok(code_results);
-  }
-
-  pub fn run_prompt(&mut self) -> Result<(), String> {
-    loop {
-      print!("(ignis) > ");
-
-      match io::stdout().flush() {
-        Ok(_) => (),
-        Err(_) => return Err("Could not flush stdout".to_string()),
-      }
-      let mut buffer = String::new();
-      let mut handler = io::stdin().lock();
+    if self.args.debug.contains(&DebugPrint::Bytecode) {
+      let mut generator = BytecodeGenerator::new();
+      generator.generate(&analyzer.irs);
 
-      match handler.read_line(&mut buffer) {
-        Ok(n) => {
-          if n == 0 {
-            println!("");
-            return Ok(());
-          } else if n == 1 {
-            continue;
-          }
-        }
-        Err(_) => return Err("Clound not read line".to_string()),
-      }
-
-      if buffer.trim() == String::from("exit") {
-        println!("Bye!");
-        exit(0);
-      }
-
-      if buffer.contains("load") == true {
-        let path = buffer.split("load").collect::<Vec<&str>>()[1]
-          .trim()
-          .to_string();
-
-        self.file_path = path;
-
-        match self.run_file() {
-          Ok(_) => (),
-          Err(_) => println!("Could not import file"),
-        }
-        continue;
-      }
-
-      self.source = buffer.clone();
-
-      match self.run() {
-        Ok(_) => (),
-        Err(()) => (),
-      }
+      BytecodeDebug::new(generator.bytecodes).print_bytecode();
     }
+
+    return Ok(analyzer.irs);
   }
 }
 
 fn main() {
-  let mut cli = Cli::parse();
+  let cli = Cli::parse();
 
-  cli.backend = Backend::Lua;
+  if cli.subcommand == SubCommand::Repl {
+    if let Err(error) = Repl::new().run() {
+      println!("{}", error);
+      exit(1);
+    }
+
+    return;
+  }
 
   let mut app = App::new(cli);
 
@@ -0,0 +1,165 @@
+use std::io::{self, BufRead, Write};
+
+use ignis::{Engine, IgnisError, Value};
+
+const PROMPT: &str = "(ignis) > ";
+const CONTINUATION_PROMPT: &str = "   ...  > ";
+const MODULE_PATH: &str = "<repl>";
+
+/**
+  Interactive session that runs every entry on the bytecode VM.
+  The variables and functions declared by an entry stay available to the
+  next ones, and the value of an expression entry is printed.
+*/
+pub struct Repl {
+  engine: Engine,
+}
+
+impl Repl {
+  pub fn new() -> Self {
+    Self {
+      engine: Engine::new(),
+    }
+  }
+
+  pub fn run(&mut self) -> Result<(), String> {
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+
+    loop {
+      let mut source = String::new();
+      let mut prompt = PROMPT;
+
+      loop {
+        print!("{}", prompt);
+        io::stdout()
+          .flush()
+          .map_err(|_| "Could not flush stdout".to_string())?;
+
+        match lines.next() {
+          Some(Ok(line)) => {
+            source.push_str(&line);
+            source.push('\n');
+          }
+          Some(Err(_)) => return Err("Could not read line".to_string()),
+          None => {
+            println!();
+            return Ok(());
+          }
+        }
+
+        if !is_incomplete(&source) {
+          break;
+        }
+
+        prompt = CONTINUATION_PROMPT;
+      }
+
+      let entry = source.trim();
+
+      match entry {
+        "" => continue,
+        "exit" => {
+          println!("Bye!");
+          return Ok(());
+        }
+        _ => (),
+      }
+
+      let result = match entry.strip_prefix("load ") {
+        Some(path) => self
+          .engine
+          .compile_file(path.trim())
+          .and_then(|program| self.engine.run(&program)),
+        None => self.engine.eval(&terminate(entry), MODULE_PATH),
+      };
+
+      match result {
+        Ok(Value::Null) => (),
+        Ok(value) => println!("{}", value),
+        Err(error) => report(&error),
+      }
+    }
+  }
+}
+
+fn report(error: &IgnisError) {
+  match error {
+    IgnisError::Compile(diagnostics) => {
+      for diagnostic in diagnostics {
+        println!(
+          "{}: {}",
+          diagnostic.code,
+          diagnostic.hint.clone().unwrap_or_default()
+        );
+      }
+    }
+    _ => println!("{}", error),
+  }
+}
+
+/**
+  Whether `source` has unclosed braces, brackets or parentheses, in which
+  case the REPL keeps reading lines.
+*/
+fn is_incomplete(source: &str) -> bool {
+  let mut depth = 0;
+  let mut quote: Option<char> = None;
+  let mut chars = source.chars().peekable();
+
+  while let Some(c) = chars.next() {
+    if let Some(q) = quote {
+      match c {
+        '\\' => {
+          chars.next();
+        }
+        c if c == q => quote = None,
+        _ => (),
+      }
+
+      continue;
+    }
+
+    match c {
+      '"' | '\'' | '`' => quote = Some(c),
+      '/' if chars.peek() == Some(&'/') => while chars.next_if(|c| *c != '\n').is_some() {},
+      '{' | '[' | '(' => depth += 1,
+      '}' | ']' | ')' => depth -= 1,
+      _ => (),
+    }
+  }
+
+  depth > 0
+}
+
+/**
+  Adds the `;` that ends an expression typed without it.
+*/
+fn terminate(entry: &str) -> String {
+  if entry.ends_with(';') || entry.ends_with('}') {
+    entry.to_string()
+  } else {
+    format!("{};", entry)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_is_incomplete() {
+    assert!(is_incomplete("function add(a: int, b: int): int {\n"));
+    assert!(is_incomplete("let x: int[] = [1,\n"));
+    assert!(!is_incomplete("function f(): void {\n}\n"));
+    assert!(!is_incomplete("let s: string = \"{\";\n"));
+    assert!(!is_incomplete("1 + 2; // {\n"));
+  }
+
+  #[test]
+  fn test_terminate() {
+    assert_eq!(terminate("1 + 2"), "1 + 2;");
+    assert_eq!(terminate("let x: int = 1;"), "let x: int = 1;");
+    assert_eq!(terminate("if (true) {\n}"), "if (true) {\n}");
+  }
+}
@@ -15,6 +15,9 @@ use token::Token;
  * - current: points to the character currently being checked.
  * - line: traces the source line of `current` to know the location of the
  * **tokens**.
+ * - template_braces: one counter for each `${` of a template string that is
+ * still open, the `{` opened inside the expression. The `}` that closes the
+ * expression goes back to the text of the template.
 */
 pub struct Lexer<'a> {
   source: &'a str,
@@ -23,6 +26,7 @@ pub struct Lexer<'a> {
   line: usize,
   current: usize,
   module_path: String,
+  template_braces: Vec<usize>,
 }
 
 impl<'a> Lexer<'a> {
@@ -34,6 +38,7 @@ impl<'a> Lexer<'a> {
       line: 0,
       current: 0,
       module_path,
+      template_braces: Vec::new(),
     }
   }
 
@@ -93,9 +98,24 @@ impl<'a> Lexer<'a> {
         token = TokenType::RightParen;
       }
       '{' => {
+        if let Some(braces) = self.template_braces.last_mut() {
+          *braces += 1;
+        }
+
         token = TokenType::LeftBrace;
       }
       '}' => {
+        match self.template_braces.last_mut() {
+          Some(0) => {
+            self.template_braces.pop();
+            self.add_token(TokenType::RightBrace);
+            self.template_string();
+            return;
+          }
+          Some(braces) => *braces -= 1,
+          None => (),
+        }
+
         token = TokenType::RightBrace;
       }
       '[' => {
@@ -211,8 +231,9 @@ impl<'a> Lexer<'a> {
         }
       }
       '`' => {
-        self.start = self.current - 1; // Guardar la posición inicial del literal de plantilla
+        self.add_token(TokenType::TemplateStringStart);
         self.template_string();
+        return;
       }
       _ => {
         if c.is_ascii_digit() {
@@ -246,6 +267,7 @@ impl<'a> Lexer<'a> {
       "null" => Some(TokenType::Null),
       "return" => Some(TokenType::Return),
       "this" => Some(TokenType::This),
+      "new" => Some(TokenType::New),
       "let" => Some(TokenType::Let),
       "const" => Some(TokenType::Const),
       "while" => Some(TokenType::While),
@@ -264,6 +286,7 @@ impl<'a> Lexer<'a> {
       "interface" => Some(TokenType::Interface),
       "extends" => Some(TokenType::Extends),
       "implements" => Some(TokenType::Implements),
+      "type" => Some(TokenType::Type),
       "string" => Some(TokenType::StringType),
       "boolean" => Some(TokenType::BooleanType),
       "int" => Some(TokenType::IntType),
@@ -272,6 +295,10 @@ impl<'a> Lexer<'a> {
       "void" => Some(TokenType::Void),
       "extern" => Some(TokenType::Extern),
       "continue" => Some(TokenType::Continue),
+      "match" => Some(TokenType::Match),
+      "switch" => Some(TokenType::Switch),
+      "case" => Some(TokenType::Case),
+      "default" => Some(TokenType::Default),
       _ => None,
     }
   }
@@ -282,28 +309,78 @@ impl<'a> Lexer<'a> {
     c.is_ascii_digit() || c.is_ascii_lowercase() || c.is_ascii_uppercase() || c == '_'
   }
 
+  /**
+  Scans the text of a template string up to the closing `` ` `` or the next
+  `${`. The text is added as a **String** token, the tokens of an expression
+  are scanned as usual until the `}` that closes it.
+  */
   fn template_string(&mut self) {
-    loop {
+    let mut text: String = String::new();
+    self.start = self.current;
+
+    while !self.is_at_end() {
       match self.peek() {
         '`' => {
-          self.add_token(TokenType::TemplateStringEnd);
+          self.add_template_text(text);
+
+          self.start = self.current;
           self.advance();
-          break;
+          self.add_token(TokenType::TemplateStringEnd);
+          return;
         }
         '$' if self.peek_next() == '{' => {
-          self.add_token(TokenType::ExpressionStart);
+          self.add_template_text(text);
+
+          self.start = self.current;
           self.advance();
           self.advance();
+          self.add_token(TokenType::ExpressionStart);
+          self.template_braces.push(0);
+          return;
         }
-        _ => {
+        '\\' => {
           self.advance();
+
+          match self.peek() {
+            '`' | '$' | '\\' => text.push(self.peek()),
+            'n' => text.push('\n'),
+            't' => text.push('\t'),
+            c => {
+              text.push('\\');
+              text.push(c);
+            }
+          }
+        }
+        '\n' => {
+          self.line += 1;
+          text.push('\n');
         }
+        c => text.push(c),
       }
 
-      if self.is_at_end() {
-        break;
-      }
+      self.advance();
     }
+
+    // Unterminated template, the parser reports the missing `` ` ``.
+    self.add_template_text(text);
+  }
+
+  fn add_template_text(&mut self, text: String) {
+    if text.is_empty() {
+      return;
+    }
+
+    self.tokens.push(Token::new(
+      TokenType::String,
+      TextSpan::new(
+        self.start,
+        self.current,
+        self.line + 1,
+        text,
+        self.current - self.start,
+        self.module_path.clone(),
+      ),
+    ));
   }
 
   fn identifier(&mut self) -> TokenType {
@@ -547,4 +624,35 @@ mod tests {
     assert_eq!(lexer.tokens[3].kind, TokenType::SemiColon);
     assert_eq!(lexer.tokens[4].kind, TokenType::Eof);
   }
+
+  #[test]
+  fn test_template_string() {
+    let source: &str = "`Hi ${name + `${1}`}, {ok}`";
+    let mut lexer: Lexer<'_> = Lexer::new(source, "".to_string());
+    lexer.scan_tokens();
+
+    let kinds: Vec<TokenType> = lexer.tokens.iter().map(|x| x.kind.clone()).collect();
+
+    assert_eq!(
+      kinds,
+      vec![
+        TokenType::TemplateStringStart,
+        TokenType::String,
+        TokenType::ExpressionStart,
+        TokenType::Identifier,
+        TokenType::Plus,
+        TokenType::TemplateStringStart,
+        TokenType::ExpressionStart,
+        TokenType::Int,
+        TokenType::RightBrace,
+        TokenType::TemplateStringEnd,
+        TokenType::RightBrace,
+        TokenType::String,
+        TokenType::TemplateStringEnd,
+        TokenType::Eof,
+      ]
+    );
+    assert_eq!(lexer.tokens[1].span.literal, "Hi ".to_string());
+    assert_eq!(lexer.tokens[11].span.literal, ", {ok}".to_string());
+  }
 }
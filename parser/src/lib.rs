@@ -1,13 +1,30 @@
 use ast::{
   statement::{
-    class::Class,
+    class::{Class, ClassField, ClassMemberModifiers, ClassMethod},
     variable::VariableMetadata,
     for_in::ForIn,
     import::{Import, ImportSource, ImportSymbol},
     function::FunctionDecorator,
     break_statement::BreakStatement, continue_statement::Continue,
+    enum_statement::{Enum, EnumVariant},
+    switch::{Switch, SwitchCase},
+    interface::{Interface, InterfaceField, InterfaceMethod},
+    type_alias::TypeAlias,
+    error_statement::ErrorStatement,
+  },
+  expression::{
+    array::Array,
+    this::This,
+    get::Get,
+    set::Set,
+    new::NewExpression,
+    match_expression::{Match, MatchArm, MatchPattern},
+    lambda::Lambda,
+    template_literal::{TemplateLiteral, TemplatePart},
+    index::Index,
+    set_index::SetIndex,
+    error_expression::ErrorExpression,
   },
-  expression::array::Array,
 };
 use enums::{data_type::DataType, token_type::TokenType};
 use lexer::{text_span::TextSpan, token};
@@ -46,10 +63,15 @@ pub enum ParserDiagnosticError {
 
 type ParserResult<T> = Result<T, ParserDiagnosticError>;
 
+/**
+  - panic_mode: an error was reported in the current statement, the errors
+    that follow it are caused by the first one and are not reported.
+*/
 pub struct Parser {
   pub tokens: Vec<Token>,
   current: usize,
   pub diagnostics: Vec<ParserDiagnosticError>,
+  panic_mode: bool,
 }
 
 impl Parser {
@@ -58,29 +80,39 @@ impl Parser {
       tokens,
       current: 0,
       diagnostics: Vec::new(),
+      panic_mode: false,
     }
   }
 
   fn report_error(&mut self, error: ParserDiagnosticError) {
+    if self.panic_mode {
+      return;
+    }
+
+    self.panic_mode = true;
     self.diagnostics.push(error);
   }
 
-  pub fn parse(&mut self) -> Result<Vec<Statement>, ()> {
+  /**
+    Parses every statement of the file. The statements with syntax errors
+    are replaced with `Statement::Error` and the missing expressions with
+    `Expression::Error`, so the rest of the file can still be analyzed.
+    The file has errors when `diagnostics` is not empty.
+  */
+  pub fn parse(&mut self) -> Vec<Statement> {
     let mut statements: Vec<Statement> = vec![];
     while !self.is_at_end() {
-      match self.declaration() {
-        Ok(result) => statements.push(result),
-        Err(error) => {
-          self.report_error(error);
-        }
-      };
-    }
+      let statement = self.declaration_or_error();
+
+      // A `}` without a block to close is skipped.
+      if matches!(statement, Statement::Error(_)) && self.check(TokenType::RightBrace) {
+        self.advance();
+      }
 
-    if self.diagnostics.len() > 0 {
-      return Err(());
+      statements.push(statement);
     }
 
-    Ok(statements)
+    statements
   }
 
   fn expression(&mut self) -> ParserResult<Expression> {
@@ -95,14 +127,7 @@ impl Parser {
       let operator: Token = self.previous();
       let right = self.comparison()?;
 
-      let data_type: DataType = DataType::Boolean;
-
-      expression = Expression::Binary(Binary::new(
-        Box::new(expression),
-        operator,
-        Box::new(right),
-        data_type,
-      ));
+      expression = Expression::Binary(Binary::new(Box::new(expression), operator, Box::new(right)));
     }
 
     Ok(expression)
@@ -121,14 +146,7 @@ impl Parser {
       let operator: Token = self.previous();
       let right = self.term()?;
 
-      let data_type: DataType = DataType::Boolean;
-
-      expression = Expression::Binary(Binary::new(
-        Box::new(expression),
-        operator,
-        Box::new(right),
-        data_type,
-      ));
+      expression = Expression::Binary(Binary::new(Box::new(expression), operator, Box::new(right)));
     }
 
     Ok(expression)
@@ -142,16 +160,7 @@ impl Parser {
       let operator: Token = self.previous();
       let right = self.factor()?;
 
-      let left_type = self.get_expression_type(&expression);
-      let right_type = self.get_expression_type(&right);
-      let operator_kind = operator.kind.clone();
-
-      expression = Expression::Binary(Binary::new(
-        Box::new(expression),
-        operator,
-        Box::new(right),
-        self.get_data_type_by_operator(Some(left_type), right_type, operator_kind),
-      ));
+      expression = Expression::Binary(Binary::new(Box::new(expression), operator, Box::new(right)));
     }
 
     Ok(expression)
@@ -165,17 +174,7 @@ impl Parser {
       let operator: Token = self.previous();
       let right: Expression = self.unary()?;
 
-      let left_type = self.get_expression_type(&expression);
-      let right_type = self.get_expression_type(&right);
-
-      let operator_kind = operator.kind.clone();
-
-      expression = Expression::Binary(Binary::new(
-        Box::new(expression),
-        operator,
-        Box::new(right),
-        self.get_data_type_by_operator(Some(left_type), right_type, operator_kind),
-      ));
+      expression = Expression::Binary(Binary::new(Box::new(expression), operator, Box::new(right)));
     }
 
     Ok(expression)
@@ -187,14 +186,7 @@ impl Parser {
       let operator = self.previous();
       let right: Expression = self.unary()?;
 
-      let right_type = self.get_expression_type(&right);
-      let operator_kind = operator.kind.clone();
-
-      return Ok(Expression::Unary(Unary::new(
-        operator,
-        Box::new(right),
-        self.get_data_type_by_operator(None, right_type, operator_kind),
-      )));
+      return Ok(Expression::Unary(Unary::new(operator, Box::new(right))));
     }
 
     self.call()
@@ -206,6 +198,17 @@ impl Parser {
     loop {
       if self.match_token(&[TokenType::LeftParen]) {
         expression = self.finish_call(expression)?;
+      } else if self.match_token(&[TokenType::Dot]) {
+        let name = self.consume(TokenType::Identifier)?;
+
+        expression = Expression::Get(Get::new(Box::new(expression), name));
+      } else if self.match_token(&[TokenType::LeftBrack]) {
+        let bracket = self.previous();
+        let index = self.expression()?;
+
+        self.consume(TokenType::RightBrack)?;
+
+        expression = Expression::Index(Index::new(Box::new(expression), Box::new(index), bracket));
       } else {
         break;
       }
@@ -227,6 +230,7 @@ impl Parser {
         self.advance();
         Ok(Expression::Literal(Literal::new(
           LiteralValue::from_token_type(token.kind.clone(), token.span.literal.clone()),
+          token.clone(),
         )))
       }
       TokenType::LeftBrack => {
@@ -244,8 +248,17 @@ impl Parser {
 
         self.consume(TokenType::RightBrack)?;
 
-        let data_type = DataType::Array(Box::new(DataType::Pending));
-        return Ok(Expression::Array(Array::new(token, elements, data_type)));
+        return Ok(Expression::Array(Array::new(token, elements)));
+      }
+      TokenType::TemplateStringStart => {
+        self.advance();
+
+        self.template_literal(token)
+      }
+      TokenType::LeftParen if self.is_lambda() => {
+        self.advance();
+
+        self.lambda(token)
       }
       TokenType::LeftParen => {
         self.advance();
@@ -254,19 +267,232 @@ impl Parser {
 
         return Ok(Expression::Grouping(Grouping::new(Box::new(expression))));
       }
+      TokenType::This => {
+        self.advance();
+
+        Ok(Expression::This(This::new(token)))
+      }
+      TokenType::New => {
+        self.advance();
+
+        let class_name = self.consume(TokenType::Identifier)?;
+
+        self.consume(TokenType::LeftParen)?;
+        let arguments = self.arguments()?;
+        self.consume(TokenType::RightParen)?;
+
+        Ok(Expression::New(NewExpression::new(
+          token, class_name, arguments,
+        )))
+      }
+      TokenType::Match => {
+        self.advance();
+
+        self.match_expression(token)
+      }
       TokenType::Identifier => {
         self.advance();
-        let kind = token.kind.clone();
-        return Ok(Expression::Variable(VariableExpression::new(
+        return Ok(Expression::Variable(VariableExpression::new(token)));
+      }
+      _ => {
+        self.report_error(ParserDiagnosticError::ExpectedExpression(token.clone()));
+
+        Ok(Expression::Error(ErrorExpression::new(token)))
+      }
+    }
+  }
+
+  /*
+   *  `Name: ${user.name}, age: ${user.age}`
+   */
+  fn template_literal(&mut self, token: Token) -> ParserResult<Expression> {
+    let mut parts: Vec<TemplatePart> = Vec::new();
+
+    loop {
+      match self.peek().kind {
+        TokenType::String => parts.push(TemplatePart::Text(self.advance())),
+        TokenType::ExpressionStart => {
+          self.advance();
+
+          let expression = self.expression()?;
+          self.consume(TokenType::RightBrace)?;
+
+          parts.push(TemplatePart::Expression(expression));
+        }
+        _ => {
+          self.consume(TokenType::TemplateStringEnd)?;
+          break;
+        }
+      }
+    }
+
+    Ok(Expression::TemplateLiteral(TemplateLiteral::new(token, parts)))
+  }
+
+  /**
+    Whether the `(` that is about to be parsed starts the parameters of a
+    lambda, `()` or `(name: type` can not start a grouping.
+  */
+  fn is_lambda(&mut self) -> bool {
+    let next = self.peek_next();
+
+    match next.kind {
+      TokenType::RightParen => true,
+      TokenType::Mut => true,
+      TokenType::Identifier => matches!(
+        self.tokens.get(self.current + 2).map(|token| &token.kind),
+        Some(TokenType::Colon)
+      ),
+      _ => false,
+    }
+  }
+
+  /*
+   *  (a: int, b: int): int -> a + b
+   *  (name: string): void -> {
+   *    println(name);
+   *  }
+   */
+  fn lambda(&mut self, token: Token) -> ParserResult<Expression> {
+    let parameters = self.parameters(&token)?;
+
+    self.consume(TokenType::RightParen)?;
+
+    let return_type = if self.match_token(&[TokenType::Colon]) {
+      Some(self.type_annotation()?)
+    } else {
+      None
+    };
+
+    let arrow = self.consume(TokenType::Arrow)?;
+
+    let body = if self.match_token(&[TokenType::LeftBrace]) {
+      self.block()?
+    } else {
+      let value = self.expression()?;
+
+      Statement::Return(Return::new(Some(Box::new(value)), Box::new(arrow)))
+    };
+
+    Ok(Expression::Lambda(Lambda::new(
+      token,
+      parameters,
+      return_type,
+      vec![body],
+    )))
+  }
+
+  /*
+   *  match value {
+   *    "John" -> println("Hi John!"),
+   *    Option.SOME(x) if x > 1 -> x,
+   *    _ -> {
+   *      println("Hi!");
+   *    },
+   *  }
+   */
+  fn match_expression(&mut self, keyword: Token) -> ParserResult<Expression> {
+    let value = self.expression()?;
+    self.consume(TokenType::LeftBrace)?;
+
+    let mut arms: Vec<MatchArm> = Vec::new();
+
+    while !self.check(TokenType::RightBrace) && !self.is_at_end() {
+      let pattern = self.match_pattern()?;
+
+      let guard = if self.match_token(&[TokenType::If]) {
+        Some(self.expression()?)
+      } else {
+        None
+      };
+
+      self.consume(TokenType::Arrow)?;
+
+      let body = if self.match_token(&[TokenType::LeftBrace]) {
+        self.block()?
+      } else {
+        Statement::Expression(ExpressionStatement::new(Box::new(self.expression()?)))
+      };
+
+      arms.push(MatchArm::new(pattern, guard, Box::new(body)));
+
+      if !self.match_token(&[TokenType::Comma]) {
+        break;
+      }
+    }
+
+    self.consume(TokenType::RightBrace)?;
+
+    Ok(Expression::Match(Match::new(
+      keyword,
+      Box::new(value),
+      arms,
+    )))
+  }
+
+  fn match_pattern(&mut self) -> ParserResult<MatchPattern> {
+    let token = self.peek();
+
+    match token.kind {
+      TokenType::True
+      | TokenType::False
+      | TokenType::Null
+      | TokenType::Int
+      | TokenType::Float
+      | TokenType::String => {
+        self.advance();
+
+        Ok(MatchPattern::Literal(Literal::new(
+          LiteralValue::from_token_type(token.kind.clone(), token.span.literal.clone()),
           token,
-          DataType::from_token_type(kind),
-        )));
+        )))
+      }
+      TokenType::Identifier if token.span.literal == "_" => {
+        self.advance();
+
+        Ok(MatchPattern::Wildcard(token))
+      }
+      TokenType::Identifier => {
+        self.advance();
+
+        self.consume(TokenType::Dot)?;
+        let variant = self.consume(TokenType::Identifier)?;
+        let mut bindings: Vec<Token> = Vec::new();
+
+        if self.match_token(&[TokenType::LeftParen]) {
+          loop {
+            bindings.push(self.consume(TokenType::Identifier)?);
+
+            if !self.match_token(&[TokenType::Comma]) {
+              break;
+            }
+          }
+
+          self.consume(TokenType::RightParen)?;
+        }
+
+        Ok(MatchPattern::Variant {
+          enum_name: token,
+          variant,
+          bindings,
+        })
       }
-      _ => Err(ParserDiagnosticError::ExpectedExpression(token.clone())),
+      _ => Err(ParserDiagnosticError::ExpectedExpression(token)),
     }
   }
 
   fn finish_call(&mut self, callee: Expression) -> ParserResult<Expression> {
+    let arguments = self.arguments()?;
+    let token = self.consume(TokenType::RightParen)?;
+
+    Ok(Expression::Call(Call::new(
+      Box::new(callee),
+      token,
+      arguments,
+    )))
+  }
+
+  fn arguments(&mut self) -> ParserResult<Vec<Expression>> {
     let mut arguments: Vec<Expression> = Vec::new();
 
     if !self.check(TokenType::RightParen) {
@@ -289,64 +515,18 @@ impl Parser {
       }
     }
 
-    let token = self.consume(TokenType::RightParen)?;
-
-    Ok(Expression::Call(Call::new(
-      Box::new(callee),
-      token,
-      arguments,
-      DataType::Pending,
-    )))
+    Ok(arguments)
   }
 
-  fn get_data_type_by_operator(
-    &mut self,
-    left: Option<DataType>,
-    right: DataType,
-    operator: TokenType,
-  ) -> DataType {
-    match (left, right, operator) {
-      (Some(DataType::Int), DataType::Int, TokenType::Plus)
-      | (Some(DataType::Int), DataType::Int, TokenType::Minus)
-      | (None, DataType::Int, TokenType::Minus)
-      | (Some(DataType::Int), DataType::Int, TokenType::Asterisk)
-      | (Some(DataType::Int), DataType::Int, TokenType::Slash) => DataType::Int,
-      (Some(DataType::Float), DataType::Float, TokenType::Plus)
-      | (Some(DataType::Float), DataType::Float, TokenType::Minus)
-      | (Some(DataType::Float), DataType::Float, TokenType::Slash)
-      | (Some(DataType::Float), DataType::Float, TokenType::Asterisk)
-      | (None, DataType::Float, TokenType::Minus) => DataType::Float,
-      (Some(DataType::String), DataType::String, TokenType::Plus) => DataType::String,
-      (None, DataType::Boolean, TokenType::Bang) | (None, DataType::String, TokenType::Bang) => {
-        DataType::Boolean
-      }
-      _ => DataType::Pending,
-    }
-  }
-
-  fn get_expression_type(&self, expression: &Expression) -> DataType {
-    match expression {
-      Expression::Binary(binary) => binary.data_type.clone(),
-      Expression::Unary(unary) => unary.data_type.clone(),
-      Expression::Literal(literal) => match literal.value {
-        LiteralValue::Boolean(_) => DataType::Boolean,
-        LiteralValue::Char(_) => DataType::Char,
-        LiteralValue::Float(_) => DataType::Float,
-        LiteralValue::Int(_) => DataType::Int,
-        LiteralValue::String(_) => DataType::String,
-        _ => DataType::Int,
-      },
-      Expression::Grouping(grouping) => self.get_expression_type(&grouping.expression),
-      Expression::Variable(variable) => DataType::Variable(variable.name.span.literal.clone()),
-      Expression::Assign(assign) => assign.data_type.clone(),
-      Expression::Logical(logical) => logical.data_type.clone(),
-      Expression::Ternary(ternary) => ternary.data_type.clone(),
-      Expression::Call(call) => call.return_type.clone(),
-      Expression::Array(a) => a.data_type.clone(),
+  /**
+    Skips the tokens of a statement with an error, up to the start of the
+    next statement or the `}` that closes the enclosing block.
+  */
+  fn synchronize(&mut self) {
+    if self.check(TokenType::RightBrace) {
+      return;
     }
-  }
 
-  fn synchronize(&mut self) {
     self.advance();
 
     while !self.is_at_end() {
@@ -355,11 +535,16 @@ impl Parser {
       }
 
       match self.peek().kind {
-        TokenType::Class
+        TokenType::RightBrace
+        | TokenType::Class
+        | TokenType::Enum
+        | TokenType::Interface
+        | TokenType::Type
         | TokenType::Function
         | TokenType::Let
         | TokenType::Const
         | TokenType::For
+        | TokenType::While
         | TokenType::If
         | TokenType::Return => return,
         _ => (),
@@ -369,7 +554,35 @@ impl Parser {
     }
   }
 
+  /**
+    Parses a declaration, a declaration with a syntax error is reported and
+    replaced with `Statement::Error`.
+  */
+  fn declaration_or_error(&mut self) -> Statement {
+    let token = self.peek();
+
+    match self.declaration() {
+      Ok(statement) => statement,
+      Err(error) => {
+        self.report_error(error);
+
+        Statement::Error(ErrorStatement::new(token))
+      }
+    }
+  }
+
   fn declaration(&mut self) -> ParserResult<Statement> {
+    self.panic_mode = false;
+    let result = self.declaration_kind();
+
+    if result.is_err() {
+      self.synchronize();
+    }
+
+    result
+  }
+
+  fn declaration_kind(&mut self) -> ParserResult<Statement> {
     if self.match_token(&[TokenType::Let]) {
       return self.variable_declaration();
     }
@@ -378,6 +591,18 @@ impl Parser {
       return self.class_declaration();
     }
 
+    if self.match_token(&[TokenType::Enum]) {
+      return self.enum_declaration();
+    }
+
+    if self.match_token(&[TokenType::Interface]) {
+      return self.interface_declaration();
+    }
+
+    if self.match_token(&[TokenType::Type]) {
+      return self.type_alias_declaration();
+    }
+
     if self.match_token(&[TokenType::Function]) {
       return self.function(FunctionKind::Function, false, None);
     }
@@ -414,13 +639,7 @@ impl Parser {
       return self.continue_statement();
     }
 
-    match self.statement() {
-      Ok(statement) => Ok(statement),
-      Err(error) => {
-        self.synchronize();
-        return Err(error);
-      }
-    }
+    self.statement()
   }
   
   fn continue_statement(&mut self) -> Result<Statement, ParserDiagnosticError> {
@@ -457,13 +676,66 @@ impl Parser {
 
   fn function_statement(
     &mut self,
+    kind: FunctionKind,
     is_public: bool,
     decorator: Option<FunctionDecorator>,
   ) -> ParserResult<Statement> {
     let name: Token = self.consume(TokenType::Identifier)?;
+    let generics = self.generic_parameters()?;
 
     self.consume(TokenType::LeftParen)?;
 
+    let parameters = self.parameters(&name)?;
+
+    self.consume(TokenType::RightParen)?;
+
+    // Constructors may omit the return type, they always return the instance.
+    let return_type: Option<DataType> =
+      if matches!(kind, FunctionKind::Initializer) && !self.check(TokenType::Colon) {
+        None
+      } else {
+        self.consume(TokenType::Colon)?;
+
+        let token = self.peek();
+
+        match self.type_annotation() {
+          Ok(data_type) => Some(data_type),
+          Err(_) => {
+            return Err(ParserDiagnosticError::ExpectedReturnTypeAfterFunction(
+              token,
+            ))
+          }
+        }
+      };
+
+    let mut body: Vec<Statement> = Vec::new();
+
+    if !self.match_token(&[TokenType::SemiColon]) {
+      self.consume(TokenType::LeftBrace)?;
+
+      body.push(self.block()?);
+    }
+
+    Ok(Statement::FunctionStatement(FunctionStatement::new(
+      name,
+      generics,
+      parameters,
+      body,
+      return_type,
+      is_public,
+      if decorator.is_some() {
+        vec![decorator.unwrap()]
+      } else {
+        vec![]
+      },
+    )))
+  }
+
+  /**
+    Parses the parameters of a function up to the `)`, `name: type` with an
+    optional `mut`.
+  */
+  fn parameters(&mut self, name: &Token) -> ParserResult<Vec<FunctionParameter>> {
     let mut parameters: Vec<FunctionParameter> = Vec::new();
 
     if !self.check(TokenType::RightParen) {
@@ -486,13 +758,9 @@ impl Parser {
         let param = self.consume(TokenType::Identifier)?;
 
         self.consume(TokenType::Colon)?;
-        let token = self.advance();
+        let data_type = self.type_annotation()?;
 
-        parameters.push(FunctionParameter::new(
-          param,
-          DataType::from_token_type(token.kind),
-          is_mut,
-        ));
+        parameters.push(FunctionParameter::new(param, data_type, is_mut));
 
         if !self.match_token(&[TokenType::Comma]) {
           break;
@@ -500,69 +768,28 @@ impl Parser {
       }
     }
 
-    self.consume(TokenType::RightParen)?;
-
-    self.consume(TokenType::Colon)?;
-
-    let return_type: Option<DataType>;
-    if self.match_token(&[
-      TokenType::Void,
-      TokenType::IntType,
-      TokenType::FloatType,
-      TokenType::StringType,
-      TokenType::BooleanType,
-      TokenType::CharType,
-    ]) {
-      return_type = Some(DataType::from_token_type(self.previous().kind));
-    } else {
-      let token = &self.peek();
+    Ok(parameters)
+  }
 
-      return Err(ParserDiagnosticError::ExpectedReturnTypeAfterFunction(
-        token.clone(),
-      ));
+  fn function(
+    &mut self,
+    kind: FunctionKind,
+    is_public: bool,
+    decorator: Option<FunctionDecorator>,
+  ) -> ParserResult<Statement> {
+    match kind {
+      FunctionKind::Function | FunctionKind::Method | FunctionKind::Initializer => {
+        self.function_statement(kind, is_public, decorator)
+      }
+      FunctionKind::Lambda => unreachable!("lambdas are parsed as expressions"),
     }
-
-    let mut body: Vec<Statement> = Vec::new();
-
-    if !self.match_token(&[TokenType::SemiColon]) {
-      self.consume(TokenType::LeftBrace)?;
-
-      body.push(self.block()?);
-    }
-
-    Ok(Statement::FunctionStatement(FunctionStatement::new(
-      name,
-      parameters,
-      body,
-      return_type,
-      is_public,
-      if decorator.is_some() {
-        vec![decorator.unwrap()]
-      } else {
-        vec![]
-      },
-    )))
-  }
-
-  fn function(
-    &mut self,
-    kind: FunctionKind,
-    is_public: bool,
-    decorator: Option<FunctionDecorator>,
-  ) -> ParserResult<Statement> {
-    match kind {
-      FunctionKind::Function => self.function_statement(is_public, decorator),
-      FunctionKind::Method => todo!(),
-      FunctionKind::Initializer => todo!(),
-      FunctionKind::Lambda => todo!(),
-    }
-  }
+  }
 
   fn block(&mut self) -> ParserResult<Statement> {
     let mut statements: Vec<Statement> = Vec::new();
 
     while !self.check(TokenType::RightBrace) && !self.is_at_end() {
-      statements.push(self.declaration()?);
+      statements.push(self.declaration_or_error());
     }
 
     self.consume(TokenType::RightBrace)?;
@@ -582,54 +809,26 @@ impl Parser {
 
     let mut initializer: Option<Expression> = None;
 
-    self.consume(TokenType::Colon)?;
-
-    let token = self.peek();
-
-    let mut type_annotation = DataType::from_token_type(token.kind.clone());
-
-    if type_annotation == DataType::None {
-      return Err(ParserDiagnosticError::ExpectedTypeAfterVariable(token));
-    }
-
-    self.advance();
-
-    if self.match_token(&[TokenType::LeftBrack]) {
-      self.consume(TokenType::RightBrack)?;
-
-      type_annotation = DataType::Array(Box::new(type_annotation));
-    }
+    // Without an annotation the type is inferred from the initializer.
+    let type_annotation = if self.match_token(&[TokenType::Colon]) {
+      Some(self.type_annotation()?)
+    } else {
+      None
+    };
 
     if self.match_token(&[TokenType::Equal]) {
-      let mut value = self.expression()?;
-
-      match value {
-        Expression::Array(a) => {
-          value = Expression::Array(Array::new(
-            a.token.clone(),
-            a.elements,
-            type_annotation.clone(),
-          ));
-        }
-        _ => (),
-      };
-
-      initializer = Some(value);
+      initializer = Some(self.expression()?);
     }
 
     self.consume(TokenType::SemiColon)?;
 
-    if let Some(ini) = initializer {
-      Ok(Statement::Variable(Variable::new(
-        Box::new(name),
-        Some(Box::new(ini)),
-        type_annotation,
-        VariableMetadata::new(mutable, false, false, false, false),
-      )))
-    } else {
-      let token = self.peek();
-      Err(ParserDiagnosticError::ExpectedExpression(token.clone()))
-    }
+    // Without an initializer the value is assigned later, `let x: int;`.
+    Ok(Statement::Variable(Variable::new(
+      Box::new(name),
+      initializer.map(Box::new),
+      type_annotation,
+      VariableMetadata::new(mutable, false, false, false, false),
+    )))
   }
 
   // statement -> expressionStatement | ifStatement;
@@ -642,6 +841,10 @@ impl Parser {
       return self.if_statement();
     }
 
+    if self.match_token(&[TokenType::Switch]) {
+      return self.switch_statement();
+    }
+
     self.expression_statement()
   }
 
@@ -649,7 +852,10 @@ impl Parser {
   fn expression_statement(&mut self) -> ParserResult<Statement> {
     let expression = self.expression()?;
 
-    self.consume(TokenType::SemiColon)?;
+    // A `match` ends with `}` like a block, the `;` is optional.
+    if !matches!(expression, Expression::Match(_)) || self.check(TokenType::SemiColon) {
+      self.consume(TokenType::SemiColon)?;
+    }
 
     Ok(Statement::Expression(ExpressionStatement::new(Box::new(
       expression,
@@ -663,17 +869,23 @@ impl Parser {
       let equals: Token = self.previous();
       let value: Expression = self.assignment()?;
 
-      if let Expression::Variable(variable) = expression {
-        expression = Expression::Assign(Assign::new(
-          variable.name,
+      expression = match expression {
+        Expression::Variable(variable) => {
+          Expression::Assign(Assign::new(variable.name, Box::new(value)))
+        }
+        Expression::Get(get) => Expression::Set(Set::new(get.object, get.name, Box::new(value))),
+        Expression::Index(index) => Expression::SetIndex(SetIndex::new(
+          index.object,
+          index.index,
           Box::new(value),
-          variable.data_type,
-        ));
-      } else {
-        return Err(ParserDiagnosticError::InvalidAssignmentTarget(
-          equals.span.clone(),
-        ));
-      }
+          index.bracket,
+        )),
+        _ => {
+          return Err(ParserDiagnosticError::InvalidAssignmentTarget(
+            equals.span.clone(),
+          ))
+        }
+      };
     }
 
     return Ok(expression);
@@ -681,10 +893,12 @@ impl Parser {
 
   fn ternary(&mut self) -> ParserResult<Expression> {
     let mut children: Vec<Expression> = Vec::new();
+    let mut tokens: Vec<Token> = Vec::new();
 
     children.push(self.or_expression()?);
 
     while self.match_token(&[TokenType::QuestionMark]) {
+      tokens.push(self.previous());
       children.push(self.expression()?);
 
       self.consume(TokenType::Colon)?;
@@ -704,7 +918,7 @@ impl Parser {
       Box::new(condition),
       Box::new(then_branch),
       Box::new(else_branch),
-      DataType::Pending,
+      tokens.pop().unwrap(),
     ));
 
     while !children.is_empty() {
@@ -712,7 +926,7 @@ impl Parser {
         Box::new(children.pop().unwrap()),
         Box::new(expression),
         Box::new(children.pop().unwrap()),
-        DataType::Pending,
+        tokens.pop().unwrap(),
       ));
     }
 
@@ -754,6 +968,7 @@ impl Parser {
   }
 
   fn while_statement(&mut self) -> ParserResult<Statement> {
+    let token: Token = self.previous();
     self.consume(TokenType::LeftParen)?;
 
     let condition: Expression = self.expression()?;
@@ -765,6 +980,7 @@ impl Parser {
     Ok(Statement::WhileStatement(WhileStatement::new(
       Box::new(condition),
       Box::new(body),
+      token,
     )))
   }
 
@@ -778,7 +994,7 @@ impl Parser {
     let variable = Variable::new(
       Box::new(item.clone()),
       None,
-      DataType::Pending,
+      None,
       VariableMetadata::new(true, false, false, false, false),
     );
 
@@ -796,6 +1012,7 @@ impl Parser {
   }
 
   fn if_statement(&mut self) -> ParserResult<Statement> {
+    let token: Token = self.previous();
     self.consume(TokenType::LeftParen)?;
 
     let condition: Expression = self.expression()?;
@@ -814,6 +1031,65 @@ impl Parser {
       Box::new(condition),
       Box::new(then_branch),
       else_branch.map(|s| Box::new(s)),
+      token,
+    )))
+  }
+
+  /**
+    Parses a `switch`, the body of a case are the statements until the next
+    `case`, `default` or the end of the switch:
+
+    ```ignis
+    switch value {
+      case 1:
+      case 2:
+        println("small");
+        break;
+      default:
+        println("big");
+    }
+    ```
+  */
+  fn switch_statement(&mut self) -> ParserResult<Statement> {
+    let keyword = self.previous();
+    let value = self.expression()?;
+
+    self.consume(TokenType::LeftBrace)?;
+
+    let mut cases: Vec<SwitchCase> = Vec::new();
+
+    while !self.check(TokenType::RightBrace) && !self.is_at_end() {
+      let token = self.peek();
+
+      let value = if self.match_token(&[TokenType::Case]) {
+        Some(self.expression()?)
+      } else if self.match_token(&[TokenType::Default]) {
+        None
+      } else {
+        return Err(ParserDiagnosticError::ExpectedToken(TokenType::Case, token));
+      };
+
+      self.consume(TokenType::Colon)?;
+
+      let mut body: Vec<Statement> = Vec::new();
+
+      while !self.check(TokenType::Case)
+        && !self.check(TokenType::Default)
+        && !self.check(TokenType::RightBrace)
+        && !self.is_at_end()
+      {
+        body.push(self.declaration_or_error());
+      }
+
+      cases.push(SwitchCase::new(token, value, body));
+    }
+
+    self.consume(TokenType::RightBrace)?;
+
+    Ok(Statement::Switch(Switch::new(
+      keyword,
+      Box::new(value),
+      cases,
     )))
   }
 
@@ -851,6 +1127,13 @@ impl Parser {
     self.tokens[self.current].clone()
   }
 
+  fn peek_next(&mut self) -> Token {
+    match self.tokens.get(self.current + 1) {
+      Some(token) => token.clone(),
+      None => self.tokens[self.tokens.len() - 1].clone(),
+    }
+  }
+
   fn is_at_end(&mut self) -> bool {
     self.peek().kind == TokenType::Eof
   }
@@ -886,23 +1169,399 @@ impl Parser {
     self.tokens[self.current - 1].clone()
   }
 
+  /*
+   *  class User {
+   *    private name: string;
+   *    public static count: int = 0;
+   *
+   *    public User(name: string) {
+   *      this.name = name;
+   *    }
+   *
+   *    public getName(): string {
+   *      return this.name;
+   *    }
+   *  }
+   */
   fn class_declaration(&mut self) -> Result<Statement, ParserDiagnosticError> {
     let name: Token = self.consume(TokenType::Identifier)?;
+    let generics = self.generic_parameters()?;
 
-    let mut methods: Vec<FunctionStatement> = Vec::new();
+    let implements = if self.match_token(&[TokenType::Implements]) {
+      self.identifier_list()?
+    } else {
+      Vec::new()
+    };
 
-    // self.consume(TokenType::LeftBrace)?;
+    let mut fields: Vec<ClassField> = Vec::new();
+    let mut constructor: Option<ClassMethod> = None;
+    let mut methods: Vec<ClassMethod> = Vec::new();
 
-    // while !self.check(TokenType::RightBrace) && !self.is_at_end() {
-    //   let method = match self.function(FunctionKind::Method)? {
-    //     Statement::FunctionStatement(function) => methods.push(function),
-    //     _ => (),
-    //   };
-    // }
+    self.consume(TokenType::LeftBrace)?;
+
+    while !self.check(TokenType::RightBrace) && !self.is_at_end() {
+      let modifiers = self.class_member_modifiers();
+      let member = self.peek();
+
+      if !matches!(
+        self.peek_next().kind,
+        TokenType::LeftParen | TokenType::Less
+      ) {
+        fields.push(self.class_field(modifiers)?);
+        continue;
+      }
+
+      let kind = if member.span.literal == name.span.literal {
+        FunctionKind::Initializer
+      } else {
+        FunctionKind::Method
+      };
+
+      let is_initializer = matches!(kind, FunctionKind::Initializer);
+
+      let method = match self.function(kind, false, None)? {
+        Statement::FunctionStatement(function) => ClassMethod::new(function, modifiers),
+        _ => unreachable!(),
+      };
+
+      if is_initializer {
+        constructor = Some(method);
+      } else {
+        methods.push(method);
+      }
+    }
 
     self.consume(TokenType::RightBrace)?;
 
-    Ok(Statement::Class(Class::new(name, methods)))
+    Ok(Statement::Class(Class::new(
+      name,
+      generics,
+      implements,
+      fields,
+      constructor,
+      methods,
+    )))
+  }
+
+  fn class_member_modifiers(&mut self) -> ClassMemberModifiers {
+    let mut modifiers = ClassMemberModifiers::default();
+
+    loop {
+      if self.match_token(&[TokenType::Public]) {
+        modifiers.is_public = true;
+      } else if self.match_token(&[TokenType::Private]) {
+        modifiers.is_public = false;
+      } else if self.match_token(&[TokenType::Static]) {
+        modifiers.is_static = true;
+      } else if self.match_token(&[TokenType::ReadOnly]) {
+        modifiers.is_readonly = true;
+      } else {
+        return modifiers;
+      }
+    }
+  }
+
+  fn class_field(&mut self, modifiers: ClassMemberModifiers) -> ParserResult<ClassField> {
+    let name = self.consume(TokenType::Identifier)?;
+
+    self.consume(TokenType::Colon)?;
+    let data_type = self.type_annotation()?;
+
+    let initializer = if self.match_token(&[TokenType::Equal]) {
+      Some(self.expression()?)
+    } else {
+      None
+    };
+
+    self.consume(TokenType::SemiColon)?;
+
+    Ok(ClassField::new(name, data_type, initializer, modifiers))
+  }
+
+  /*
+   *  interface Shape extends Named {
+   *    sides: int;
+   *    area(): float;
+   *  }
+   */
+  fn interface_declaration(&mut self) -> ParserResult<Statement> {
+    let name = self.consume(TokenType::Identifier)?;
+
+    let extends = if self.match_token(&[TokenType::Extends]) {
+      self.identifier_list()?
+    } else {
+      Vec::new()
+    };
+
+    let mut fields: Vec<InterfaceField> = Vec::new();
+    let mut methods: Vec<InterfaceMethod> = Vec::new();
+
+    self.consume(TokenType::LeftBrace)?;
+
+    while !self.check(TokenType::RightBrace) && !self.is_at_end() {
+      let member = self.consume(TokenType::Identifier)?;
+
+      if self.match_token(&[TokenType::LeftParen]) {
+        let parameters = self.parameters(&member)?;
+
+        self.consume(TokenType::RightParen)?;
+        self.consume(TokenType::Colon)?;
+
+        let return_type = self.type_annotation()?;
+
+        methods.push(InterfaceMethod::new(member, parameters, return_type));
+      } else {
+        self.consume(TokenType::Colon)?;
+
+        fields.push(InterfaceField::new(member, self.type_annotation()?));
+      }
+
+      self.consume(TokenType::SemiColon)?;
+    }
+
+    self.consume(TokenType::RightBrace)?;
+
+    Ok(Statement::Interface(Interface::new(
+      name, extends, fields, methods,
+    )))
+  }
+
+  // type Id = int | string;
+  fn type_alias_declaration(&mut self) -> ParserResult<Statement> {
+    let name = self.consume(TokenType::Identifier)?;
+
+    self.consume(TokenType::Equal)?;
+
+    let value = self.type_annotation()?;
+
+    self.consume(TokenType::SemiColon)?;
+
+    Ok(Statement::TypeAlias(TypeAlias::new(name, value)))
+  }
+
+  /**
+    Names separated by commas, the interfaces after `implements` or `extends`.
+  */
+  fn identifier_list(&mut self) -> ParserResult<Vec<Token>> {
+    let mut names = vec![self.consume(TokenType::Identifier)?];
+
+    while self.match_token(&[TokenType::Comma]) {
+      names.push(self.consume(TokenType::Identifier)?);
+    }
+
+    Ok(names)
+  }
+
+  /**
+    Type parameters written after the name of a declaration, `<T, U>`.
+  */
+  fn generic_parameters(&mut self) -> ParserResult<Vec<Token>> {
+    if !self.match_token(&[TokenType::Less]) {
+      return Ok(Vec::new());
+    }
+
+    let generics = self.identifier_list()?;
+
+    self.consume(TokenType::Greater)?;
+
+    Ok(generics)
+  }
+
+  /*
+   *  enum Option<T> {
+   *    SOME(T),
+   *    NONE,
+   *  }
+   *
+   *  enum Color {
+   *    RED = "red",
+   *    GREEN = "green",
+   *  }
+   */
+  fn enum_declaration(&mut self) -> ParserResult<Statement> {
+    let name = self.consume(TokenType::Identifier)?;
+    let generics = self.generic_parameters()?;
+
+    self.consume(TokenType::LeftBrace)?;
+
+    let mut variants: Vec<EnumVariant> = Vec::new();
+
+    while !self.check(TokenType::RightBrace) && !self.is_at_end() {
+      let variant_name = self.consume(TokenType::Identifier)?;
+      let mut fields: Vec<DataType> = Vec::new();
+      let mut value: Option<Expression> = None;
+
+      if self.match_token(&[TokenType::LeftParen]) {
+        loop {
+          fields.push(self.type_annotation()?);
+
+          if !self.match_token(&[TokenType::Comma]) {
+            break;
+          }
+        }
+
+        self.consume(TokenType::RightParen)?;
+      } else if self.match_token(&[TokenType::Equal]) {
+        let token = self.peek();
+
+        if !matches!(token.kind, TokenType::String | TokenType::Int) {
+          return Err(ParserDiagnosticError::ExpectedToken(
+            TokenType::String,
+            token,
+          ));
+        }
+
+        value = Some(self.primary()?);
+      }
+
+      variants.push(EnumVariant::new(variant_name, fields, value));
+
+      if !self.match_token(&[TokenType::Comma]) {
+        break;
+      }
+    }
+
+    self.consume(TokenType::RightBrace)?;
+
+    Ok(Statement::Enum(Enum::new(name, generics, variants)))
+  }
+
+  /*
+   *  type         -> intersection ( "|" intersection )*
+   *  intersection -> postfix ( "&" postfix )*
+   *  postfix      -> primary ( "[" "]" | "?" )*
+   *  primary      -> "(" types? ")" ( "->" type )? | primitive | IDENTIFIER ( "<" types ">" )?
+   */
+  fn type_annotation(&mut self) -> ParserResult<DataType> {
+    let data_type = self.intersection_type()?;
+
+    if !self.check(TokenType::Pipe) {
+      return Ok(data_type);
+    }
+
+    let mut types = Self::union_members(data_type);
+
+    while self.match_token(&[TokenType::Pipe]) {
+      types.extend(Self::union_members(self.intersection_type()?));
+    }
+
+    Ok(DataType::UnionType(types))
+  }
+
+  fn intersection_type(&mut self) -> ParserResult<DataType> {
+    let data_type = self.postfix_type()?;
+
+    if !self.check(TokenType::Ampersand) {
+      return Ok(data_type);
+    }
+
+    let mut types = vec![data_type];
+
+    while self.match_token(&[TokenType::Ampersand]) {
+      types.push(self.postfix_type()?);
+    }
+
+    Ok(DataType::IntersectionType(types))
+  }
+
+  /**
+    Arrays, `int[][]`, and nullable types, `int?`, a nullable type is the
+    union of the type and `null`.
+  */
+  fn postfix_type(&mut self) -> ParserResult<DataType> {
+    let mut data_type = self.primary_type()?;
+
+    loop {
+      if self.match_token(&[TokenType::LeftBrack]) {
+        self.consume(TokenType::RightBrack)?;
+
+        data_type = DataType::Array(Box::new(data_type));
+      } else if self.match_token(&[TokenType::QuestionMark]) {
+        let mut types = Self::union_members(data_type);
+
+        if !types.contains(&DataType::Null) {
+          types.push(DataType::Null);
+        }
+
+        data_type = DataType::UnionType(types);
+      } else {
+        break;
+      }
+    }
+
+    Ok(data_type)
+  }
+
+  fn primary_type(&mut self) -> ParserResult<DataType> {
+    let token = self.peek();
+
+    // Function type `(int, int) -> int`, tuple `(int, string)` or a grouped type `(int | string)`.
+    if self.match_token(&[TokenType::LeftParen]) {
+      let mut types: Vec<DataType> = Vec::new();
+
+      if !self.check(TokenType::RightParen) {
+        loop {
+          types.push(self.type_annotation()?);
+
+          if !self.match_token(&[TokenType::Comma]) {
+            break;
+          }
+        }
+      }
+
+      self.consume(TokenType::RightParen)?;
+
+      if self.match_token(&[TokenType::Arrow]) {
+        let return_type = self.type_annotation()?;
+
+        return Ok(DataType::Callable(types, Box::new(return_type)));
+      }
+
+      return match types.len() {
+        0 => Err(ParserDiagnosticError::ExpectedTypeAfterVariable(token)),
+        1 => Ok(types.pop().unwrap()),
+        _ => Ok(DataType::TupleType(types)),
+      };
+    }
+
+    let data_type = match token.kind {
+      TokenType::Identifier => DataType::ClassType(token.span.literal.clone()),
+      _ => DataType::from_token_type(token.kind.clone()),
+    };
+
+    if data_type == DataType::None {
+      return Err(ParserDiagnosticError::ExpectedTypeAfterVariable(token));
+    }
+
+    self.advance();
+
+    if matches!(data_type, DataType::ClassType(_)) && self.match_token(&[TokenType::Less]) {
+      let mut parameters: Vec<DataType> = Vec::new();
+
+      loop {
+        parameters.push(self.type_annotation()?);
+
+        if !self.match_token(&[TokenType::Comma]) {
+          break;
+        }
+      }
+
+      self.consume(TokenType::Greater)?;
+
+      return Ok(DataType::GenericType {
+        base: Box::new(data_type),
+        parameters,
+      });
+    }
+
+    Ok(data_type)
+  }
+
+  fn union_members(data_type: DataType) -> Vec<DataType> {
+    match data_type {
+      DataType::UnionType(types) => types,
+      data_type => vec![data_type],
+    }
   }
 
   fn import_statement(&mut self) -> Result<Statement, ParserDiagnosticError> {
@@ -1004,3 +1663,157 @@ impl Parser {
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use lexer::Lexer;
+
+  /**
+    Parses `source`, the error is the line of every diagnostic reported.
+  */
+  fn parse(source: &str) -> Result<Vec<Statement>, Vec<usize>> {
+    let mut lexer = Lexer::new(source, "main.ign".to_string());
+    lexer.scan_tokens();
+
+    let mut parser = Parser::new(lexer.tokens);
+    let statements = parser.parse();
+
+    if !parser.diagnostics.is_empty() {
+      return Err(parser.diagnostics.iter().map(error_line).collect());
+    }
+
+    Ok(statements)
+  }
+
+  fn error_line(error: &ParserDiagnosticError) -> usize {
+    match error {
+      ParserDiagnosticError::ExpectedExpression(token)
+      | ParserDiagnosticError::ExpectedToken(_, token)
+      | ParserDiagnosticError::ExpectedVariableName(token)
+      | ParserDiagnosticError::ExpectedReturnTypeAfterFunction(token)
+      | ParserDiagnosticError::ExpectedAfterExpression(_, _, token)
+      | ParserDiagnosticError::ExpectedExpressionAfter(token)
+      | ParserDiagnosticError::UnexpectedToken(_, token)
+      | ParserDiagnosticError::ExpectedTypeAfterVariable(token)
+      | ParserDiagnosticError::InvalidNumberOfArguments(_, _, token) => token.span.line,
+      ParserDiagnosticError::InvalidAssignmentTarget(span) => span.line,
+    }
+  }
+
+  fn variable_type(statement: &Statement) -> Option<DataType> {
+    match statement {
+      Statement::Variable(variable) => variable.type_annotation.clone(),
+      _ => None,
+    }
+  }
+
+  #[test]
+  fn test_class_declarations() {
+    let statements = parse(
+      "class Counter {\n  public static created: int = 0;\n  private count: int = 0;\n\n  public Counter(start: int) {\n    this.count = start;\n  }\n\n  public increment(): int {\n    this.count = this.count + 1;\n    return this.count;\n  }\n}",
+    )
+    .unwrap();
+
+    match &statements[..] {
+      [Statement::Class(class)] => {
+        assert_eq!(class.name.span.literal, "Counter");
+        assert_eq!(class.fields.len(), 2);
+        assert!(class.fields[0].modifiers.is_static);
+        assert!(!class.fields[1].modifiers.is_public);
+        assert!(class.constructor.is_some());
+        assert_eq!(class.methods.len(), 1);
+      }
+      other => panic!("Expected a class, found {:?}", other),
+    }
+  }
+
+  #[test]
+  fn test_template_literals() {
+    let statements = parse("`${name} is ${`nested ${age}`}!`;").unwrap();
+
+    match &statements[..] {
+      [Statement::Expression(statement)] => match &*statement.expression {
+        Expression::TemplateLiteral(template) => {
+          assert_eq!(template.parts.len(), 4);
+          assert!(matches!(
+            template.parts[2],
+            TemplatePart::Expression(Expression::TemplateLiteral(_))
+          ));
+        }
+        other => panic!("Expected a template literal, found {:?}", other),
+      },
+      other => panic!("Expected an expression, found {:?}", other),
+    }
+  }
+
+  #[test]
+  fn test_index_assignment_target() {
+    let statements = parse("grid[0][1] = 2;").unwrap();
+
+    match &statements[..] {
+      [Statement::Expression(statement)] => match &*statement.expression {
+        Expression::SetIndex(set) => assert!(matches!(*set.object, Expression::Index(_))),
+        other => panic!("Expected an index assignment, found {:?}", other),
+      },
+      other => panic!("Expected an expression, found {:?}", other),
+    }
+
+    assert_eq!(parse("grid[0] + 1 = 2;").unwrap_err(), vec![1]);
+  }
+
+  #[test]
+  fn test_type_annotations() {
+    let statements = parse(
+      "let grid: int[][] = [];\nlet id: int | string = 1;\nlet name: string? = null;\nlet pair: (int, string) = null;\nlet f: (int) -> boolean = null;",
+    )
+    .unwrap();
+
+    let types: Vec<Option<DataType>> = statements.iter().map(variable_type).collect();
+
+    assert_eq!(
+      types,
+      vec![
+        Some(DataType::Array(Box::new(DataType::Array(Box::new(
+          DataType::Int
+        ))))),
+        Some(DataType::UnionType(vec![DataType::Int, DataType::String])),
+        Some(DataType::UnionType(vec![DataType::String, DataType::Null])),
+        Some(DataType::TupleType(vec![DataType::Int, DataType::String])),
+        Some(DataType::Callable(
+          vec![DataType::Int],
+          Box::new(DataType::Boolean)
+        )),
+      ]
+    );
+  }
+
+  #[test]
+  fn test_all_errors_are_reported() {
+    assert_eq!(
+      parse("function f(): void {\n  let a = ;\n  let b = 1 +;\n}\nlet c = 2;\nlet d = );"),
+      Err(vec![2, 3, 6])
+    );
+  }
+
+  #[test]
+  fn test_syntax_errors_keep_the_rest_of_the_ast() {
+    let mut lexer = Lexer::new("let a = );\nlet b = 1;\nb + ;", "main.ign".to_string());
+    lexer.scan_tokens();
+
+    let mut parser = Parser::new(lexer.tokens);
+    let statements = parser.parse();
+
+    assert_eq!(parser.diagnostics.len(), 2);
+    assert!(matches!(statements[0], Statement::Error(_)));
+    assert!(matches!(statements[1], Statement::Variable(_)));
+
+    match &statements[2] {
+      Statement::Expression(statement) => match &*statement.expression {
+        Expression::Binary(binary) => assert!(matches!(*binary.right, Expression::Error(_))),
+        other => panic!("Expected a binary expression, found {:?}", other),
+      },
+      other => panic!("Expected an expression, found {:?}", other),
+    }
+  }
+}
@@ -1,8 +1,23 @@
 use std::{vec, collections::HashMap};
 
+use code_generator::{CodeGenerator, CodeResult};
+use diagnostic::Diagnostic;
+use enums::data_type::DataType;
+
 use analyzer::{
   ir::{
-    instruction::{IRInstruction, function::IRFunction, call::IRCall, variable::IRVariable},
+    instruction::{
+      IRInstruction,
+      function::IRFunction,
+      call::IRCall,
+      variable::IRVariable,
+      class::{IRClass, IRClassMemberMetadata},
+      ir_enum::IREnum,
+      ir_match::{IRMatch, IRMatchArm, IRMatchPattern},
+      ir_switch::IRSwitch,
+      ir_lambda::IRLambda,
+      ir_template_literal::IRTemplateLiteral,
+    },
     instruction_type::IRInstructionType,
   },
   analyzer_value::AnalyzerValue,
@@ -13,6 +28,7 @@ enum TranspilerContext {
   For,
   While,
   Continue(Box<TranspilerContext>),
+  Constructor,
 }
 
 pub struct TranspilerToLua {
@@ -38,7 +54,7 @@ impl TranspilerToLua {
     self.statement_imported = HashMap::new();
 
     for instruction in ir {
-      let code = self.transpile_ir_to_lua(instruction, 0).clone();
+      let code = self.transpile_statement_to_lua(instruction, 0);
 
       self.code.push_str(code.as_str());
     }
@@ -66,15 +82,7 @@ impl TranspilerToLua {
     let mut code = String::new();
 
     match instruction {
-      IRInstruction::Literal(literal) => code.push_str(&match &literal.value {
-        AnalyzerValue::Int(num) => num.to_string(),
-        AnalyzerValue::String(s) => format!("\"{}\"", s),
-        AnalyzerValue::Float(num) => num.to_string(),
-        AnalyzerValue::Boolean(boolean) => boolean.to_string(),
-        AnalyzerValue::Return(r) => r.to_string(),
-        AnalyzerValue::Function(f) => f.name.span.literal.clone(),
-        AnalyzerValue::Null | AnalyzerValue::None => "nil".to_string(),
-      }),
+      IRInstruction::Literal(literal) => code.push_str(&Self::value_to_lua(&literal.value)),
       IRInstruction::Binary(binary) => {
         let left = self.transpile_ir_to_lua(&binary.left, indent_level);
         let right = self.transpile_ir_to_lua(&binary.right, indent_level);
@@ -84,11 +92,27 @@ impl TranspilerToLua {
       }
       IRInstruction::Block(block) => {
         for instr in &block.instructions {
-          code.push_str(&self.transpile_ir_to_lua(instr, indent_level));
+          code.push_str(&self.transpile_statement_to_lua(instr, indent_level));
         }
       }
-      IRInstruction::Function(func) => {
-        code.push_str(&self.transpile_function_to_lua(func, indent_level));
+      IRInstruction::Function(func) => code.push_str(&func.name),
+      IRInstruction::Lambda(lambda) => {
+        code.push_str(&self.transpile_lambda_to_lua(lambda, indent_level))
+      }
+      IRInstruction::TemplateLiteral(template) => {
+        code.push_str(&self.transpile_template_literal_to_lua(template))
+      }
+      IRInstruction::CallValue(call) => {
+        // Calls end with a new line, the callee and its arguments must stay
+        // on the same line.
+        let callee = self.transpile_ir_to_lua(&call.callee, 0);
+        let callee = callee.trim_end();
+        let arguments = self.transpile_arguments_to_lua(&call.arguments);
+
+        match &*call.callee {
+          IRInstruction::Lambda(_) => code.push_str(&format!("({})({})", callee, arguments)),
+          _ => code.push_str(&format!("{}({})", callee, arguments)),
+        }
       }
       IRInstruction::Unary(unary) => {
         let value = self.transpile_ir_to_lua(&unary.right, indent_level);
@@ -151,6 +175,9 @@ impl TranspilerToLua {
         self.context.pop();
       }
       IRInstruction::Call(call) => code.push_str(&self.transpile_call_to_lua(call, indent_level)),
+      IRInstruction::Return(_) if self.context.contains(&TranspilerContext::Constructor) => {
+        code.push_str(&format!("{}return self\n", " ".repeat(indent_level)));
+      }
       IRInstruction::Return(r) => {
         let value = self.transpile_ir_to_lua(&r.value, indent_level);
         code.push_str(&format!("{}return {}\n", " ".repeat(indent_level), value));
@@ -164,7 +191,113 @@ impl TranspilerToLua {
           value
         ));
       }
-      IRInstruction::Class(_) => todo!(),
+      IRInstruction::Class(class) => {
+        code.push_str(&self.transpile_class_to_lua(class, indent_level))
+      }
+      IRInstruction::Enum(ir_enum) => {
+        code.push_str(&self.transpile_enum_to_lua(ir_enum, indent_level))
+      }
+      IRInstruction::Interface(_) | IRInstruction::TypeAlias(_) => {
+        // Lua has no types, they are only checked by the analyzer.
+      }
+      IRInstruction::Error(_) => unreachable!("code with errors is not transpiled"),
+      IRInstruction::EnumValue(enum_value) => {
+        code.push_str(&format!("{}.{}", enum_value.enum_name, enum_value.variant));
+
+        if !enum_value.arguments.is_empty() {
+          code.push_str(&format!(
+            "({})",
+            self.transpile_arguments_to_lua(&enum_value.arguments)
+          ));
+        }
+      }
+      IRInstruction::Match(ir_match) => {
+        code.push_str(&self.transpile_match_expression_to_lua(ir_match, indent_level))
+      }
+      IRInstruction::Switch(switch) => {
+        code.push_str(&self.transpile_switch_to_lua(switch, indent_level))
+      }
+      IRInstruction::This(_) => code.push_str("self"),
+      IRInstruction::Get(get) if get.is_method && !get.metadata.is_static => {
+        // A method read as a value keeps its object as `self`.
+        code.push_str(&format!(
+          "(function(object) return function(...) return object:{}(...) end end)({})",
+          Self::member_name(&get.name, &get.metadata),
+          self.transpile_ir_to_lua(&get.object, 0)
+        ));
+      }
+      IRInstruction::Get(get) => {
+        code.push_str(&format!(
+          "{}.{}",
+          self.transpile_ir_to_lua(&get.object, 0),
+          Self::member_name(&get.name, &get.metadata)
+        ));
+      }
+      IRInstruction::Set(set) => {
+        let value = self.transpile_ir_to_lua(&set.value, 0);
+        code.push_str(&format!(
+          "{}{}.{} = {}\n",
+          " ".repeat(indent_level),
+          self.transpile_ir_to_lua(&set.object, 0),
+          Self::member_name(&set.name, &set.metadata),
+          value
+        ));
+      }
+      IRInstruction::Index(index) => {
+        let object = self.transpile_ir_to_lua(&index.object, 0);
+        let position = self.transpile_ir_to_lua(&index.index, 0);
+
+        // Lua tables and strings start at 1. The position of a character
+        // is used twice, it is stored in a local unless it is a literal or
+        // a variable.
+        match (&index.object_type, &*index.index) {
+          (DataType::String, IRInstruction::Literal(_) | IRInstruction::Variable(_)) => {
+            code.push_str(&format!(
+              "string.sub({}, {} + 1, {} + 1)",
+              object.trim_end(),
+              position,
+              position
+            ));
+          }
+          (DataType::String, _) => {
+            code.push_str(&format!(
+              "(function(position) return string.sub({}, position, position) end)(({}) + 1)",
+              object.trim_end(),
+              position
+            ));
+          }
+          _ => code.push_str(&format!("{}[({}) + 1]", object.trim_end(), position)),
+        }
+      }
+      IRInstruction::SetIndex(set) => {
+        let value = self.transpile_ir_to_lua(&set.value, 0);
+        code.push_str(&format!(
+          "{}{}[({}) + 1] = {}\n",
+          " ".repeat(indent_level),
+          self.transpile_ir_to_lua(&set.object, 0).trim_end(),
+          self.transpile_ir_to_lua(&set.index, 0),
+          value.trim_end()
+        ));
+      }
+      IRInstruction::New(new) => {
+        code.push_str(&format!(
+          "{}.new({})",
+          new.class_name,
+          self.transpile_arguments_to_lua(&new.arguments)
+        ));
+      }
+      IRInstruction::MethodCall(call) => {
+        // Instance methods receive the object as `self` through `:`.
+        let separator = if call.metadata.is_static { "." } else { ":" };
+
+        code.push_str(&format!(
+          "{}{}{}({})",
+          self.transpile_ir_to_lua(&call.object, 0),
+          separator,
+          Self::member_name(&call.name, &call.metadata),
+          self.transpile_arguments_to_lua(&call.arguments)
+        ));
+      }
       IRInstruction::Ternary(ternary) => {
         let condition = self.transpile_ir_to_lua(&ternary.condition, indent_level);
 
@@ -179,12 +312,23 @@ impl TranspilerToLua {
       IRInstruction::ForIn(for_in) => {
         self.context.push(TranspilerContext::For);
 
-        code.push_str(&format!(
-          "{}for _, {} in pairs({}) do\n",
-          " ".repeat(indent_level),
-          for_in.variable.name,
-          self.transpile_ir_to_lua(&for_in.iterable, indent_level)
-        ));
+        let iterable = self.transpile_ir_to_lua(&for_in.iterable, indent_level);
+
+        if for_in.iterable_type == DataType::String {
+          code.push_str(&format!(
+            "{}for {} in string.gmatch({}, \".\") do\n",
+            " ".repeat(indent_level),
+            for_in.variable.name,
+            iterable
+          ));
+        } else {
+          code.push_str(&format!(
+            "{}for _, {} in pairs({}) do\n",
+            " ".repeat(indent_level),
+            for_in.variable.name,
+            iterable
+          ));
+        }
 
 
         code.push_str(&self.transpile_ir_to_lua(&for_in.body, indent_level + 2));
@@ -303,7 +447,7 @@ impl TranspilerToLua {
       }
 
       for instr in &body.instructions {
-        code.push_str(&self.transpile_ir_to_lua(instr, indent_level + 2));
+        code.push_str(&self.transpile_statement_to_lua(instr, indent_level + 2));
       }
 
       code.push_str(format!("{}end\n", " ".repeat(indent_level)).as_str());
@@ -316,6 +460,596 @@ impl TranspilerToLua {
     code
   }
 
+  /**
+    A lambda is an anonymous Lua function, the variables it captures are
+    upvalues of that function.
+  */
+  fn transpile_lambda_to_lua(&mut self, lambda: &IRLambda, indent_level: usize) -> String {
+    let mut code = format!("function({})\n", Self::parameters_to_lua(&lambda.function));
+
+    // Loops and constructors that enclose the lambda do not apply to its body.
+    let context = std::mem::take(&mut self.context);
+
+    if let Some(body) = &lambda.function.body {
+      for instr in &body.instructions {
+        code.push_str(&self.transpile_statement_to_lua(instr, indent_level + 2));
+      }
+    }
+
+    self.context = context;
+
+    code.push_str(&format!("{}end", " ".repeat(indent_level)));
+
+    code
+  }
+
+  /**
+    The parts of a template are joined with `..`, the expressions are
+    converted with `tostring`.
+
+    ```lua
+    "Name: " .. tostring(name)
+    ```
+  */
+  fn transpile_template_literal_to_lua(&mut self, template: &IRTemplateLiteral) -> String {
+    if template.parts.is_empty() {
+      return "\"\"".to_string();
+    }
+
+    template
+      .parts
+      .iter()
+      .map(|part| match part {
+        IRInstruction::Literal(literal) if matches!(literal.value, AnalyzerValue::String(_)) => {
+          Self::value_to_lua(&literal.value)
+        }
+        part => format!("tostring({})", self.transpile_ir_to_lua(part, 0).trim_end()),
+      })
+      .collect::<Vec<String>>()
+      .join(" .. ")
+  }
+
+  /**
+    Transpiles an instruction that appears as a statement. Member accesses
+    are expressions, they get their own line when their value is discarded.
+  */
+  fn transpile_statement_to_lua(
+    &mut self,
+    instruction: &IRInstruction,
+    indent_level: usize,
+  ) -> String {
+    match instruction {
+      IRInstruction::This(_)
+      | IRInstruction::Get(_)
+      | IRInstruction::Index(_)
+      | IRInstruction::New(_)
+      | IRInstruction::MethodCall(_)
+      | IRInstruction::CallValue(_)
+      | IRInstruction::EnumValue(_) => format!(
+        "{}{}\n",
+        " ".repeat(indent_level),
+        self.transpile_ir_to_lua(instruction, indent_level)
+      ),
+      IRInstruction::Match(ir_match) => {
+        self.transpile_match_statement_to_lua(ir_match, indent_level)
+      }
+      IRInstruction::Function(func) => self.transpile_function_to_lua(func, indent_level),
+      _ => self.transpile_ir_to_lua(instruction, indent_level),
+    }
+  }
+
+  /**
+    A class becomes a table that is the metatable of its instances:
+
+    ```lua
+    local User = {}
+    User.__index = User
+
+    function User.new(name)
+      local self = setmetatable({}, User)
+      self._name = name
+      return self
+    end
+
+    function User:getName()
+      return self._name
+    end
+    ```
+
+    Static members are stored in the class table and private members are
+    prefixed with `_`.
+  */
+  fn transpile_class_to_lua(&mut self, class: &IRClass, indent_level: usize) -> String {
+    let indent = " ".repeat(indent_level);
+    let mut code = String::new();
+
+    code.push_str(&format!("{}local {} = {{}}\n", indent, class.name));
+    code.push_str(&format!(
+      "{}{}.__index = {}\n",
+      indent, class.name, class.name
+    ));
+
+    for field in &class.fields {
+      if let Some(value) = &field.value {
+        code.push_str(&format!(
+          "{}{}.{} = {}\n",
+          indent,
+          class.name,
+          Self::member_name(&field.name, &field.metadata),
+          self.transpile_ir_to_lua(value, 0)
+        ));
+      }
+    }
+
+    let parameters = class
+      .constructor
+      .as_ref()
+      .map_or(String::new(), |constructor| {
+        Self::parameters_to_lua(&constructor.function)
+      });
+
+    code.push_str(&format!(
+      "\n{}function {}.new({})\n",
+      indent, class.name, parameters
+    ));
+    code.push_str(&format!(
+      "{}  local self = setmetatable({{}}, {})\n",
+      indent, class.name
+    ));
+
+    if let Some(body) = class
+      .constructor
+      .as_ref()
+      .and_then(|c| c.function.body.as_ref())
+    {
+      self.context.push(TranspilerContext::Constructor);
+
+      for instruction in &body.instructions {
+        code.push_str(&self.transpile_statement_to_lua(instruction, indent_level + 2));
+      }
+
+      self.context.pop();
+    }
+
+    code.push_str(&format!("{}  return self\n", indent));
+    code.push_str(&format!("{}end\n", indent));
+
+    for method in &class.methods {
+      let separator = if method.metadata.is_static { "." } else { ":" };
+
+      code.push_str(&format!(
+        "\n{}function {}{}{}({})\n",
+        indent,
+        class.name,
+        separator,
+        Self::member_name(&method.function.name, &method.metadata),
+        Self::parameters_to_lua(&method.function)
+      ));
+
+      if let Some(body) = &method.function.body {
+        for instruction in &body.instructions {
+          code.push_str(&self.transpile_statement_to_lua(instruction, indent_level + 2));
+        }
+      }
+
+      code.push_str(&format!("{}end\n", indent));
+    }
+
+    code.push('\n');
+
+    code
+  }
+
+  /**
+    An enum becomes a table with an entry per variant. Variants with a
+    payload are functions that build a tagged table, the payload is stored
+    in the array part and its length in `n`:
+
+    ```lua
+    local __Option = { __eq = ... }
+    local Option = {}
+    Option.SOME = function(...) return setmetatable({ tag = "SOME", n = select("#", ...), ... }, __Option) end
+    Option.NONE = setmetatable({ tag = "NONE", n = 0 }, __Option)
+    ```
+
+    The tagged tables share a metatable whose `__eq` compares the tag and the
+    payload, so variants are equal like in the VM. Variants declared with a
+    constant are that constant.
+  */
+  fn transpile_enum_to_lua(&mut self, ir_enum: &IREnum, indent_level: usize) -> String {
+    let indent = " ".repeat(indent_level);
+    let metatable = format!("__{}", ir_enum.name);
+    let mut code = String::new();
+
+    let has_tags = ir_enum
+      .variants
+      .iter()
+      .any(|variant| variant.value.is_none());
+
+    if has_tags {
+      let lines = [
+        "  __eq = function(a, b)",
+        "    if getmetatable(a) ~= getmetatable(b) or a.tag ~= b.tag or a.n ~= b.n then",
+        "      return false",
+        "    end",
+        "    for i = 1, a.n do",
+        "      if a[i] ~= b[i] then",
+        "        return false",
+        "      end",
+        "    end",
+        "    return true",
+        "  end,",
+        "}",
+      ];
+
+      code.push_str(&format!("{}local {} = {{\n", indent, metatable));
+
+      for line in lines {
+        code.push_str(&format!("{}{}\n", indent, line));
+      }
+    }
+
+    code.push_str(&format!("{}local {} = {{}}\n", indent, ir_enum.name));
+
+    for variant in &ir_enum.variants {
+      let value = match &variant.value {
+        Some(AnalyzerValue::String(string)) => format!("\"{}\"", string),
+        Some(AnalyzerValue::Int(int)) => int.to_string(),
+        _ if variant.fields.is_empty() => format!(
+          "setmetatable({{ tag = \"{}\", n = 0 }}, {})",
+          variant.name, metatable
+        ),
+        _ => format!(
+          "function(...) return setmetatable({{ tag = \"{}\", n = select(\"#\", ...), ... }}, {}) end",
+          variant.name, metatable
+        ),
+      };
+
+      code.push_str(&format!(
+        "{}{}.{} = {}\n",
+        indent, ir_enum.name, variant.name, value
+      ));
+    }
+
+    code.push('\n');
+
+    code
+  }
+
+  /**
+    A `match` used as a statement keeps `return`, `break` and `continue` of
+    its arms working, so it is lowered in place:
+
+    ```lua
+    do
+      local __match = value
+      local __matched = false
+      if not __matched and __match.tag == "SOME" then
+        local x = __match[1]
+        if x > 0 then
+          __matched = true
+          print(x)
+        end
+      end
+    end
+    ```
+  */
+  fn transpile_match_statement_to_lua(
+    &mut self,
+    ir_match: &IRMatch,
+    indent_level: usize,
+  ) -> String {
+    let indent = " ".repeat(indent_level);
+    let inner = " ".repeat(indent_level + 2);
+    let mut code = format!("{}do\n", indent);
+
+    code.push_str(&format!(
+      "{}local __match = {}\n",
+      inner,
+      self.transpile_ir_to_lua(&ir_match.value, 0)
+    ));
+    code.push_str(&format!("{}local __matched = false\n", inner));
+
+    for arm in &ir_match.arms {
+      let arm_indent = " ".repeat(indent_level + 4);
+
+      code.push_str(&format!(
+        "{}if not __matched and {} then\n",
+        inner,
+        Self::match_pattern_to_lua(&arm.pattern)
+      ));
+      code.push_str(&Self::match_bindings_to_lua(arm, indent_level + 4));
+
+      let body_indent = match &arm.guard {
+        Some(guard) => {
+          code.push_str(&format!(
+            "{}if {} then\n",
+            arm_indent,
+            self.transpile_ir_to_lua(guard, 0)
+          ));
+
+          indent_level + 6
+        }
+        None => indent_level + 4,
+      };
+
+      code.push_str(&format!("{}__matched = true\n", " ".repeat(body_indent)));
+
+      match &*arm.body {
+        IRInstruction::Literal(_)
+        | IRInstruction::Binary(_)
+        | IRInstruction::Unary(_)
+        | IRInstruction::Logical(_)
+        | IRInstruction::Ternary(_)
+        | IRInstruction::Variable(_)
+        | IRInstruction::Array(_) => code.push_str(&format!(
+          "{}local _ = {}\n",
+          " ".repeat(body_indent),
+          self.transpile_ir_to_lua(&arm.body, body_indent)
+        )),
+        body => code.push_str(&self.transpile_statement_to_lua(body, body_indent)),
+      }
+
+      if arm.guard.is_some() {
+        code.push_str(&format!("{}end\n", arm_indent));
+      }
+
+      code.push_str(&format!("{}end\n", inner));
+    }
+
+    code.push_str(&format!("{}end\n", indent));
+
+    code
+  }
+
+  /**
+    A `match` used as a value is a function called with the matched value,
+    the first arm that matches returns its value.
+  */
+  fn transpile_match_expression_to_lua(
+    &mut self,
+    ir_match: &IRMatch,
+    indent_level: usize,
+  ) -> String {
+    let inner = " ".repeat(indent_level + 2);
+    let mut code = String::from("(function(__match)\n");
+
+    for arm in &ir_match.arms {
+      let arm_indent = " ".repeat(indent_level + 4);
+
+      code.push_str(&format!(
+        "{}if {} then\n",
+        inner,
+        Self::match_pattern_to_lua(&arm.pattern)
+      ));
+      code.push_str(&Self::match_bindings_to_lua(arm, indent_level + 4));
+
+      let body_indent = match &arm.guard {
+        Some(guard) => {
+          code.push_str(&format!(
+            "{}if {} then\n",
+            arm_indent,
+            self.transpile_ir_to_lua(guard, 0)
+          ));
+
+          indent_level + 6
+        }
+        None => indent_level + 4,
+      };
+
+      match &*arm.body {
+        IRInstruction::Block(_) => {
+          code.push_str(&self.transpile_ir_to_lua(&arm.body, body_indent));
+          code.push_str(&format!("{}return nil\n", " ".repeat(body_indent)));
+        }
+        body => code.push_str(&format!(
+          "{}return {}\n",
+          " ".repeat(body_indent),
+          self.transpile_ir_to_lua(body, body_indent)
+        )),
+      }
+
+      if arm.guard.is_some() {
+        code.push_str(&format!("{}end\n", arm_indent));
+      }
+
+      code.push_str(&format!("{}end\n", inner));
+    }
+
+    code.push_str(&format!(
+      "{}end)({})",
+      " ".repeat(indent_level),
+      self.transpile_ir_to_lua(&ir_match.value, 0)
+    ));
+
+    code
+  }
+
+  /**
+    A `switch` becomes an if/elseif chain inside a loop that runs once, so
+    `break` leaves it. A case that falls through gets the bodies of the
+    cases that follow it:
+
+    ```lua
+    repeat
+      local __switch = value
+      if __switch == 1 or __switch == 2 then
+        print("small")
+      else
+        print("big")
+      end
+    until true
+    ```
+  */
+  fn transpile_switch_to_lua(&mut self, switch: &IRSwitch, indent_level: usize) -> String {
+    let indent = " ".repeat(indent_level);
+    let inner = " ".repeat(indent_level + 2);
+    let mut code = format!("{}repeat\n", indent);
+
+    code.push_str(&format!(
+      "{}local __switch = {}\n",
+      inner,
+      self.transpile_ir_to_lua(&switch.value, 0)
+    ));
+
+    // Cases with an empty body share the body of the next case.
+    let mut branches: Vec<(Vec<String>, usize)> = Vec::new();
+    let mut conditions: Vec<String> = Vec::new();
+
+    for (index, case) in switch.cases.iter().enumerate() {
+      if let Some(value) = &case.value {
+        conditions.push(format!(
+          "__switch == {}",
+          self.transpile_ir_to_lua(value, 0)
+        ));
+      }
+
+      if !case.body.is_empty() || index == switch.cases.len() - 1 {
+        if !conditions.is_empty() {
+          branches.push((conditions, index));
+        }
+
+        conditions = Vec::new();
+      }
+    }
+
+    for (i, (conditions, start)) in branches.iter().enumerate() {
+      let keyword = if i == 0 { "if" } else { "elseif" };
+
+      code.push_str(&format!(
+        "{}{} {} then\n",
+        inner,
+        keyword,
+        conditions.join(" or ")
+      ));
+      code.push_str(&self.transpile_switch_body_to_lua(switch, *start, indent_level + 4));
+    }
+
+    if let Some(default) = switch.default_case() {
+      let body = self.transpile_switch_body_to_lua(switch, default, indent_level + 4);
+
+      if branches.is_empty() {
+        code.push_str(&format!("{}do\n{}", inner, body));
+      } else {
+        code.push_str(&format!("{}else\n{}", inner, body));
+      }
+    }
+
+    if !branches.is_empty() || switch.default_case().is_some() {
+      code.push_str(&format!("{}end\n", inner));
+    }
+
+    code.push_str(&format!("{}until true\n", indent));
+
+    code
+  }
+
+  /**
+    The bodies run when the case `start` matches, up to the first one that
+    does not fall through. Its trailing `break` is not needed, the chain ends
+    there.
+  */
+  fn transpile_switch_body_to_lua(
+    &mut self,
+    switch: &IRSwitch,
+    start: usize,
+    indent_level: usize,
+  ) -> String {
+    let mut code = String::new();
+
+    for case in &switch.cases[start..] {
+      let body = match case.body.last() {
+        Some(IRInstruction::Break(_)) => &case.body[..case.body.len() - 1],
+        _ => &case.body[..],
+      };
+
+      for instruction in body {
+        code.push_str(&self.transpile_statement_to_lua(instruction, indent_level));
+      }
+
+      if !case.falls_through() {
+        break;
+      }
+    }
+
+    code
+  }
+
+  fn match_pattern_to_lua(pattern: &IRMatchPattern) -> String {
+    match pattern {
+      IRMatchPattern::Literal(value) => format!("__match == {}", Self::value_to_lua(value)),
+      IRMatchPattern::Wildcard => "true".to_string(),
+      IRMatchPattern::Variant {
+        enum_name,
+        variant,
+        value: Some(_),
+        ..
+      } => format!("__match == {}.{}", enum_name, variant),
+      IRMatchPattern::Variant { variant, .. } => format!("__match.tag == \"{}\"", variant),
+    }
+  }
+
+  fn match_bindings_to_lua(arm: &IRMatchArm, indent_level: usize) -> String {
+    let mut code = String::new();
+
+    if let IRMatchPattern::Variant { bindings, .. } = &arm.pattern {
+      for (index, name) in bindings.iter().enumerate() {
+        if let Some(name) = name {
+          code.push_str(&format!(
+            "{}local {} = __match[{}]\n",
+            " ".repeat(indent_level),
+            name,
+            index + 1
+          ));
+        }
+      }
+    }
+
+    code
+  }
+
+  fn value_to_lua(value: &AnalyzerValue) -> String {
+    match value {
+      AnalyzerValue::Int(num) => num.to_string(),
+      // Quotes, backslashes and line breaks are escaped as in Lua.
+      AnalyzerValue::String(s) => format!("{:?}", s),
+      AnalyzerValue::Float(num) => num.to_string(),
+      AnalyzerValue::Boolean(boolean) => boolean.to_string(),
+      AnalyzerValue::Return(r) => r.to_string(),
+      AnalyzerValue::Function(f) => f.name.span.literal.clone(),
+      AnalyzerValue::Null | AnalyzerValue::None => "nil".to_string(),
+    }
+  }
+
+  /**
+    Lua has no access modifiers, private members are kept private by the
+    `_` prefix convention.
+  */
+  fn member_name(name: &str, metadata: &IRClassMemberMetadata) -> String {
+    if metadata.is_public {
+      name.to_string()
+    } else {
+      format!("_{}", name)
+    }
+  }
+
+  fn parameters_to_lua(function: &IRFunction) -> String {
+    function
+      .parameters
+      .iter()
+      .map(|x| x.name.clone())
+      .collect::<Vec<String>>()
+      .join(", ")
+  }
+
+  fn transpile_arguments_to_lua(&mut self, arguments: &[IRInstruction]) -> String {
+    arguments
+      .iter()
+      .map(|x| self.transpile_ir_to_lua(x, 0))
+      .collect::<Vec<String>>()
+      .join(", ")
+  }
+
   fn transpile_opeartor_to_lua(&self, operator: &IRInstructionType) -> String {
     match operator {
       IRInstructionType::Add => "+",
@@ -395,7 +1129,7 @@ impl TranspilerToLua {
     let var_value = if let Some(value) = &variable.value {
       self.transpile_ir_to_lua(value, 0)
     } else {
-      "".to_string()
+      "nil".to_string()
     };
 
     if variable.metadata.is_declaration {
@@ -410,3 +1144,112 @@ impl TranspilerToLua {
     }
   }
 }
+
+impl CodeGenerator for TranspilerToLua {
+  fn generate_code(
+    &mut self,
+    irs: &HashMap<String, Vec<IRInstruction>>,
+  ) -> Result<Vec<CodeResult>, Vec<Diagnostic>> {
+    let mut code_results: Vec<CodeResult> = vec![];
+
+    for (module_path, ir) in irs {
+      self.transpile(ir);
+
+      code_results.push(CodeResult::new(
+        self.code.clone().into_bytes(),
+        module_path.clone(),
+      ));
+    }
+
+    Ok(code_results)
+  }
+
+  fn file_extension(&self) -> &str {
+    "lua"
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use analyzer::Analyzer;
+  use lexer::Lexer;
+  use parser::Parser;
+
+  /**
+    Transpiles `source`, it must have no errors.
+  */
+  fn lua(source: &str) -> String {
+    let mut lexer = Lexer::new(source, "main.ign".to_string());
+    lexer.scan_tokens();
+
+    let mut parser = Parser::new(lexer.tokens);
+    let statements = parser.parse();
+    assert!(parser.diagnostics.is_empty());
+    let mut analyzer = Analyzer::new("main.ign".to_string());
+    analyzer.analyze(&statements);
+    assert!(
+      analyzer.diagnostics.is_empty(),
+      "Unexpected errors {:?}",
+      analyzer.diagnostics
+    );
+
+    let mut transpiler = TranspilerToLua::new();
+    transpiler.transpile(&analyzer.irs["main.ign"]);
+
+    transpiler.code
+  }
+
+  const COUNTER: &str = "class Counter {\n  public static created: int = 0;\n  private count: int = 0;\n\n  public Counter(start: int) {\n    this.count = start;\n    Counter.created = Counter.created + 1;\n  }\n\n  public increment(): int {\n    this.count = this.count + 1;\n    return this.count;\n  }\n\n  public static make(): Counter {\n    return new Counter(0);\n  }\n}\n";
+
+  #[test]
+  fn test_classes() {
+    let code = lua(&format!(
+      "{}let counter: Counter = Counter.make();\ncounter.increment();",
+      COUNTER
+    ));
+
+    assert_eq!(
+      code,
+      "local Counter = {}\nCounter.__index = Counter\nCounter.created = 0\n\nfunction Counter.new(start)\n  local self = setmetatable({}, Counter)\n  self._count = 0\n  self._count = start\n  Counter.created = Counter.created + 1\n  return self\nend\n\nfunction Counter:increment()\n  self._count = self._count + 1\n  return self._count\nend\n\nfunction Counter.make()\n  return Counter.new(0)\nend\n\nlocal counter = Counter.make()\ncounter:increment()\n"
+    );
+  }
+
+  #[test]
+  fn test_method_as_value() {
+    let code = lua(&format!(
+      "{}let counter: Counter = new Counter(1);\nlet increment: () -> int = counter.increment;\nincrement();\nlet make: () -> Counter = Counter.make;",
+      COUNTER
+    ));
+
+    assert!(code.contains(
+      "local increment = (function(object) return function(...) return object:increment(...) end end)(counter)\n"
+    ));
+    assert!(code.contains("local make = Counter.make\n"));
+  }
+
+  #[test]
+  fn test_index_expressions() {
+    let code = lua(
+      "let mut items: int[] = [1, 2];\nlet i: int = 0;\nitems[i > 0 ? 1 : 0] = items[i];\nlet s: string = \"ab\";\nlet a: string = s[i];\nlet b: string = s[i > 0 ? 1 : 0];",
+    );
+
+    assert!(code.contains("items[(i > 0 and 1 or 0) + 1] = items[(i) + 1]\n"));
+    assert!(code.contains("local a = string.sub(s, i + 1, i + 1)\n"));
+    assert!(code.contains(
+      "local b = (function(position) return string.sub(s, position, position) end)((i > 0 and 1 or 0) + 1)\n"
+    ));
+  }
+
+  #[test]
+  fn test_enum_equality() {
+    let code = lua(
+      "enum Option<T> {\n  SOME(T),\n  NONE\n}\nlet a: Option<int> = Option.SOME(1);\nlet b: Option<int> = Option.SOME(1);\nlet same: boolean = a == b;",
+    );
+
+    assert_eq!(
+      code,
+      "local __Option = {\n  __eq = function(a, b)\n    if getmetatable(a) ~= getmetatable(b) or a.tag ~= b.tag or a.n ~= b.n then\n      return false\n    end\n    for i = 1, a.n do\n      if a[i] ~= b[i] then\n        return false\n      end\n    end\n    return true\n  end,\n}\nlocal Option = {}\nOption.SOME = function(...) return setmetatable({ tag = \"SOME\", n = select(\"#\", ...), ... }, __Option) end\nOption.NONE = setmetatable({ tag = \"NONE\", n = 0 }, __Option)\n\nlocal a = Option.SOME(1)\nlocal b = Option.SOME(1)\nlocal same = a == b\n"
+    );
+  }
+}
@@ -0,0 +1,75 @@
+use std::rc::Rc;
+
+use bytecode_generator::FunctionPrototype;
+
+/**
+  A single activation record.
+
+  - function: the function being executed by this frame.
+  - ip: index of the next byte to read in the function bytecode.
+  - slot: index in the VM stack where the frame's window starts, it holds
+    the called function followed by its arguments and locals.
+*/
+#[derive(Debug, Clone)]
+pub struct CallFrame {
+  pub function: Rc<FunctionPrototype>,
+  pub ip: usize,
+  pub slot: usize,
+  pub module_path: Rc<str>,
+}
+
+impl CallFrame {
+  pub fn new(function: Rc<FunctionPrototype>, slot: usize, module_path: Rc<str>) -> Self {
+    Self {
+      function,
+      ip: 0,
+      slot,
+      module_path,
+    }
+  }
+
+  pub fn is_at_end(&self) -> bool {
+    self.ip >= self.function.bytecode.code.len()
+  }
+
+  pub fn read_byte(&mut self) -> u8 {
+    let byte = self.function.bytecode.code[self.ip];
+    self.ip += 1;
+
+    byte
+  }
+
+  pub fn read_short(&mut self) -> u16 {
+    let high = self.read_byte() as u16;
+    let low = self.read_byte() as u16;
+
+    (high << 8) | low
+  }
+
+  /**
+    Reads a big endian operand of `width` bytes.
+  */
+  pub fn read_operand(&mut self, width: usize) -> usize {
+    let operand = self.function.bytecode.read_operand(self.ip, width);
+    self.ip += width;
+
+    operand
+  }
+
+  /**
+    Line of the instruction that was read last.
+  */
+  pub fn current_line(&self) -> usize {
+    if self.ip == 0 {
+      return 0;
+    }
+
+    self
+      .function
+      .bytecode
+      .lines
+      .get(self.ip - 1)
+      .copied()
+      .unwrap_or(0)
+  }
+}
@@ -0,0 +1,329 @@
+use std::{collections::HashMap, fmt::Display, rc::Rc};
+
+use crate::{
+  object::{ObjRef, ObjUpvalue, Object},
+  vm_value::VMValue,
+};
+
+const INITIAL_NEXT_GC: usize = 1024 * 1024;
+const HEAP_GROW_FACTOR: usize = 2;
+
+/**
+  Counters collected by the garbage collector since the VM was created.
+*/
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GCStats {
+  pub collections: usize,
+  pub objects_allocated: usize,
+  pub objects_freed: usize,
+  pub bytes_allocated: usize,
+  pub bytes_freed: usize,
+  pub peak_bytes: usize,
+}
+
+impl Display for GCStats {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    writeln!(f, "GC collections:     {}", self.collections)?;
+    writeln!(f, "Objects allocated:  {}", self.objects_allocated)?;
+    writeln!(f, "Objects freed:      {}", self.objects_freed)?;
+    writeln!(
+      f,
+      "Live objects:       {}",
+      self.objects_allocated - self.objects_freed
+    )?;
+    writeln!(f, "Bytes allocated:    {}", self.bytes_allocated)?;
+    writeln!(f, "Bytes freed:        {}", self.bytes_freed)?;
+    write!(f, "Peak heap size:     {}", self.peak_bytes)
+  }
+}
+
+struct HeapObject {
+  marked: bool,
+  size: usize,
+  object: Object,
+}
+
+/**
+  Storage for every object created by the VM, managed by a mark and sweep
+  collector.
+
+  - objects: object slots, freed slots are reused by the next allocations.
+  - strings: interned strings, each distinct string is allocated only once.
+  - bytes: approximate size of the live objects.
+  - next_gc: size that triggers the next collection.
+  - stress: collect before every allocation, used to test the roots.
+*/
+pub struct Heap {
+  objects: Vec<Option<HeapObject>>,
+  free: Vec<usize>,
+  strings: HashMap<Rc<str>, ObjRef>,
+  gray: Vec<ObjRef>,
+  bytes: usize,
+  next_gc: usize,
+  pub stress: bool,
+  pub stats: GCStats,
+}
+
+impl Heap {
+  pub fn new() -> Self {
+    Self {
+      objects: Vec::new(),
+      free: Vec::new(),
+      strings: HashMap::new(),
+      gray: Vec::new(),
+      bytes: 0,
+      next_gc: INITIAL_NEXT_GC,
+      stress: false,
+      stats: GCStats::default(),
+    }
+  }
+
+  /**
+    Whether the next allocation should be preceded by a collection.
+  */
+  pub fn should_collect(&self) -> bool {
+    self.stress || self.bytes > self.next_gc
+  }
+
+  pub fn alloc(&mut self, object: Object) -> ObjRef {
+    let size = object.size();
+    let object = HeapObject {
+      marked: false,
+      size,
+      object,
+    };
+
+    let index = match self.free.pop() {
+      Some(index) => {
+        self.objects[index] = Some(object);
+        index
+      }
+      None => {
+        self.objects.push(Some(object));
+        self.objects.len() - 1
+      }
+    };
+
+    self.bytes += size;
+    self.stats.objects_allocated += 1;
+    self.stats.bytes_allocated += size;
+    self.stats.peak_bytes = self.stats.peak_bytes.max(self.bytes);
+
+    ObjRef(index)
+  }
+
+  /**
+    Returns the interned string with the contents of `string`, allocating it
+    the first time it is seen.
+  */
+  pub fn intern(&mut self, string: &str) -> ObjRef {
+    if let Some(reference) = self.strings.get(string) {
+      return *reference;
+    }
+
+    let string: Rc<str> = Rc::from(string);
+    let reference = self.alloc(Object::String(string.clone()));
+    self.strings.insert(string, reference);
+
+    reference
+  }
+
+  pub fn get(&self, reference: ObjRef) -> &Object {
+    &self.objects[reference.0].as_ref().unwrap().object
+  }
+
+  pub fn get_mut(&mut self, reference: ObjRef) -> &mut Object {
+    &mut self.objects[reference.0].as_mut().unwrap().object
+  }
+
+  pub fn live_objects(&self) -> usize {
+    self.objects.len() - self.free.len()
+  }
+
+  pub fn type_name(&self, value: &VMValue) -> &'static str {
+    match value {
+      VMValue::Object(reference) => self.get(*reference).type_name(),
+      _ => value.type_name(),
+    }
+  }
+
+  pub fn as_str(&self, value: &VMValue) -> Option<&str> {
+    match value {
+      VMValue::Object(reference) => match self.get(*reference) {
+        Object::String(string) => Some(string),
+        _ => None,
+      },
+      _ => None,
+    }
+  }
+
+  /**
+    Text used by `println` and string concatenation.
+  */
+  pub fn format(&self, value: &VMValue) -> String {
+    let reference = match value {
+      VMValue::Object(reference) => *reference,
+      _ => return value.to_string(),
+    };
+
+    match self.get(reference) {
+      Object::String(string) => string.to_string(),
+      Object::Array(array) => {
+        let elements = array
+          .iter()
+          .map(|x| self.format(x))
+          .collect::<Vec<String>>();
+
+        format!("[{}]", elements.join(", "))
+      }
+      Object::Function(function) => format!("<fn {}>", function.name),
+      Object::Native(native) => format!("<native fn {}>", native.name),
+      Object::Closure(closure) => self.format(&VMValue::Object(closure.function)),
+      Object::Upvalue(ObjUpvalue::Closed(value)) => self.format(value),
+      Object::Upvalue(ObjUpvalue::Open(_)) => "<upvalue>".to_string(),
+      Object::Instance(instance) => format!("<{} instance>", instance.class_name),
+      Object::Class(class) => format!("<class {}>", class.name),
+      Object::BoundMethod(bound) => self.format(&VMValue::Object(bound.method)),
+      Object::Variant(variant) if variant.values.is_empty() => {
+        format!("{}.{}", variant.enum_name, variant.name)
+      }
+      Object::Variant(variant) => {
+        let values = variant
+          .values
+          .iter()
+          .map(|x| self.format(x))
+          .collect::<Vec<String>>();
+
+        format!(
+          "{}.{}({})",
+          variant.enum_name,
+          variant.name,
+          values.join(", ")
+        )
+      }
+    }
+  }
+
+  /**
+    Compares two values, variants are equal when they are the same variant
+    with equal payloads. Other objects are compared by reference.
+  */
+  pub fn values_equal(&self, left: &VMValue, right: &VMValue) -> bool {
+    match (left, right) {
+      (VMValue::Object(a), VMValue::Object(b)) if a != b => match (self.get(*a), self.get(*b)) {
+        (Object::Variant(a), Object::Variant(b)) => {
+          a.enum_name == b.enum_name
+            && a.name == b.name
+            && a.values.len() == b.values.len()
+            && a
+              .values
+              .iter()
+              .zip(&b.values)
+              .all(|(a, b)| self.values_equal(a, b))
+        }
+        _ => false,
+      },
+      _ => left == right,
+    }
+  }
+
+  /**
+    Frees every object that is not reachable from `roots`.
+  */
+  pub fn collect<'a>(&mut self, roots: impl Iterator<Item = &'a VMValue>) {
+    let before = self.bytes;
+
+    for root in roots {
+      self.mark_value(root);
+    }
+
+    self.trace();
+    self.sweep();
+
+    self.next_gc = (self.bytes * HEAP_GROW_FACTOR).max(INITIAL_NEXT_GC);
+    self.stats.collections += 1;
+    self.stats.bytes_freed += before - self.bytes;
+  }
+
+  pub fn mark_value(&mut self, value: &VMValue) {
+    if let VMValue::Object(reference) = value {
+      self.mark_object(*reference);
+    }
+  }
+
+  pub fn mark_object(&mut self, reference: ObjRef) {
+    let object = self.objects[reference.0].as_mut().unwrap();
+
+    if object.marked {
+      return;
+    }
+
+    object.marked = true;
+    self.gray.push(reference);
+  }
+
+  fn trace(&mut self) {
+    while let Some(reference) = self.gray.pop() {
+      let mut children: Vec<VMValue> = vec![];
+
+      match self.get(reference) {
+        Object::String(_) | Object::Function(_) | Object::Native(_) => (),
+        Object::Array(array) => children.extend(array.iter().copied()),
+        Object::Closure(closure) => {
+          children.push(VMValue::Object(closure.function));
+          children.extend(closure.upvalues.iter().map(|x| VMValue::Object(*x)));
+        }
+        Object::Upvalue(ObjUpvalue::Closed(value)) => children.push(*value),
+        // The stack is a root, the value of an open upvalue is already marked.
+        Object::Upvalue(ObjUpvalue::Open(_)) => (),
+        Object::Instance(instance) => {
+          children.push(VMValue::Object(instance.class));
+          children.extend(instance.fields.values().copied());
+        }
+        Object::Class(class) => {
+          children.extend(class.methods.values().copied());
+          children.extend(class.statics.values().copied());
+        }
+        Object::BoundMethod(bound) => {
+          children.push(bound.receiver);
+          children.push(VMValue::Object(bound.method));
+        }
+        Object::Variant(variant) => children.extend(variant.values.iter().copied()),
+      }
+
+      for child in &children {
+        self.mark_value(child);
+      }
+    }
+  }
+
+  fn sweep(&mut self) {
+    for (index, slot) in self.objects.iter_mut().enumerate() {
+      let object = match slot {
+        Some(object) => object,
+        None => continue,
+      };
+
+      if object.marked {
+        object.marked = false;
+        continue;
+      }
+
+      if let Object::String(string) = &object.object {
+        self.strings.remove(string);
+      }
+
+      self.bytes -= object.size;
+      self.stats.objects_freed += 1;
+
+      *slot = None;
+      self.free.push(index);
+    }
+  }
+}
+
+impl Default for Heap {
+  fn default() -> Self {
+    Self::new()
+  }
+}
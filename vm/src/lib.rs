@@ -0,0 +1,1706 @@
+pub mod call_frame;
+pub mod heap;
+pub mod native;
+pub mod object;
+pub mod vm_error;
+pub mod vm_value;
+
+use std::{collections::HashMap, rc::Rc};
+
+use bytecode_generator::{Bytecode, FunctionPrototype, NativeDeclaration, OpCode, Value};
+use call_frame::CallFrame;
+use heap::{GCStats, Heap};
+use native::{NativeFunction, NativeRegistry};
+use object::{
+  ObjBoundMethod, ObjClass, ObjClosure, ObjInstance, ObjRef, ObjUpvalue, ObjVariant, Object,
+};
+use vm_error::{VMError, VMErrorKind};
+use vm_value::VMValue;
+
+pub type VMResult<T> = Result<T, VMError>;
+
+/**
+  Deepest call stack, the frames and the operand stack grow on demand up to
+  these limits.
+*/
+const FRAMES_MAX: usize = 1 << 16;
+const STACK_MAX: usize = FRAMES_MAX * 256;
+
+/*
+ * IVM
+ *
+ * Stack based virtual machine that executes the output of the `BytecodeGenerator`.
+ *
+ * - frames: call stack, the last frame is the one being executed.
+ * - stack: operand stack shared by every frame, each frame owns the values
+ *   from its `slot` to the top.
+ * - globals: top level variables and functions of the module.
+ * - heap: objects created by the program, the stack and the globals are the
+ *   roots of the garbage collector.
+ * - natives: host functions bound to the `@extern` declarations.
+ * - open_upvalues: upvalues that still point to a slot of the stack.
+ * - module_path: module of the last script, used by the functions the host
+ *   calls after it finished.
+*/
+pub struct VM {
+  frames: Vec<CallFrame>,
+  stack: Vec<VMValue>,
+  globals: HashMap<String, VMValue>,
+  heap: Heap,
+  natives: NativeRegistry,
+  open_upvalues: Vec<ObjRef>,
+  module_path: Rc<str>,
+}
+
+impl VM {
+  pub fn new() -> Self {
+    Self::with_natives(NativeRegistry::with_std())
+  }
+
+  pub fn with_natives(natives: NativeRegistry) -> Self {
+    Self {
+      frames: Vec::new(),
+      stack: Vec::new(),
+      globals: HashMap::new(),
+      heap: Heap::new(),
+      natives,
+      open_upvalues: Vec::new(),
+      module_path: Rc::from("<host>"),
+    }
+  }
+
+  /**
+  Registry used to resolve the `@extern` functions, embedders add their own
+  functions to it before running a script.
+  */
+  pub fn natives_mut(&mut self) -> &mut NativeRegistry {
+    &mut self.natives
+  }
+
+  pub fn gc_stats(&self) -> &GCStats {
+    &self.heap.stats
+  }
+
+  /**
+  Runs the collector before every allocation, to find values that are used
+  without being reachable from the roots.
+  */
+  pub fn set_gc_stress(&mut self, stress: bool) {
+    self.heap.stress = stress;
+  }
+
+  pub fn heap(&self) -> &Heap {
+    &self.heap
+  }
+
+  /**
+  Text of `value` as `println` would print it.
+  */
+  pub fn format_value(&self, value: &VMValue) -> String {
+    self.heap.format(value)
+  }
+
+  /**
+  Frees every object that can not be reached from the stack or the globals.
+  */
+  pub fn collect_garbage(&mut self) {
+    for upvalue in &self.open_upvalues {
+      self.heap.mark_object(*upvalue);
+    }
+
+    self
+      .heap
+      .collect(self.stack.iter().chain(self.globals.values()));
+  }
+
+  /**
+  Executes `bytecode` as the top level code of `module_path`.
+  Returns the value produced by the last `Return`, or `null` when the code
+  runs until the end without returning.
+  */
+  pub fn interpret(&mut self, bytecode: Bytecode, module_path: String) -> VMResult<VMValue> {
+    self.stack.clear();
+    self.frames.clear();
+    self.open_upvalues.clear();
+
+    let script = Rc::new(FunctionPrototype::new("<script>".to_string(), 0, bytecode));
+    let closure = self.alloc_closure(script.clone());
+
+    self.module_path = Rc::from(module_path);
+    self.stack.push(VMValue::Object(closure));
+    self
+      .frames
+      .push(CallFrame::new(script, 0, self.module_path.clone()));
+
+    let result = self.run(0);
+
+    if result.is_err() {
+      self.stack.clear();
+      self.frames.clear();
+      self.open_upvalues.clear();
+    }
+
+    result
+  }
+
+  /**
+  Calls `callee` with `arguments` from the host, after a script has run.
+  Functions declared by the script stay in the globals between calls.
+  */
+  pub fn call_value(&mut self, callee: VMValue, arguments: &[VMValue]) -> VMResult<VMValue> {
+    let depth = self.frames.len();
+    let slot = self.stack.len();
+
+    self.stack.push(callee);
+    self.stack.extend_from_slice(arguments);
+
+    // Native functions return right away, script functions push a frame.
+    let result = self.call(arguments.len()).and_then(|_| {
+      if self.frames.len() > depth {
+        self.run(depth)
+      } else {
+        Ok(self.stack.last().copied().unwrap_or(VMValue::Null))
+      }
+    });
+
+    self.close_upvalues(slot);
+    self.stack.truncate(slot);
+    self.frames.truncate(depth);
+
+    result
+  }
+
+  pub fn get_global(&self, name: &str) -> Option<VMValue> {
+    self.globals.get(name).copied()
+  }
+
+  /**
+  Defines or replaces a global, the value is visible to the scripts run
+  afterwards.
+  */
+  pub fn set_global(&mut self, name: &str, value: VMValue) {
+    self.globals.insert(name.to_string(), value);
+  }
+
+  /**
+  Heap used by the host to create the strings and arrays it passes to the
+  scripts. Allocating through it never runs the collector.
+  */
+  pub fn heap_mut(&mut self) -> &mut Heap {
+    &mut self.heap
+  }
+
+  /**
+  Executes instructions until the frame count drops back to `depth`.
+  */
+  fn run(&mut self, depth: usize) -> VMResult<VMValue> {
+    loop {
+      if self.frame().is_at_end() {
+        let frame = self.frames.pop().unwrap();
+        self.close_upvalues(frame.slot);
+        self.stack.truncate(frame.slot);
+
+        if self.frames.len() == depth {
+          return Ok(VMValue::Null);
+        }
+
+        self.push(VMValue::Null)?;
+        continue;
+      }
+
+      let op = self.read_op()?;
+
+      match op {
+        OpCode::ConstantInt
+        | OpCode::ConstantFloat
+        | OpCode::ConstantString
+        | OpCode::Function
+        | OpCode::ConstantIntLong
+        | OpCode::ConstantFloatLong
+        | OpCode::ConstantStringLong
+        | OpCode::FunctionLong
+        | OpCode::Native
+        | OpCode::NativeLong => {
+          let constant = self.read_constant(op)?;
+          self.push(constant)?;
+        }
+        OpCode::Null => self.push(VMValue::Null)?,
+        OpCode::True => self.push(VMValue::Boolean(true))?,
+        OpCode::False => self.push(VMValue::Boolean(false))?,
+        OpCode::Pop => {
+          self.pop()?;
+        }
+        OpCode::Add | OpCode::Subtract | OpCode::Multiply | OpCode::Divide | OpCode::Modulo => {
+          let right = self.pop()?;
+          let left = self.pop()?;
+
+          let result = self.binary_arithmetic(op, left, right)?;
+          self.push(result)?;
+        }
+        OpCode::Negate => {
+          let value = self.pop()?;
+
+          let result = match value {
+            VMValue::Int(int) => match int.checked_neg() {
+              Some(result) => VMValue::Int(result),
+              None => return Err(self.error(VMErrorKind::IntegerOverflow(op))),
+            },
+            VMValue::Float(float) => VMValue::Float(-float),
+            _ => return Err(self.invalid_operand(op, &value)),
+          };
+
+          self.push(result)?;
+        }
+        OpCode::Not => {
+          let value = self.pop()?;
+          self.push(VMValue::Boolean(value.is_falsy()))?;
+        }
+        OpCode::Equal | OpCode::NotEqual => {
+          let right = self.pop()?;
+          let left = self.pop()?;
+
+          let equal = self.heap.values_equal(&left, &right);
+          self.push(VMValue::Boolean(if op == OpCode::Equal {
+            equal
+          } else {
+            !equal
+          }))?;
+        }
+        OpCode::Greater | OpCode::GreaterEqual | OpCode::Less | OpCode::LessEqual => {
+          let right = self.pop()?;
+          let left = self.pop()?;
+
+          let result = self.compare(op, left, right)?;
+          self.push(VMValue::Boolean(result))?;
+        }
+        OpCode::Concatenate => {
+          let right = self.peek(0)?;
+          let left = self.peek(1)?;
+
+          let string = format!("{}{}", self.heap.format(left), self.heap.format(right));
+          let string = self.intern(&string);
+
+          self.pop()?;
+          self.pop()?;
+          self.push(VMValue::Object(string))?;
+        }
+        OpCode::DefineGlobal | OpCode::DefineGlobalLong => {
+          let (function, index) = self.read_string(op)?;
+          let value = self.pop()?;
+
+          let name = Self::constant_string(&function, index);
+          self.globals.insert(name.to_string(), value);
+        }
+        OpCode::GetGlobal | OpCode::GetGlobalLong => {
+          let (function, index) = self.read_string(op)?;
+          let name = Self::constant_string(&function, index);
+
+          let value = match self.globals.get(name) {
+            Some(value) => *value,
+            None => return Err(self.error(VMErrorKind::UndefinedVariable(name.to_string()))),
+          };
+
+          self.push(value)?;
+        }
+        OpCode::SetGlobal | OpCode::SetGlobalLong => {
+          let (function, index) = self.read_string(op)?;
+          let name = Self::constant_string(&function, index);
+          let value = *self.peek(0)?;
+
+          match self.globals.get_mut(name) {
+            Some(global) => *global = value,
+            None => return Err(self.error(VMErrorKind::UndefinedVariable(name.to_string()))),
+          }
+        }
+        OpCode::GetLocal => {
+          let slot = self.read_local()?;
+          let value = self.stack[slot];
+
+          self.push(value)?;
+        }
+        OpCode::SetLocal => {
+          let slot = self.read_local()?;
+          let value = *self.peek(0)?;
+
+          self.stack[slot] = value;
+        }
+        OpCode::CaptureLocal => {
+          let slot = self.read_local()?;
+          let upvalue = self.capture_upvalue(slot);
+
+          self.add_upvalue(op, upvalue)?;
+        }
+        OpCode::CaptureUpvalue => {
+          let index = self.frame_mut().read_byte() as usize;
+          let upvalue = self.upvalue(op, index)?;
+
+          self.add_upvalue(op, upvalue)?;
+        }
+        OpCode::GetUpvalue => {
+          let index = self.frame_mut().read_byte() as usize;
+          let upvalue = self.upvalue(op, index)?;
+
+          let value = match self.heap.get(upvalue) {
+            Object::Upvalue(ObjUpvalue::Open(slot)) => self.stack[*slot],
+            Object::Upvalue(ObjUpvalue::Closed(value)) => *value,
+            _ => return Err(self.invalid_operand(op, &VMValue::Object(upvalue))),
+          };
+
+          self.push(value)?;
+        }
+        OpCode::SetUpvalue => {
+          let index = self.frame_mut().read_byte() as usize;
+          let upvalue = self.upvalue(op, index)?;
+          let value = *self.peek(0)?;
+
+          match *self.heap.get(upvalue) {
+            Object::Upvalue(ObjUpvalue::Open(slot)) => self.stack[slot] = value,
+            Object::Upvalue(ObjUpvalue::Closed(_)) => {
+              *self.heap.get_mut(upvalue) = Object::Upvalue(ObjUpvalue::Closed(value));
+            }
+            _ => return Err(self.invalid_operand(op, &VMValue::Object(upvalue))),
+          }
+        }
+        OpCode::CloseUpvalue => {
+          self.peek(0)?;
+          self.close_upvalues(self.stack.len() - 1);
+          self.pop()?;
+        }
+        OpCode::Jump => {
+          let offset = self.frame_mut().read_short() as usize;
+          self.frame_mut().ip += offset;
+        }
+        OpCode::JumpIfFalse => {
+          let offset = self.frame_mut().read_short() as usize;
+
+          if self.peek(0)?.is_falsy() {
+            self.frame_mut().ip += offset;
+          }
+        }
+        OpCode::Loop => {
+          let offset = self.frame_mut().read_short() as usize;
+          self.frame_mut().ip -= offset;
+        }
+        OpCode::JumpTable => {
+          let size = self.frame_mut().read_short() as usize;
+          let min = self.pop()?;
+          let value = self.pop()?;
+
+          // Every entry of the table is a 3-byte `Jump`, the last one is
+          // taken by the values outside of the table.
+          let entry = match (value, min) {
+            (VMValue::Int(value), VMValue::Int(min)) => value
+              .checked_sub(min)
+              .and_then(|entry| usize::try_from(entry).ok())
+              .filter(|entry| *entry < size),
+            _ => None,
+          };
+
+          self.frame_mut().ip += entry.unwrap_or(size) * 3;
+        }
+        OpCode::Call => {
+          let argc = self.frame_mut().read_byte() as usize;
+          self.call(argc)?;
+        }
+        OpCode::Array => {
+          let count = self.frame_mut().read_short() as usize;
+
+          if self.stack.len() < self.frame().slot + count {
+            return Err(self.error(VMErrorKind::StackUnderflow));
+          }
+
+          let start = self.stack.len() - count;
+          let array = self.alloc(Object::Array(self.stack[start..].to_vec()));
+
+          self.stack.truncate(start);
+          self.push(VMValue::Object(array))?;
+        }
+        OpCode::Index => {
+          let index = *self.peek(0)?;
+          let target = *self.peek(1)?;
+
+          let i = match index {
+            VMValue::Int(i) => i,
+            _ => return Err(self.invalid_operands(op, &target, &index)),
+          };
+
+          let object = match target {
+            VMValue::Object(reference) => self.heap.get(reference),
+            _ => return Err(self.invalid_operands(op, &target, &index)),
+          };
+
+          let value = match object {
+            Object::Array(array) => match usize::try_from(i).ok().and_then(|i| array.get(i)) {
+              Some(value) => *value,
+              None => {
+                let length = array.len();
+                return Err(self.error(VMErrorKind::IndexOutOfBounds(i, length)));
+              }
+            },
+            Object::String(string) => {
+              match usize::try_from(i).ok().and_then(|i| string.chars().nth(i)) {
+                Some(c) => VMValue::Object(self.intern(&c.to_string())),
+                None => {
+                  let length = string.chars().count();
+                  return Err(self.error(VMErrorKind::IndexOutOfBounds(i, length)));
+                }
+              }
+            }
+            _ => return Err(self.invalid_operands(op, &target, &index)),
+          };
+
+          self.pop()?;
+          self.pop()?;
+          self.push(value)?;
+        }
+        OpCode::SetIndex => {
+          let value = *self.peek(0)?;
+          let index = *self.peek(1)?;
+          let target = *self.peek(2)?;
+
+          let i = match index {
+            VMValue::Int(i) => i,
+            _ => return Err(self.invalid_operands(op, &target, &index)),
+          };
+
+          let array = match target {
+            VMValue::Object(reference) => match self.heap.get_mut(reference) {
+              Object::Array(array) => array,
+              _ => return Err(self.invalid_operands(op, &target, &index)),
+            },
+            _ => return Err(self.invalid_operands(op, &target, &index)),
+          };
+
+          match usize::try_from(i).ok().and_then(|i| array.get_mut(i)) {
+            Some(element) => *element = value,
+            None => {
+              let length = array.len();
+              return Err(self.error(VMErrorKind::IndexOutOfBounds(i, length)));
+            }
+          }
+
+          self.pop()?;
+          self.pop()?;
+          self.pop()?;
+          self.push(value)?;
+        }
+        OpCode::Length => {
+          let value = self.pop()?;
+
+          let length = match &value {
+            VMValue::Object(reference) => match self.heap.get(*reference) {
+              Object::Array(array) => Some(array.len()),
+              Object::String(string) => Some(string.chars().count()),
+              _ => None,
+            },
+            _ => None,
+          };
+
+          let length = match length {
+            Some(length) => length,
+            None => return Err(self.invalid_operand(op, &value)),
+          };
+
+          self.push(VMValue::Int(length as i64))?;
+        }
+        OpCode::Print => {
+          let value = self.pop()?;
+          println!("{}", self.heap.format(&value));
+        }
+        OpCode::ToString => {
+          let value = self.peek(0)?;
+          let string = self.heap.format(value);
+          let string = self.intern(&string);
+
+          self.pop()?;
+          self.push(VMValue::Object(string))?;
+        }
+        OpCode::Variant | OpCode::VariantLong => {
+          let index = self.frame_mut().read_operand(op.operand_width());
+
+          let variant = match self.frame().function.bytecode.constants.get(index) {
+            Some(Value::Variant(variant)) => variant.clone(),
+            _ => return Err(self.error(VMErrorKind::InvalidConstant(index))),
+          };
+
+          if self.stack.len() < self.frame().slot + variant.arity {
+            return Err(self.error(VMErrorKind::StackUnderflow));
+          }
+
+          let start = self.stack.len() - variant.arity;
+          let values = self.stack[start..].to_vec();
+          let object = self.alloc(Object::Variant(ObjVariant::new(
+            variant.enum_name,
+            variant.name,
+            values,
+          )));
+
+          self.stack.truncate(start);
+          self.push(VMValue::Object(object))?;
+        }
+        OpCode::CheckVariant | OpCode::CheckVariantLong => {
+          let index = self.frame_mut().read_operand(op.operand_width());
+
+          let variant = match self.frame().function.bytecode.constants.get(index) {
+            Some(Value::Variant(variant)) => variant.clone(),
+            _ => return Err(self.error(VMErrorKind::InvalidConstant(index))),
+          };
+
+          let value = self.pop()?;
+          let result = match value {
+            VMValue::Object(reference) => match self.heap.get(reference) {
+              Object::Variant(object) => {
+                object.enum_name == variant.enum_name && object.name == variant.name
+              }
+              _ => false,
+            },
+            _ => false,
+          };
+
+          self.push(VMValue::Boolean(result))?;
+        }
+        OpCode::GetVariantValue => {
+          let index = self.frame_mut().read_byte() as usize;
+          let value = *self.peek(0)?;
+
+          let field = match value {
+            VMValue::Object(reference) => match self.heap.get(reference) {
+              Object::Variant(variant) => variant.values.get(index).copied(),
+              _ => None,
+            },
+            _ => None,
+          };
+
+          let field = match field {
+            Some(field) => field,
+            None => return Err(self.invalid_operand(op, &value)),
+          };
+
+          self.pop()?;
+          self.push(field)?;
+        }
+        OpCode::Class | OpCode::ClassLong => {
+          let (function, index) = self.read_string(op)?;
+          let name = Self::constant_string(&function, index).to_string();
+
+          let class = self.alloc(Object::Class(ObjClass::new(name)));
+          self.push(VMValue::Object(class))?;
+        }
+        OpCode::Method | OpCode::MethodLong | OpCode::StaticMethod | OpCode::StaticMethodLong => {
+          let (function, index) = self.read_string(op)?;
+          let name = Self::constant_string(&function, index).to_string();
+          let method = *self.peek(0)?;
+
+          match *self.peek(1)? {
+            VMValue::Object(reference) => match self.heap.get_mut(reference) {
+              Object::Class(class) if op.short_form() == OpCode::Method => {
+                class.methods.insert(name, method);
+              }
+              Object::Class(class) => {
+                class.statics.insert(name, method);
+              }
+              _ => return Err(self.invalid_operand(op, &VMValue::Object(reference))),
+            },
+            value => return Err(self.invalid_operand(op, &value)),
+          }
+
+          self.pop()?;
+        }
+        OpCode::GetProperty | OpCode::GetPropertyLong => {
+          let (function, index) = self.read_string(op)?;
+          let name = Self::constant_string(&function, index);
+          let object = *self.peek(0)?;
+
+          let value = self.get_property(object, name)?;
+
+          self.pop()?;
+          self.push(value)?;
+        }
+        OpCode::SetProperty | OpCode::SetPropertyLong => {
+          let (function, index) = self.read_string(op)?;
+          let name = Self::constant_string(&function, index);
+          let value = *self.peek(0)?;
+          let object = *self.peek(1)?;
+
+          self.set_property(object, name, value)?;
+
+          self.pop()?;
+          self.pop()?;
+          self.push(value)?;
+        }
+        OpCode::Return => {
+          let result = self.pop()?;
+
+          let frame = self.frames.pop().unwrap();
+          self.close_upvalues(frame.slot);
+          self.stack.truncate(frame.slot);
+
+          if self.frames.len() == depth {
+            return Ok(result);
+          }
+
+          self.push(result)?;
+        }
+      }
+    }
+  }
+
+  /**
+  Calls the value that sits below the `argc` arguments on top of the stack.
+  */
+  fn call(&mut self, argc: usize) -> VMResult<()> {
+    let callee = *self.peek(argc)?;
+    let slot = self.stack.len() - argc - 1;
+
+    let function = match callee {
+      VMValue::Object(reference) => match self.heap.get(reference) {
+        Object::Closure(closure) => match self.heap.get(closure.function) {
+          Object::Function(function) => Some(function.clone()),
+          _ => None,
+        },
+        Object::Native(native) => return self.call_native(native.clone(), argc),
+        Object::BoundMethod(bound) => {
+          let (receiver, method) = (bound.receiver, bound.method);
+          self.stack[slot] = receiver;
+
+          return self.call_closure(method, argc);
+        }
+        Object::Class(class) => {
+          let class_name = class.name.clone();
+          let initializer = class.methods.get(&class_name).copied();
+
+          let instance = self.alloc(Object::Instance(ObjInstance::new(reference, class_name)));
+          self.stack[slot] = VMValue::Object(instance);
+
+          return match initializer {
+            Some(VMValue::Object(initializer)) => self.call_closure(initializer, argc),
+            _ if argc != 0 => Err(self.error(VMErrorKind::InvalidNumberOfArguments(0, argc))),
+            _ => Ok(()),
+          };
+        }
+        _ => None,
+      },
+      _ => None,
+    };
+
+    let function = match function {
+      Some(function) => function,
+      None => {
+        let type_name = self.heap.type_name(&callee);
+        return Err(self.error(VMErrorKind::NotCallable(type_name)));
+      }
+    };
+
+    if function.arity != argc {
+      return Err(self.error(VMErrorKind::InvalidNumberOfArguments(function.arity, argc)));
+    }
+
+    if self.frames.len() >= FRAMES_MAX {
+      return Err(self.error(VMErrorKind::StackOverflow));
+    }
+
+    let module_path = match self.frames.last() {
+      Some(frame) => frame.module_path.clone(),
+      None => self.module_path.clone(),
+    };
+
+    self
+      .frames
+      .push(CallFrame::new(function, slot, module_path));
+
+    Ok(())
+  }
+
+  /**
+  Calls a method, its receiver is already in the slot of the callee.
+  */
+  fn call_closure(&mut self, closure: ObjRef, argc: usize) -> VMResult<()> {
+    let callee = self.stack.len() - argc - 1;
+    let receiver = self.stack[callee];
+
+    self.stack[callee] = VMValue::Object(closure);
+    self.call(argc)?;
+    self.stack[callee] = receiver;
+
+    Ok(())
+  }
+
+  /**
+  Reads a field of an instance or a method bound to it, static members are
+  read from the class.
+  */
+  fn get_property(&mut self, object: VMValue, name: &str) -> VMResult<VMValue> {
+    let reference = match object {
+      VMValue::Object(reference) => reference,
+      _ => {
+        return Err(self.error(VMErrorKind::InvalidPropertyAccess(
+          self.heap.type_name(&object),
+        )))
+      }
+    };
+
+    let method = match self.heap.get(reference) {
+      Object::Instance(instance) => {
+        if let Some(value) = instance.fields.get(name) {
+          return Ok(*value);
+        }
+
+        match self.heap.get(instance.class) {
+          Object::Class(class) => class.methods.get(name).copied(),
+          _ => None,
+        }
+      }
+      Object::Class(class) => match class.statics.get(name) {
+        Some(value) => return Ok(*value),
+        None => None,
+      },
+      _ => {
+        return Err(self.error(VMErrorKind::InvalidPropertyAccess(
+          self.heap.type_name(&object),
+        )))
+      }
+    };
+
+    match method {
+      Some(VMValue::Object(method)) => {
+        let bound = self.alloc(Object::BoundMethod(ObjBoundMethod::new(object, method)));
+
+        Ok(VMValue::Object(bound))
+      }
+      _ => Err(self.error(VMErrorKind::UndefinedProperty(name.to_string()))),
+    }
+  }
+
+  fn set_property(&mut self, object: VMValue, name: &str, value: VMValue) -> VMResult<()> {
+    if let VMValue::Object(reference) = object {
+      match self.heap.get_mut(reference) {
+        Object::Instance(instance) => {
+          instance.fields.insert(name.to_string(), value);
+          return Ok(());
+        }
+        Object::Class(class) => {
+          class.statics.insert(name.to_string(), value);
+          return Ok(());
+        }
+        _ => (),
+      }
+    }
+
+    Err(self.error(VMErrorKind::InvalidPropertyAccess(
+      self.heap.type_name(&object),
+    )))
+  }
+
+  fn binary_arithmetic(&self, op: OpCode, left: VMValue, right: VMValue) -> VMResult<VMValue> {
+    match (&left, &right) {
+      (VMValue::Int(l), VMValue::Int(r)) => {
+        let (l, r) = (*l, *r);
+
+        if (op == OpCode::Divide || op == OpCode::Modulo) && r == 0 {
+          return Err(self.error(VMErrorKind::DivisionByZero));
+        }
+
+        let result = match op {
+          OpCode::Add => l.checked_add(r),
+          OpCode::Subtract => l.checked_sub(r),
+          OpCode::Multiply => l.checked_mul(r),
+          OpCode::Divide => l.checked_div(r),
+          OpCode::Modulo => l.checked_rem(r),
+          _ => None,
+        };
+
+        match result {
+          Some(result) => Ok(VMValue::Int(result)),
+          None => Err(self.error(VMErrorKind::IntegerOverflow(op))),
+        }
+      }
+      (VMValue::Int(_) | VMValue::Float(_), VMValue::Int(_) | VMValue::Float(_)) => {
+        let l = Self::as_float(&left);
+        let r = Self::as_float(&right);
+
+        let result = match op {
+          OpCode::Add => l + r,
+          OpCode::Subtract => l - r,
+          OpCode::Multiply => l * r,
+          OpCode::Modulo => l % r,
+          _ => l / r,
+        };
+
+        Ok(VMValue::Float(result))
+      }
+      _ => Err(self.invalid_operands(op, &left, &right)),
+    }
+  }
+
+  fn compare(&self, op: OpCode, left: VMValue, right: VMValue) -> VMResult<bool> {
+    let ordering = match (&left, &right) {
+      (VMValue::Int(l), VMValue::Int(r)) => l.partial_cmp(r),
+      (VMValue::Int(_) | VMValue::Float(_), VMValue::Int(_) | VMValue::Float(_)) => {
+        Self::as_float(&left).partial_cmp(&Self::as_float(&right))
+      }
+      _ => match (self.heap.as_str(&left), self.heap.as_str(&right)) {
+        (Some(l), Some(r)) => l.partial_cmp(r),
+        _ => return Err(self.invalid_operands(op, &left, &right)),
+      },
+    };
+
+    // NaN compares false against everything
+    let Some(ordering) = ordering else {
+      return Ok(false);
+    };
+
+    Ok(match op {
+      OpCode::Greater => ordering.is_gt(),
+      OpCode::GreaterEqual => ordering.is_ge(),
+      OpCode::Less => ordering.is_lt(),
+      _ => ordering.is_le(),
+    })
+  }
+
+  fn as_float(value: &VMValue) -> f64 {
+    match value {
+      VMValue::Int(int) => *int as f64,
+      VMValue::Float(float) => *float,
+      _ => 0.0,
+    }
+  }
+
+  fn read_constant(&mut self, op: OpCode) -> VMResult<VMValue> {
+    let index = self.frame_mut().read_operand(op.operand_width());
+
+    let constant = match self.frame().function.bytecode.constants.get(index) {
+      Some(constant) => constant.clone(),
+      None => return Err(self.error(VMErrorKind::InvalidConstant(index))),
+    };
+
+    let value = match constant {
+      Value::Int(int) => VMValue::Int(int),
+      Value::Float(float) => VMValue::Float(float),
+      Value::String(string) => VMValue::Object(self.intern(&string)),
+      Value::Function(function) => VMValue::Object(self.alloc_closure(function)),
+      Value::Native(native) => {
+        let native = self.bind_native(&native)?;
+        VMValue::Object(self.alloc(Object::Native(native)))
+      }
+      Value::Variant(_) => return Err(self.error(VMErrorKind::InvalidConstant(index))),
+    };
+
+    Ok(value)
+  }
+
+  /**
+  Finds the host function of an `@extern` declaration and checks that it
+  takes the number of arguments the script declared.
+  */
+  fn bind_native(&self, declaration: &NativeDeclaration) -> VMResult<Rc<NativeFunction>> {
+    let native = match self.natives.get(&declaration.module, &declaration.name) {
+      Some(native) => native,
+      None => {
+        return Err(self.error(VMErrorKind::UndefinedNative(
+          declaration.module.clone(),
+          declaration.name.clone(),
+        )))
+      }
+    };
+
+    if native.arity() != declaration.arity {
+      return Err(self.error(VMErrorKind::NativeArityMismatch(
+        native.name.clone(),
+        native.arity(),
+        declaration.arity,
+      )));
+    }
+
+    Ok(native)
+  }
+
+  fn call_native(&mut self, native: Rc<NativeFunction>, argc: usize) -> VMResult<()> {
+    if native.arity() != argc {
+      return Err(self.error(VMErrorKind::InvalidNumberOfArguments(native.arity(), argc)));
+    }
+
+    let start = self.stack.len() - argc;
+
+    for (i, parameter) in native.parameters.iter().enumerate() {
+      let argument = &self.stack[start + i];
+
+      if !parameter.accepts(&self.heap, argument) {
+        return Err(self.error(VMErrorKind::InvalidArgumentType(
+          native.name.clone(),
+          i + 1,
+          *parameter,
+          self.heap.type_name(argument),
+        )));
+      }
+    }
+
+    let result = match (native.function)(&mut self.heap, &self.stack[start..]) {
+      Ok(result) => result,
+      Err(message) => {
+        return Err(self.error(VMErrorKind::NativeError(native.name.clone(), message)))
+      }
+    };
+
+    self.stack.truncate(start - 1);
+    self.push(result)
+  }
+
+  fn alloc(&mut self, object: Object) -> ObjRef {
+    if self.heap.should_collect() {
+      self.collect_garbage();
+    }
+
+    self.heap.alloc(object)
+  }
+
+  fn intern(&mut self, string: &str) -> ObjRef {
+    if self.heap.should_collect() {
+      self.collect_garbage();
+    }
+
+    self.heap.intern(string)
+  }
+
+  /**
+  Allocates a function object and the closure that wraps it, the collector
+  only runs before both allocations so the function is never left unrooted.
+  */
+  fn alloc_closure(&mut self, function: Rc<FunctionPrototype>) -> ObjRef {
+    if self.heap.should_collect() {
+      self.collect_garbage();
+    }
+
+    let function = self.heap.alloc(Object::Function(function));
+    self
+      .heap
+      .alloc(Object::Closure(ObjClosure::new(function, vec![])))
+  }
+
+  /**
+  Upvalue for the stack slot `slot`, the closures that capture the same
+  variable share it.
+  */
+  fn capture_upvalue(&mut self, slot: usize) -> ObjRef {
+    let open = self.open_upvalues.iter().find(|upvalue| {
+      matches!(self.heap.get(**upvalue), Object::Upvalue(ObjUpvalue::Open(open)) if *open == slot)
+    });
+
+    if let Some(upvalue) = open {
+      return *upvalue;
+    }
+
+    let upvalue = self.alloc(Object::Upvalue(ObjUpvalue::Open(slot)));
+    self.open_upvalues.push(upvalue);
+
+    upvalue
+  }
+
+  /**
+  Moves the variables at `slot` and above to their upvalues before they are
+  removed from the stack.
+  */
+  fn close_upvalues(&mut self, slot: usize) {
+    let stack = &self.stack;
+    let heap = &mut self.heap;
+
+    self
+      .open_upvalues
+      .retain(|upvalue| match heap.get_mut(*upvalue) {
+        Object::Upvalue(object) => match *object {
+          ObjUpvalue::Open(open) if open >= slot => {
+            *object = ObjUpvalue::Closed(stack[open]);
+            false
+          }
+          ObjUpvalue::Open(_) => true,
+          ObjUpvalue::Closed(_) => false,
+        },
+        _ => false,
+      });
+  }
+
+  /**
+  Upvalue `index` of the closure being executed, lambdas are called with
+  their closure in the slot 0.
+  */
+  fn upvalue(&self, op: OpCode, index: usize) -> VMResult<ObjRef> {
+    let closure = self.stack[self.frame().slot];
+
+    let upvalue = match closure {
+      VMValue::Object(reference) => match self.heap.get(reference) {
+        Object::Closure(closure) => closure.upvalues.get(index).copied(),
+        _ => None,
+      },
+      _ => None,
+    };
+
+    upvalue.ok_or_else(|| self.invalid_operand(op, &closure))
+  }
+
+  /**
+  Adds `upvalue` to the closure on top of the stack.
+  */
+  fn add_upvalue(&mut self, op: OpCode, upvalue: ObjRef) -> VMResult<()> {
+    let closure = *self.peek(0)?;
+
+    match closure {
+      VMValue::Object(reference) => match self.heap.get_mut(reference) {
+        Object::Closure(closure) => closure.upvalues.push(upvalue),
+        _ => return Err(self.invalid_operand(op, &closure)),
+      },
+      _ => return Err(self.invalid_operand(op, &closure)),
+    }
+
+    Ok(())
+  }
+
+  fn invalid_operand(&self, op: OpCode, value: &VMValue) -> VMError {
+    self.error(VMErrorKind::InvalidOperand(op, self.heap.type_name(value)))
+  }
+
+  fn invalid_operands(&self, op: OpCode, left: &VMValue, right: &VMValue) -> VMError {
+    self.error(VMErrorKind::InvalidOperands(
+      op,
+      self.heap.type_name(left),
+      self.heap.type_name(right),
+    ))
+  }
+
+  /**
+  Reads a constant operand that holds an identifier, used by the global
+  variable instructions. The name is borrowed from the constant pool of
+  `function` to avoid allocating on every global access.
+  */
+  fn read_string(&mut self, op: OpCode) -> VMResult<(Rc<FunctionPrototype>, usize)> {
+    let index = self.frame_mut().read_operand(op.operand_width());
+    let function = self.frame().function.clone();
+
+    match function.bytecode.constants.get(index) {
+      Some(Value::String(_)) => Ok((function, index)),
+      _ => Err(self.error(VMErrorKind::InvalidConstant(index))),
+    }
+  }
+
+  fn constant_string(function: &FunctionPrototype, index: usize) -> &str {
+    match &function.bytecode.constants[index] {
+      Value::String(name) => name,
+      _ => unreachable!(),
+    }
+  }
+
+  fn frame(&self) -> &CallFrame {
+    self.frames.last().unwrap()
+  }
+
+  /**
+  First stack slot owned by the current frame, the whole stack when the host
+  calls a function.
+  */
+  /**
+  Decodes the next instruction, checking that its operands are inside the code.
+  */
+  fn read_op(&mut self) -> VMResult<OpCode> {
+    let byte = self.frame_mut().read_byte();
+
+    let op = match OpCode::from_byte(byte) {
+      Some(op) => op,
+      None => return Err(self.error(VMErrorKind::InvalidOpCode(byte))),
+    };
+
+    let frame = self.frame();
+    if frame.ip + op.operand_width() > frame.function.bytecode.code.len() {
+      return Err(self.error(VMErrorKind::MissingOperands(op)));
+    }
+
+    Ok(op)
+  }
+
+  /**
+  Reads a local slot operand and returns its index in the stack.
+  */
+  fn read_local(&mut self) -> VMResult<usize> {
+    let slot = self.frame_mut().read_byte() as usize;
+    let index = self.frame().slot + slot;
+
+    if index >= self.stack.len() {
+      return Err(self.error(VMErrorKind::InvalidLocal(slot)));
+    }
+
+    Ok(index)
+  }
+
+  fn base(&self) -> usize {
+    self.frames.last().map_or(0, |frame| frame.slot)
+  }
+
+  fn frame_mut(&mut self) -> &mut CallFrame {
+    self.frames.last_mut().unwrap()
+  }
+
+  fn push(&mut self, value: VMValue) -> VMResult<()> {
+    if self.stack.len() >= STACK_MAX {
+      return Err(self.error(VMErrorKind::StackOverflow));
+    }
+
+    self.stack.push(value);
+
+    Ok(())
+  }
+
+  fn pop(&mut self) -> VMResult<VMValue> {
+    if self.stack.len() <= self.base() {
+      return Err(self.error(VMErrorKind::StackUnderflow));
+    }
+
+    Ok(self.stack.pop().unwrap())
+  }
+
+  fn peek(&self, distance: usize) -> VMResult<&VMValue> {
+    if self.stack.len() <= self.base() + distance {
+      return Err(self.error(VMErrorKind::StackUnderflow));
+    }
+
+    Ok(&self.stack[self.stack.len() - 1 - distance])
+  }
+
+  fn error(&self, kind: VMErrorKind) -> VMError {
+    match self.frames.last() {
+      Some(frame) => VMError::new(kind, frame.current_line(), frame.module_path.to_string()),
+      None => VMError::new(kind, 0, self.module_path.to_string()),
+    }
+  }
+}
+
+impl Default for VM {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use analyzer::Analyzer;
+  use bytecode_generator::{bytecode_file::BytecodeFile, BytecodeGenerator};
+  use lexer::Lexer;
+  use native::NativeType;
+  use parser::Parser;
+
+  fn constant(bytecode: &mut Bytecode, value: Value, line: usize) {
+    let op = match value {
+      Value::Int(_) => OpCode::ConstantInt,
+      Value::Float(_) => OpCode::ConstantFloat,
+      Value::String(_) => OpCode::ConstantString,
+      Value::Function(_) => OpCode::Function,
+      Value::Native(_) => OpCode::Native,
+      Value::Variant(_) => OpCode::Variant,
+    };
+
+    let index = bytecode.add_constant(value);
+    bytecode.write(op as u8, line);
+    bytecode.write(index as u8, line);
+  }
+
+  #[test]
+  fn test_arithmetic() {
+    // -(1 + 2) * 4
+    let mut bytecode = Bytecode::new();
+    constant(&mut bytecode, Value::Int(1), 1);
+    constant(&mut bytecode, Value::Int(2), 1);
+    bytecode.write(OpCode::Add as u8, 1);
+    bytecode.write(OpCode::Negate as u8, 1);
+    constant(&mut bytecode, Value::Int(4), 1);
+    bytecode.write(OpCode::Multiply as u8, 1);
+    bytecode.write(OpCode::Return as u8, 1);
+
+    let mut vm = VM::new();
+    let result = vm.interpret(bytecode, "test".to_string());
+
+    assert_eq!(result, Ok(VMValue::Int(-12)));
+  }
+
+  #[test]
+  fn test_int_float_promotion() {
+    let mut bytecode = Bytecode::new();
+    constant(&mut bytecode, Value::Int(3), 1);
+    constant(&mut bytecode, Value::Float(0.5), 1);
+    bytecode.write(OpCode::Subtract as u8, 1);
+    bytecode.write(OpCode::Return as u8, 1);
+
+    let mut vm = VM::new();
+    let result = vm.interpret(bytecode, "test".to_string());
+
+    assert_eq!(result, Ok(VMValue::Float(2.5)));
+  }
+
+  #[test]
+  fn test_runtime_error_line() {
+    let mut bytecode = Bytecode::new();
+    constant(&mut bytecode, Value::Int(10), 1);
+    constant(&mut bytecode, Value::Int(0), 2);
+    bytecode.write(OpCode::Divide as u8, 3);
+    bytecode.write(OpCode::Return as u8, 3);
+
+    let mut vm = VM::new();
+    let error = vm.interpret(bytecode, "test".to_string()).unwrap_err();
+
+    assert_eq!(error.kind, VMErrorKind::DivisionByZero);
+    assert_eq!(error.line, 3);
+  }
+
+  #[test]
+  fn test_invalid_bytecode_is_an_error() {
+    let mut bytecode = Bytecode::new();
+    bytecode.write(OpCode::GetLocal as u8, 1);
+    bytecode.write(200, 1);
+    bytecode.write(OpCode::Return as u8, 1);
+
+    let mut vm = VM::new();
+    let error = vm.interpret(bytecode, "test".to_string()).unwrap_err();
+    assert_eq!(error.kind, VMErrorKind::InvalidLocal(200));
+
+    // Jumps into the operand of `ConstantInt`, which is not an opcode.
+    let mut bytecode = Bytecode::new();
+    bytecode.write(OpCode::Jump as u8, 1);
+    bytecode.write(0, 1);
+    bytecode.write(1, 1);
+    bytecode.write(OpCode::ConstantInt as u8, 1);
+    bytecode.write(199, 1);
+
+    let mut vm = VM::new();
+    let error = vm.interpret(bytecode, "test".to_string()).unwrap_err();
+    assert_eq!(error.kind, VMErrorKind::InvalidOpCode(199));
+  }
+
+  #[test]
+  fn test_call_global_function() {
+    // function double(x) { return x + x; } double(21)
+    let mut body = Bytecode::new();
+    body.write(OpCode::GetLocal as u8, 1);
+    body.write(1, 1);
+    body.write(OpCode::GetLocal as u8, 1);
+    body.write(1, 1);
+    body.write(OpCode::Add as u8, 1);
+    body.write(OpCode::Return as u8, 1);
+
+    let double = FunctionPrototype::new("double".to_string(), 1, body);
+
+    let mut bytecode = Bytecode::new();
+    constant(&mut bytecode, Value::Function(Rc::new(double)), 2);
+    let name = bytecode.add_constant(Value::String("double".to_string()));
+    bytecode.write(OpCode::DefineGlobal as u8, 2);
+    bytecode.write(name as u8, 2);
+    bytecode.write(OpCode::GetGlobal as u8, 3);
+    bytecode.write(name as u8, 3);
+    constant(&mut bytecode, Value::Int(21), 3);
+    bytecode.write(OpCode::Call as u8, 3);
+    bytecode.write(1, 3);
+    bytecode.write(OpCode::Return as u8, 3);
+
+    let mut vm = VM::new();
+    let result = vm.interpret(bytecode, "test".to_string());
+
+    assert_eq!(result, Ok(VMValue::Int(42)));
+  }
+
+  #[test]
+  fn test_concatenated_strings_are_interned() {
+    // "a" + "b" == "ab"
+    let mut bytecode = Bytecode::new();
+    constant(&mut bytecode, Value::String("a".to_string()), 1);
+    constant(&mut bytecode, Value::String("b".to_string()), 1);
+    bytecode.write(OpCode::Concatenate as u8, 1);
+    constant(&mut bytecode, Value::String("ab".to_string()), 1);
+    bytecode.write(OpCode::Equal as u8, 1);
+    bytecode.write(OpCode::Return as u8, 1);
+
+    let mut vm = VM::new();
+    vm.set_gc_stress(true);
+    let result = vm.interpret(bytecode, "test".to_string());
+
+    assert_eq!(result, Ok(VMValue::Boolean(true)));
+    assert!(vm.gc_stats().collections > 0);
+  }
+
+  #[test]
+  fn test_unreachable_objects_are_freed() {
+    // [1, 2]; (discarded)
+    let mut bytecode = Bytecode::new();
+    constant(&mut bytecode, Value::Int(1), 1);
+    constant(&mut bytecode, Value::Int(2), 1);
+    bytecode.write(OpCode::Array as u8, 1);
+    bytecode.write(0, 1);
+    bytecode.write(2, 1);
+    bytecode.write(OpCode::Pop as u8, 1);
+    bytecode.write(OpCode::Null as u8, 1);
+    bytecode.write(OpCode::Return as u8, 1);
+
+    let mut vm = VM::new();
+    let result = vm.interpret(bytecode, "test".to_string());
+    let allocated = vm.heap().live_objects();
+
+    vm.collect_garbage();
+
+    assert_eq!(result, Ok(VMValue::Null));
+    assert_eq!(vm.gc_stats().collections, 1);
+    assert!(vm.gc_stats().objects_freed > 0);
+    assert!(vm.heap().live_objects() < allocated);
+  }
+
+  fn call_native(arguments: Vec<Value>) -> Bytecode {
+    // @extern("host") function add(a, b); add(...)
+    let mut bytecode = Bytecode::new();
+    let native = NativeDeclaration::new("host".to_string(), "add".to_string(), 2);
+    constant(&mut bytecode, Value::Native(native), 1);
+    let name = bytecode.add_constant(Value::String("add".to_string()));
+    bytecode.write(OpCode::DefineGlobal as u8, 1);
+    bytecode.write(name as u8, 1);
+    bytecode.write(OpCode::GetGlobal as u8, 2);
+    bytecode.write(name as u8, 2);
+
+    let argc = arguments.len();
+    for argument in arguments {
+      constant(&mut bytecode, argument, 2);
+    }
+
+    bytecode.write(OpCode::Call as u8, 2);
+    bytecode.write(argc as u8, 2);
+    bytecode.write(OpCode::Return as u8, 2);
+
+    bytecode
+  }
+
+  fn host_vm() -> VM {
+    let mut natives = NativeRegistry::new();
+    natives.register(
+      "host",
+      "add",
+      vec![NativeType::Int, NativeType::Int],
+      |_, arguments| match (arguments[0], arguments[1]) {
+        (VMValue::Int(a), VMValue::Int(b)) => a
+          .checked_add(b)
+          .map(VMValue::Int)
+          .ok_or("overflow".to_string()),
+        _ => unreachable!(),
+      },
+    );
+
+    VM::with_natives(natives)
+  }
+
+  fn identifier(bytecode: &mut Bytecode, op: OpCode, name: &str, line: usize) {
+    let index = bytecode.add_constant(Value::String(name.to_string()));
+    bytecode.write(op as u8, line);
+    bytecode.write(index as u8, line);
+  }
+
+  #[test]
+  fn test_instances_and_methods() {
+    // class Point { get() { this.x = 7; return this.x; } }
+    let mut body = Bytecode::new();
+    body.write(OpCode::GetLocal as u8, 1);
+    body.write(0, 1);
+    constant(&mut body, Value::Int(7), 1);
+    identifier(&mut body, OpCode::SetProperty, "x", 1);
+    body.write(OpCode::Pop as u8, 1);
+    body.write(OpCode::GetLocal as u8, 1);
+    body.write(0, 1);
+    identifier(&mut body, OpCode::GetProperty, "x", 1);
+    body.write(OpCode::Return as u8, 1);
+
+    // Point().get()
+    let mut bytecode = Bytecode::new();
+    identifier(&mut bytecode, OpCode::Class, "Point", 1);
+    let get = FunctionPrototype::new("get".to_string(), 0, body);
+    constant(&mut bytecode, Value::Function(Rc::new(get)), 1);
+    identifier(&mut bytecode, OpCode::Method, "get", 1);
+    bytecode.write(OpCode::Call as u8, 2);
+    bytecode.write(0, 2);
+    identifier(&mut bytecode, OpCode::GetProperty, "get", 2);
+    bytecode.write(OpCode::Call as u8, 2);
+    bytecode.write(0, 2);
+    bytecode.write(OpCode::Return as u8, 2);
+
+    let mut vm = VM::new();
+    vm.set_gc_stress(true);
+    let result = vm.interpret(bytecode, "test".to_string());
+
+    assert_eq!(result, Ok(VMValue::Int(7)));
+  }
+
+  #[test]
+  fn test_call_native_function() {
+    let bytecode = call_native(vec![Value::Int(40), Value::Int(2)]);
+    let result = host_vm().interpret(bytecode, "test".to_string());
+
+    assert_eq!(result, Ok(VMValue::Int(42)));
+  }
+
+  #[test]
+  fn test_native_arguments_are_checked() {
+    let bytecode = call_native(vec![Value::Int(1)]);
+    let error = host_vm()
+      .interpret(bytecode, "test".to_string())
+      .unwrap_err();
+    assert_eq!(error.kind, VMErrorKind::InvalidNumberOfArguments(2, 1));
+
+    let bytecode = call_native(vec![Value::Int(1), Value::Float(2.0)]);
+    let error = host_vm()
+      .interpret(bytecode, "test".to_string())
+      .unwrap_err();
+    assert_eq!(
+      error.kind,
+      VMErrorKind::InvalidArgumentType("add".to_string(), 2, NativeType::Int, "float")
+    );
+
+    let bytecode = call_native(vec![Value::Int(i64::MAX), Value::Int(1)]);
+    let error = host_vm()
+      .interpret(bytecode, "test".to_string())
+      .unwrap_err();
+    assert_eq!(
+      error.kind,
+      VMErrorKind::NativeError("add".to_string(), "overflow".to_string())
+    );
+
+    let bytecode = call_native(vec![Value::Int(1), Value::Int(2)]);
+    let error = VM::new()
+      .interpret(bytecode, "test".to_string())
+      .unwrap_err();
+    assert_eq!(
+      error.kind,
+      VMErrorKind::UndefinedNative("host".to_string(), "add".to_string())
+    );
+  }
+
+  /**
+  Compiles and runs `source`, the result is the text of the value of its
+  last expression statement. The bytecode goes through an `.ignc` file first,
+  so the generated code also has to pass its validation.
+  */
+  fn run(source: &str) -> VMResult<String> {
+    let mut lexer = Lexer::new(source, "main.ign".to_string());
+    lexer.scan_tokens();
+
+    let mut parser = Parser::new(lexer.tokens);
+    let statements = parser.parse();
+    assert!(
+      parser.diagnostics.is_empty(),
+      "Failed to parse {:?}",
+      source
+    );
+
+    let mut analyzer = Analyzer::new("main.ign".to_string());
+    analyzer.analyze(&statements);
+    assert!(
+      analyzer.diagnostics.is_empty(),
+      "Unexpected errors {:?}",
+      analyzer.diagnostics
+    );
+
+    let mut generator = BytecodeGenerator::new();
+    generator.return_last_expression = true;
+    generator.generate(&analyzer.irs);
+
+    let bytecode = generator.bytecodes.remove("main.ign").unwrap_or_default();
+
+    let mut file = BytecodeFile::new();
+    file.add_section("main.ign".to_string(), bytecode);
+    let file = BytecodeFile::from_bytes(&file.to_bytes()).unwrap();
+    let bytecode = file.section("main.ign").unwrap().bytecode.clone();
+
+    let mut vm = VM::new();
+    vm.set_gc_stress(true);
+    let value = vm.interpret(bytecode, "main.ign".to_string())?;
+
+    Ok(vm.format_value(&value))
+  }
+
+  fn assert_runs(source: &str, expected: &str) {
+    assert_eq!(run(source), Ok(expected.to_string()), "{}", source);
+  }
+
+  #[test]
+  fn test_classes() {
+    let counter = "class Counter {\n  public static created: int = 0;\n  private count: int = 0;\n\n  public Counter(start: int) {\n    this.count = start;\n    Counter.created = Counter.created + 1;\n  }\n\n  public increment(): int {\n    this.count = this.count + 1;\n    return this.count;\n  }\n}\nlet counter: Counter = new Counter(40);\n";
+
+    assert_runs(
+      &format!("{}counter.increment();\ncounter.increment();", counter),
+      "42",
+    );
+    assert_runs(&format!("{}Counter.created;", counter), "1");
+    assert_runs(&format!("{}counter;", counter), "<Counter instance>");
+  }
+
+  #[test]
+  fn test_enums() {
+    let prelude = "enum Option<T> {\n  SOME(T),\n  NONE\n}\n\nenum Colors {\n  RED = \"red\",\n  GREEN = \"green\",\n}\nlet value: Option<int> = Option.SOME(42);\n";
+
+    assert_runs(&format!("{}value;", prelude), "Option.SOME(42)");
+    assert_runs(&format!("{}value == Option.SOME(42);", prelude), "true");
+    assert_runs(&format!("{}value == Option.NONE;", prelude), "false");
+    assert_runs(&format!("{}value == Option.SOME(1);", prelude), "false");
+    assert_runs(&format!("{}Option.NONE == Option.NONE;", prelude), "true");
+    assert_runs(&format!("{}Colors.GREEN;", prelude), "green");
+  }
+
+  #[test]
+  fn test_match() {
+    let prelude = "enum Shape {\n  CIRCLE(int),\n  RECT(int, int),\n  EMPTY\n}\n\nfunction area(shape: Shape): int {\n  return match shape {\n    Shape.CIRCLE(r) if r > 10 -> 1000,\n    Shape.CIRCLE(r) -> r * r,\n    Shape.RECT(w, h) -> w * h,\n    Shape.EMPTY -> 0,\n  };\n}\n";
+
+    assert_runs(&format!("{}area(Shape.CIRCLE(3));", prelude), "9");
+    assert_runs(&format!("{}area(Shape.CIRCLE(20));", prelude), "1000");
+    assert_runs(&format!("{}area(Shape.RECT(3, 4));", prelude), "12");
+    assert_runs("1 + match \"b\" { \"a\" -> 1, \"b\" -> 2, _ -> 3 };", "3");
+  }
+
+  #[test]
+  fn test_switch() {
+    let prelude = "function size(n: int): string {\n  let mut result: string = \"\";\n  switch n {\n    case 1:\n    case 2:\n      result = \"small\";\n      break;\n    case 3:\n      result = \"three\";\n    default:\n      result = result + \"!\";\n  }\n  return result;\n}\n";
+
+    assert_runs(&format!("{}size(2);", prelude), "small");
+    assert_runs(&format!("{}size(3);", prelude), "three!");
+    assert_runs(&format!("{}size(7);", prelude), "!");
+    assert_runs(&format!("{}size(0);", prelude), "!");
+
+    let gap = "function gap(n: int): int {\n  switch n {\n    case 1:\n      return 10;\n    case 2:\n      return 20;\n    case 4:\n      return 40;\n  }\n  return 0;\n}\n";
+
+    assert_runs(&format!("{}gap(3);", gap), "0");
+    assert_runs(&format!("{}gap(4);", gap), "40");
+    assert_runs(&format!("{}gap(-1);", gap), "0");
+  }
+
+  #[test]
+  fn test_lambdas() {
+    let prelude = "function makeCounter(): () -> int {\n  let mut count: int = 0;\n  return (): int -> {\n    count = count + 1;\n    return count;\n  };\n}\nfunction apply(f: (int, int) -> int, a: int, b: int): int {\n  return f(a, b);\n}\nfunction adder(n: int): (int) -> int {\n  return (x: int): int -> x + n;\n}\n";
+
+    assert_runs(
+      &format!(
+        "{}let counter: () -> int = makeCounter();\ncounter();\ncounter();",
+        prelude
+      ),
+      "2",
+    );
+    assert_runs(
+      &format!("{}apply((a: int, b: int) -> a * b, 6, 7);", prelude),
+      "42",
+    );
+    assert_runs(&format!("{}adder(10)(5);", prelude), "15");
+  }
+
+  #[test]
+  fn test_template_literals() {
+    let prelude = "let name: string = \"Ignis\";\nlet age: int = 3;\n";
+
+    assert_runs(
+      &format!(
+        "{}`${{name}} is ${{age + 1}}, ${{`nested {{${{age}}}}`}}`;",
+        prelude
+      ),
+      "Ignis is 4, nested {3}",
+    );
+    assert_runs(&format!("{}`${{age}}`;", prelude), "3");
+  }
+
+  #[test]
+  fn test_index_expressions() {
+    let prelude = "let mut numbers: int[] = [1, 2, 3];\nlet name: string = \"ignis\";\n";
+
+    assert_runs(
+      &format!("{}numbers[1] = numbers[0] + numbers[2];", prelude),
+      "4",
+    );
+    assert_runs(
+      &format!("{}numbers[1] = numbers[0] + numbers[2];\nnumbers;", prelude),
+      "[1, 4, 3]",
+    );
+    assert_runs(&format!("{}name[1];", prelude), "g");
+
+    let error = run(&format!("{}numbers[3];", prelude)).unwrap_err();
+    assert_eq!(error.kind, VMErrorKind::IndexOutOfBounds(3, 3));
+  }
+
+  #[test]
+  fn test_type_annotations() {
+    let prelude = "let grid: int[][] = [[1, 2], [3, 4]];\nlet mut id: int | string = 1;\nlet name: string? = null;\n";
+
+    assert_runs(&format!("{}id = \"one\";", prelude), "one");
+    assert_runs(
+      &format!(
+        "{}let pick: (int[][]) -> int = (rows: int[][]): int -> rows[1][0];\npick(grid);",
+        prelude
+      ),
+      "3",
+    );
+  }
+
+  #[test]
+  fn test_interfaces_and_type_aliases() {
+    assert_runs(
+      "type Size = int;\ninterface Shape {\n  area(): Size;\n}\nclass Square implements Shape {\n  public side: Size;\n  public Square(side: int) {\n    this.side = side;\n  }\n  public area(): int {\n    return this.side * this.side;\n  }\n}\nfunction area(shape: Shape): Size {\n  return shape.area();\n}\narea(new Square(3));",
+      "9",
+    );
+  }
+
+  #[test]
+  fn test_generics() {
+    let prelude = "function apply<T, U>(x: T, f: (T) -> U): U {\n  return f(x);\n}\nclass Box<T> {\n  public value: T;\n  public Box(value: T) {\n    this.value = value;\n  }\n  public get(): T {\n    return this.value;\n  }\n}\n";
+
+    assert_runs(
+      &format!("{}apply(20, (x: int): int -> x * 2) + 2;", prelude),
+      "42",
+    );
+    assert_runs(&format!("{}new Box(4).get() + 1;", prelude), "5");
+  }
+
+  #[test]
+  fn test_inferred_variable_types() {
+    let prelude = "function twice(x: int): int {\n  return x * 2;\n}\nlet total = twice(21);\nlet names = [\"a\", \"b\"];\n";
+
+    assert_runs(&format!("{}total;", prelude), "42");
+    assert_runs(&format!("{}names[1];", prelude), "b");
+  }
+
+  #[test]
+  fn test_type_checking() {
+    let prelude = "function half(x: float): float {\n  return x / 2;\n}\nlet mut a = 3;\nlet b: float = 1;\nlet flag = !(a > 2) || false;\n";
+
+    assert_runs(&format!("{}half(a + b) + 0.5;", prelude), "2.5");
+    assert_runs(&format!("{}-a;", prelude), "-3");
+    assert_runs(&format!("{}flag ? 1 : 2.5;", prelude), "2.5");
+  }
+
+  #[test]
+  fn test_for_in() {
+    assert_runs(
+      "let a: int[] = [1, 2, 3];\nlet mut total: int = 0;\nfor (let i in a) {\n  total = total + i;\n}\ntotal;",
+      "6",
+    );
+    assert_runs(
+      "let mut letters: string = \"\";\nfor (let letter in \"abc\") {\n  letters = letter + letters;\n}\nletters;",
+      "cba",
+    );
+    assert_runs(
+      "let a: int[] = [1, 2, 3, 4, 5, 6];\nlet mut total: int = 0;\nfor (let i in a) {\n  let half: int = i / 2;\n  if (i == 2) {\n    continue;\n  }\n  if (i == 5) {\n    break;\n  }\n  total = total + i + half;\n}\ntotal;",
+      "11",
+    );
+  }
+
+  #[test]
+  fn test_control_flow() {
+    let prelude = "function pick(flag: boolean): int {\n  let mut x: int;\n  if (flag) {\n    x = 1;\n  } else {\n    x = 2;\n  }\n  return x;\n}\n";
+
+    assert_runs(&format!("{}pick(true);", prelude), "1");
+    assert_runs(&format!("{}pick(false);", prelude), "2");
+  }
+
+  #[test]
+  fn test_deep_recursion() {
+    let sum = "function sum(n: int): int {\n  if (n == 0) {\n    return 0;\n  }\n  return n + sum(n - 1);\n}\n";
+
+    assert_runs(&format!("{}sum(63);", sum), "2016");
+    assert_runs(&format!("{}sum(5000);", sum), "12502500");
+
+    let error =
+      run("function forever(n: int): int {\n  return forever(n + 1);\n}\nforever(0);").unwrap_err();
+    assert_eq!(error.kind, VMErrorKind::StackOverflow);
+  }
+}
@@ -0,0 +1,180 @@
+use std::{collections::HashMap, fmt::Display, rc::Rc};
+
+use crate::{heap::Heap, object::Object, vm_value::VMValue};
+
+/**
+  Result of a host function, the error message becomes a runtime error of
+  the script that called it.
+*/
+pub type NativeResult = Result<VMValue, String>;
+
+pub type NativeFn = dyn Fn(&mut Heap, &[VMValue]) -> NativeResult;
+
+/**
+  Type accepted by a parameter of a native function, checked by the VM before
+  the host function runs.
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NativeType {
+  Int,
+  Float,
+  /// An int or a float
+  Number,
+  Boolean,
+  String,
+  Array,
+  Function,
+  Any,
+}
+
+impl NativeType {
+  pub fn accepts(&self, heap: &Heap, value: &VMValue) -> bool {
+    match (self, value) {
+      (NativeType::Any, _) => true,
+      (NativeType::Int, VMValue::Int(_)) => true,
+      (NativeType::Float, VMValue::Float(_)) => true,
+      (NativeType::Number, VMValue::Int(_) | VMValue::Float(_)) => true,
+      (NativeType::Boolean, VMValue::Boolean(_)) => true,
+      (
+        NativeType::String | NativeType::Array | NativeType::Function,
+        VMValue::Object(reference),
+      ) => {
+        matches!(
+          (self, heap.get(*reference)),
+          (NativeType::String, Object::String(_))
+            | (NativeType::Array, Object::Array(_))
+            | (
+              NativeType::Function,
+              Object::Closure(_) | Object::Function(_) | Object::Native(_)
+            )
+        )
+      }
+      _ => false,
+    }
+  }
+}
+
+impl Display for NativeType {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    let name = match self {
+      NativeType::Int => "int",
+      NativeType::Float => "float",
+      NativeType::Number => "number",
+      NativeType::Boolean => "boolean",
+      NativeType::String => "string",
+      NativeType::Array => "array",
+      NativeType::Function => "function",
+      NativeType::Any => "any",
+    };
+
+    write!(f, "{}", name)
+  }
+}
+
+/**
+  A function implemented by the host and exposed to Ignis through an
+  `@extern("module")` declaration.
+*/
+pub struct NativeFunction {
+  pub module: String,
+  pub name: String,
+  pub parameters: Vec<NativeType>,
+  pub function: Box<NativeFn>,
+}
+
+impl NativeFunction {
+  pub fn arity(&self) -> usize {
+    self.parameters.len()
+  }
+}
+
+impl std::fmt::Debug for NativeFunction {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("NativeFunction")
+      .field("module", &self.module)
+      .field("name", &self.name)
+      .field("parameters", &self.parameters)
+      .finish()
+  }
+}
+
+/**
+  Host functions available to the scripts, indexed by module and name.
+*/
+#[derive(Default)]
+pub struct NativeRegistry {
+  functions: HashMap<(String, String), Rc<NativeFunction>>,
+}
+
+impl NativeRegistry {
+  pub fn new() -> Self {
+    Self {
+      functions: HashMap::new(),
+    }
+  }
+
+  /**
+    Registry with the functions of the standard library, `std:io` and
+    `std:string`.
+  */
+  pub fn with_std() -> Self {
+    let mut registry = Self::new();
+
+    registry.register(
+      "std:io",
+      "println",
+      vec![NativeType::Any],
+      |heap, arguments| {
+        println!("{}", heap.format(&arguments[0]));
+        Ok(VMValue::Null)
+      },
+    );
+
+    registry.register(
+      "std:string",
+      "toString",
+      vec![NativeType::Any],
+      |heap, arguments| {
+        let string = heap.format(&arguments[0]);
+        Ok(VMValue::Object(heap.intern(&string)))
+      },
+    );
+
+    registry
+  }
+
+  /**
+    Registers `function` as `name` of `module`, replacing any previous
+    function with the same name.
+    The VM checks the number and the types of the arguments against
+    `parameters` before calling it.
+  */
+  pub fn register<F>(&mut self, module: &str, name: &str, parameters: Vec<NativeType>, function: F)
+  where
+    F: Fn(&mut Heap, &[VMValue]) -> NativeResult + 'static,
+  {
+    let native = NativeFunction {
+      module: module.to_string(),
+      name: name.to_string(),
+      parameters,
+      function: Box::new(function),
+    };
+
+    self
+      .functions
+      .insert((module.to_string(), name.to_string()), Rc::new(native));
+  }
+
+  pub fn get(&self, module: &str, name: &str) -> Option<Rc<NativeFunction>> {
+    self
+      .functions
+      .get(&(module.to_string(), name.to_string()))
+      .cloned()
+  }
+
+  pub fn contains(&self, module: &str, name: &str) -> bool {
+    self
+      .functions
+      .contains_key(&(module.to_string(), name.to_string()))
+  }
+}
@@ -0,0 +1,173 @@
+use std::{collections::HashMap, rc::Rc};
+
+use bytecode_generator::FunctionPrototype;
+
+use crate::{native::NativeFunction, vm_value::VMValue};
+
+/**
+  Handle to an object stored in the `Heap`.
+  It stays valid as long as the object is reachable from the VM roots.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ObjRef(pub(crate) usize);
+
+/**
+  A function value created at runtime from a `FunctionPrototype`.
+
+  - function: the function object with the compiled code.
+  - upvalues: variables captured from the enclosing functions.
+*/
+#[derive(Debug, Clone)]
+pub struct ObjClosure {
+  pub function: ObjRef,
+  pub upvalues: Vec<ObjRef>,
+}
+
+impl ObjClosure {
+  pub fn new(function: ObjRef, upvalues: Vec<ObjRef>) -> Self {
+    Self { function, upvalues }
+  }
+}
+
+/**
+  A variable captured by a closure. It points to the stack slot of the
+  variable while the variable is in scope and holds its value afterwards.
+*/
+#[derive(Debug, Clone, Copy)]
+pub enum ObjUpvalue {
+  Open(usize),
+  Closed(VMValue),
+}
+
+/**
+  A class declared by the script.
+
+  - methods: closures called on the instances, the constructor is stored
+    under the name of the class.
+  - statics: static methods and static fields, read through the class.
+*/
+#[derive(Debug, Clone)]
+pub struct ObjClass {
+  pub name: String,
+  pub methods: HashMap<String, VMValue>,
+  pub statics: HashMap<String, VMValue>,
+}
+
+impl ObjClass {
+  pub fn new(name: String) -> Self {
+    Self {
+      name,
+      methods: HashMap::new(),
+      statics: HashMap::new(),
+    }
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct ObjInstance {
+  pub class: ObjRef,
+  pub class_name: String,
+  pub fields: HashMap<String, VMValue>,
+}
+
+impl ObjInstance {
+  pub fn new(class: ObjRef, class_name: String) -> Self {
+    Self {
+      class,
+      class_name,
+      fields: HashMap::new(),
+    }
+  }
+}
+
+/**
+  A method read from an instance, `receiver` is placed in the slot 0 of the
+  method when it is called.
+*/
+#[derive(Debug, Clone)]
+pub struct ObjBoundMethod {
+  pub receiver: VMValue,
+  pub method: ObjRef,
+}
+
+impl ObjBoundMethod {
+  pub fn new(receiver: VMValue, method: ObjRef) -> Self {
+    Self { receiver, method }
+  }
+}
+
+/**
+  A variant of an enum, `values` is its payload.
+*/
+#[derive(Debug, Clone)]
+pub struct ObjVariant {
+  pub enum_name: String,
+  pub name: String,
+  pub values: Vec<VMValue>,
+}
+
+impl ObjVariant {
+  pub fn new(enum_name: String, name: String, values: Vec<VMValue>) -> Self {
+    Self {
+      enum_name,
+      name,
+      values,
+    }
+  }
+}
+
+#[derive(Debug, Clone)]
+pub enum Object {
+  String(Rc<str>),
+  Array(Vec<VMValue>),
+  Function(Rc<FunctionPrototype>),
+  Native(Rc<NativeFunction>),
+  Closure(ObjClosure),
+  Upvalue(ObjUpvalue),
+  Instance(ObjInstance),
+  Class(ObjClass),
+  BoundMethod(ObjBoundMethod),
+  Variant(ObjVariant),
+}
+
+impl Object {
+  pub fn type_name(&self) -> &'static str {
+    match self {
+      Object::String(_) => "string",
+      Object::Array(_) => "array",
+      Object::Function(_) | Object::Native(_) | Object::Closure(_) | Object::BoundMethod(_) => {
+        "function"
+      }
+      Object::Upvalue(_) => "upvalue",
+      Object::Instance(_) => "instance",
+      Object::Class(_) => "class",
+      Object::Variant(_) => "enum",
+    }
+  }
+
+  /**
+    Approximate number of bytes owned by the object, used to decide when the
+    next collection runs.
+  */
+  pub fn size(&self) -> usize {
+    let owned = match self {
+      Object::String(string) => string.len(),
+      Object::Array(array) => array.capacity() * std::mem::size_of::<VMValue>(),
+      Object::Function(_) | Object::Native(_) => 0,
+      Object::Closure(closure) => closure.upvalues.capacity() * std::mem::size_of::<ObjRef>(),
+      Object::Upvalue(_) => 0,
+      Object::Instance(instance) => {
+        instance.fields.capacity()
+          * (std::mem::size_of::<String>() + std::mem::size_of::<VMValue>())
+      }
+      Object::Class(class) => {
+        (class.methods.capacity() + class.statics.capacity())
+          * (std::mem::size_of::<String>() + std::mem::size_of::<VMValue>())
+      }
+      Object::BoundMethod(_) => 0,
+      Object::Variant(variant) => variant.values.capacity() * std::mem::size_of::<VMValue>(),
+    };
+
+    std::mem::size_of::<Object>() + owned
+  }
+}
@@ -0,0 +1,114 @@
+use std::fmt::Display;
+
+use bytecode_generator::OpCode;
+
+use crate::native::NativeType;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum VMErrorKind {
+  InvalidOperands(OpCode, &'static str, &'static str),
+  InvalidOperand(OpCode, &'static str),
+  DivisionByZero,
+  IntegerOverflow(OpCode),
+  InvalidConstant(usize),
+  UndefinedVariable(String),
+  NotCallable(&'static str),
+  UndefinedNative(String, String),
+  NativeArityMismatch(String, usize, usize),
+  InvalidArgumentType(String, usize, NativeType, &'static str),
+  NativeError(String, String),
+  InvalidNumberOfArguments(usize, usize),
+  IndexOutOfBounds(i64, usize),
+  UndefinedProperty(String),
+  InvalidPropertyAccess(&'static str),
+  StackOverflow,
+  StackUnderflow,
+  InvalidOpCode(u8),
+  MissingOperands(OpCode),
+  InvalidLocal(usize),
+}
+
+impl Display for VMErrorKind {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      VMErrorKind::InvalidOperands(op, left, right) => write!(
+        f,
+        "Invalid operands for {:?}: '{}' and '{}'",
+        op, left, right
+      ),
+      VMErrorKind::InvalidOperand(op, value) => {
+        write!(f, "Invalid operand for {:?}: '{}'", op, value)
+      }
+      VMErrorKind::DivisionByZero => write!(f, "Division by zero"),
+      VMErrorKind::IntegerOverflow(op) => write!(f, "Integer overflow in {:?}", op),
+      VMErrorKind::InvalidConstant(index) => write!(f, "Invalid constant index {}", index),
+      VMErrorKind::UndefinedVariable(name) => write!(f, "Undefined variable '{}'", name),
+      VMErrorKind::NotCallable(type_name) => write!(f, "'{}' is not callable", type_name),
+      VMErrorKind::UndefinedNative(module, name) => {
+        write!(
+          f,
+          "No native function '{}' registered for '{}'",
+          name, module
+        )
+      }
+      VMErrorKind::NativeArityMismatch(name, registered, declared) => write!(
+        f,
+        "Native function '{}' takes {} arguments but is declared with {}",
+        name, registered, declared
+      ),
+      VMErrorKind::InvalidArgumentType(name, position, expected, found) => write!(
+        f,
+        "Argument {} of '{}' must be '{}', found '{}'",
+        position, name, expected, found
+      ),
+      VMErrorKind::NativeError(name, message) => write!(f, "{}: {}", name, message),
+      VMErrorKind::InvalidNumberOfArguments(expected, found) => {
+        write!(f, "Expected {} arguments, found {}", expected, found)
+      }
+      VMErrorKind::IndexOutOfBounds(index, length) => {
+        write!(f, "Index {} out of bounds for length {}", index, length)
+      }
+      VMErrorKind::UndefinedProperty(name) => write!(f, "Undefined property '{}'", name),
+      VMErrorKind::InvalidPropertyAccess(type_name) => {
+        write!(f, "'{}' has no properties", type_name)
+      }
+      VMErrorKind::StackOverflow => write!(f, "Stack overflow"),
+      VMErrorKind::StackUnderflow => write!(f, "Stack underflow"),
+      VMErrorKind::InvalidOpCode(byte) => write!(f, "Invalid opcode {}", byte),
+      VMErrorKind::MissingOperands(op) => write!(f, "Missing operands for {:?}", op),
+      VMErrorKind::InvalidLocal(slot) => write!(f, "Invalid local slot {}", slot),
+    }
+  }
+}
+
+/**
+  A runtime error raised while executing bytecode.
+
+  `line` is taken from `Bytecode::lines` for the instruction that failed.
+*/
+#[derive(Debug, Clone, PartialEq)]
+pub struct VMError {
+  pub kind: VMErrorKind,
+  pub line: usize,
+  pub module_path: String,
+}
+
+impl VMError {
+  pub fn new(kind: VMErrorKind, line: usize, module_path: String) -> Self {
+    Self {
+      kind,
+      line,
+      module_path,
+    }
+  }
+}
+
+impl Display for VMError {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(
+      f,
+      "Runtime error: {}\n[line {}] in {}",
+      self.kind, self.line, self.module_path
+    )
+  }
+}
@@ -0,0 +1,68 @@
+use std::fmt::Display;
+
+use crate::object::ObjRef;
+
+/**
+  A value on the VM stack.
+  Strings, arrays, functions and instances live in the `Heap` and are
+  referenced through `Object`.
+*/
+#[derive(Debug, Clone, Copy)]
+pub enum VMValue {
+  Int(i64),
+  Float(f64),
+  Boolean(bool),
+  Object(ObjRef),
+  Null,
+}
+
+impl VMValue {
+  pub fn type_name(&self) -> &'static str {
+    match self {
+      VMValue::Int(_) => "int",
+      VMValue::Float(_) => "float",
+      VMValue::Boolean(_) => "boolean",
+      VMValue::Object(_) => "object",
+      VMValue::Null => "null",
+    }
+  }
+
+  /**
+  Only `null` and `false` are falsy, the same rule Lua follows.
+  */
+  pub fn is_falsy(&self) -> bool {
+    matches!(self, VMValue::Null | VMValue::Boolean(false))
+  }
+}
+
+/**
+  Objects are compared by identity, strings are interned so two equal
+  strings are always the same object.
+*/
+impl PartialEq for VMValue {
+  fn eq(&self, other: &Self) -> bool {
+    match (self, other) {
+      (VMValue::Int(a), VMValue::Int(b)) => a == b,
+      (VMValue::Float(a), VMValue::Float(b)) => a == b,
+      (VMValue::Int(a), VMValue::Float(b)) | (VMValue::Float(b), VMValue::Int(a)) => {
+        *a as f64 == *b
+      }
+      (VMValue::Boolean(a), VMValue::Boolean(b)) => a == b,
+      (VMValue::Object(a), VMValue::Object(b)) => a == b,
+      (VMValue::Null, VMValue::Null) => true,
+      _ => false,
+    }
+  }
+}
+
+impl Display for VMValue {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      VMValue::Int(int) => write!(f, "{}", int),
+      VMValue::Float(float) => write!(f, "{}", float),
+      VMValue::Boolean(boolean) => write!(f, "{}", boolean),
+      VMValue::Object(reference) => write!(f, "<object {}>", reference.0),
+      VMValue::Null => write!(f, "null"),
+    }
+  }
+}